@@ -5,9 +5,17 @@
 use anyhow::Result;
 use serde::{Deserialize, Serialize};
 use serde_json::{json, Value};
-use std::io::{self, BufRead, Write};
+use std::collections::HashMap;
+use std::fs;
+use std::io::{self, BufRead, IsTerminal, Read, Write};
+use std::path::{Path, PathBuf};
+use tauri_code_base_analyzer::tool_execution::codebase::enrichment::{load_entities, StaticAnalyzer};
 use tauri_code_base_analyzer::tool_execution::codebase::{
-    EnrichmentConfig, EnrichmentOrchestrator, FileWalker,
+    analyze_impact, check_architecture, extract_routes, find_dead_code, git_diff,
+    parse_unified_diff, resolve_entity_fuzzy, ArchitectureReport, CodeEntity, Confidence,
+    DeadCodeReport, DependencyGraph, Direction, EdgeKind, EnrichmentConfig,
+    EnrichmentOrchestrator, FileWalker, ImpactReport, ProjectConfig, RouteMap,
+    PROJECT_CONFIG_FILE,
 };
 
 /// MCP 协议请求
@@ -48,7 +56,7 @@ fn get_tools() -> Vec<Tool> {
     vec![
         Tool {
             name: "scan_project".to_string(),
-            description: "扫描项目目录，提取所有代码实体（Vue组件、TypeScript函数/类等）".to_string(),
+            description: "扫描项目目录，提取所有代码实体（Vue组件、TypeScript函数/类等）。重复扫描时只重新提取变化的文件，并返回新增/删除/变化的实体；build_graph 为 true 时同时静态分析构建依赖图（graph_file）".to_string(),
             input_schema: json!({
                 "type": "object",
                 "properties": {
@@ -59,8 +67,11 @@ fn get_tools() -> Vec<Tool> {
                     "extensions": {
                         "type": "array",
                         "items": { "type": "string" },
-                        "description": "要扫描的文件扩展名，默认 ['.ts', '.tsx', '.vue']",
-                        "default": [".ts", ".tsx", ".vue"]
+                        "description": "要扫描的文件扩展名，默认读取项目 codebase.config.json，未配置时为 ['.vue', '.ts', '.tsx', '.js', '.jsx', '.mjs', '.cjs']"
+                    },
+                    "build_graph": {
+                        "type": "boolean",
+                        "description": "是否静态分析全部实体构建依赖图并保存到实体文件旁（需要解析全部文件，大项目耗时较长）。默认 false"
                     }
                 },
                 "required": ["project_path"]
@@ -68,13 +79,13 @@ fn get_tools() -> Vec<Tool> {
         },
         Tool {
             name: "analyze_entity".to_string(),
-            description: "分析特定代码实体的依赖关系、调用关系和事件".to_string(),
+            description: "分析特定代码实体：返回导入、调用、事件、模板组件、注释、代码片段以及依赖它的实体。基于最近一次 scan_project 保存的实体".to_string(),
             input_schema: json!({
                 "type": "object",
                 "properties": {
                     "entity_id": {
                         "type": "string",
-                        "description": "实体ID，格式如 'Component:Header' 或 'Function:getUserData'"
+                        "description": "实体ID，可以是规范 ID、'路径#短ID' 或短 ID（如 'Component:Header'、'Function:getUserData'），找不到时按实体名模糊匹配"
                     },
                     "project_path": {
                         "type": "string",
@@ -94,6 +105,10 @@ fn get_tools() -> Vec<Tool> {
                         "type": "string",
                         "description": "实体JSON文件的绝对路径（由 scan_project 生成）"
                     },
+                    "project_path": {
+                        "type": "string",
+                        "description": "项目根目录，默认从实体文件所在目录向上查找包含 codebase.config.json 或 package.json 的目录"
+                    },
                     "output_path": {
                         "type": "string",
                         "description": "富化后输出的JSON文件路径，默认为 'entities.enriched.json'"
@@ -102,16 +117,540 @@ fn get_tools() -> Vec<Tool> {
                         "type": "number",
                         "description": "并发数，默认为5",
                         "default": 5
+                    },
+                    "resume": {
+                        "type": "boolean",
+                        "description": "从检查点恢复：跳过上次运行中已由 LLM 完成标注且未变化的实体（回退标注的实体会重新处理）。默认 false（清空检查点重新开始）"
+                    },
+                    "checkpoint_path": {
+                        "type": "string",
+                        "description": "检查点文件路径（JSONL），默认为输出文件旁的 '<输出文件名>.checkpoint.jsonl'"
+                    },
+                    "use_cache": {
+                        "type": "boolean",
+                        "description": "是否使用富化缓存：代码与静态上下文未变化的实体复用上次的摘要和标签，不再调用 LLM。默认 true"
                     }
                 },
                 "required": ["entities_json_path"]
             }),
         },
+        Tool {
+            name: "query_dependencies".to_string(),
+            description: "查询依赖图：谁导入/调用/渲染了某个实体，或它依赖了谁；transitive 为 true 时返回传递闭包，用于重构前的影响分析".to_string(),
+            input_schema: json!({
+                "type": "object",
+                "properties": {
+                    "graph_path": {
+                        "type": "string",
+                        "description": "依赖图文件路径（scan_project 或 enrich_code 返回的 graph_file）"
+                    },
+                    "entity_id": {
+                        "type": "string",
+                        "description": "实体ID，可以是规范 ID、'路径#短ID' 或短 ID（如 'Function:getUserData'）"
+                    },
+                    "direction": {
+                        "type": "string",
+                        "enum": ["dependents", "dependencies"],
+                        "description": "dependents: 依赖该实体的实体；dependencies: 该实体依赖的实体。默认 dependents"
+                    },
+                    "transitive": {
+                        "type": "boolean",
+                        "description": "是否返回传递闭包，默认 false"
+                    },
+                    "max_depth": {
+                        "type": "number",
+                        "description": "传递查询的最大跳数，默认不限制"
+                    },
+                    "kinds": {
+                        "type": "array",
+                        "items": { "type": "string", "enum": ["imports", "calls", "renders"] },
+                        "description": "只沿这些依赖类型查询，默认全部"
+                    }
+                },
+                "required": ["graph_path", "entity_id"]
+            }),
+        },
+        Tool {
+            name: "analyze_impact".to_string(),
+            description: "变更影响分析：把 git diff 变化的行映射到实体，沿依赖图反向查找受影响的页面、组件和 store 及距离。可用于评审和确定测试范围".to_string(),
+            input_schema: json!({
+                "type": "object",
+                "properties": {
+                    "project_path": {
+                        "type": "string",
+                        "description": "项目根目录路径（需要先执行 scan_project）"
+                    },
+                    "range": {
+                        "type": "string",
+                        "description": "git 修订范围，如 'main...HEAD'、'HEAD~3'；在 project_path 下执行 git diff，默认对比工作区（含暂存区）与 HEAD"
+                    },
+                    "diff": {
+                        "type": "string",
+                        "description": "unified diff 文本，提供时不再调用 git"
+                    },
+                    "patch_file": {
+                        "type": "string",
+                        "description": "补丁文件路径，提供时不再调用 git"
+                    },
+                    "graph_path": {
+                        "type": "string",
+                        "description": "依赖图文件路径（scan_project 或 enrich_code 返回的 graph_file），默认对已扫描实体做静态分析构建"
+                    },
+                    "max_depth": {
+                        "type": "number",
+                        "description": "反向遍历的最大跳数，默认不限制"
+                    }
+                },
+                "required": ["project_path"]
+            }),
+        },
+        Tool {
+            name: "check_architecture".to_string(),
+            description: "架构检查：检测文件之间、包之间的循环导入，并按 codebase.config.json 中的 architecture.rules 校验分层规则，结果可输出为 JSON 或 SARIF".to_string(),
+            input_schema: json!({
+                "type": "object",
+                "properties": {
+                    "project_path": {
+                        "type": "string",
+                        "description": "项目根目录路径"
+                    },
+                    "format": {
+                        "type": "string",
+                        "enum": ["json", "sarif"],
+                        "description": "输出格式，默认 json"
+                    },
+                    "output_path": {
+                        "type": "string",
+                        "description": "报告输出文件路径，提供时写入文件"
+                    }
+                },
+                "required": ["project_path"]
+            }),
+        },
+        Tool {
+            name: "find_dead_code".to_string(),
+            description: "死代码检测：找出没有被其他实体导入、渲染或调用的导出函数、组件、组合式函数和 store，按置信度（high/medium/low）分级，考虑入口文件、路由页面、动态导入和 workspace 包的公开入口".to_string(),
+            input_schema: json!({
+                "type": "object",
+                "properties": {
+                    "project_path": {
+                        "type": "string",
+                        "description": "项目根目录路径"
+                    },
+                    "min_confidence": {
+                        "type": "string",
+                        "enum": ["high", "medium", "low"],
+                        "description": "只返回不低于该置信度的结果，默认 low（全部）"
+                    },
+                    "output_path": {
+                        "type": "string",
+                        "description": "报告输出文件路径，提供时写入文件"
+                    }
+                },
+                "required": ["project_path"]
+            }),
+        },
+        Tool {
+            name: "get_routes".to_string(),
+            description: "路由表：提取 Vue Router / React Router 配置中的路由树（路径、名称、meta、懒加载）并关联页面组件实体；提供 component 时返回显示该组件的 URL".to_string(),
+            input_schema: json!({
+                "type": "object",
+                "properties": {
+                    "project_path": {
+                        "type": "string",
+                        "description": "项目根目录路径"
+                    },
+                    "component": {
+                        "type": "string",
+                        "description": "组件实体 ID 或名称（支持模糊匹配），只返回显示该组件的路由"
+                    },
+                    "output_path": {
+                        "type": "string",
+                        "description": "路由表输出文件路径，提供时写入文件"
+                    }
+                },
+                "required": ["project_path"]
+            }),
+        },
+        Tool {
+            name: "find_dependency_path".to_string(),
+            description: "在依赖图中查找 from 依赖到 to 的最短路径".to_string(),
+            input_schema: json!({
+                "type": "object",
+                "properties": {
+                    "graph_path": {
+                        "type": "string",
+                        "description": "依赖图文件路径（scan_project 或 enrich_code 返回的 graph_file）"
+                    },
+                    "from": {
+                        "type": "string",
+                        "description": "起点实体ID"
+                    },
+                    "to": {
+                        "type": "string",
+                        "description": "终点实体ID"
+                    },
+                    "kinds": {
+                        "type": "array",
+                        "items": { "type": "string", "enum": ["imports", "calls", "renders"] },
+                        "description": "只沿这些依赖类型查找，默认全部"
+                    }
+                },
+                "required": ["graph_path", "from", "to"]
+            }),
+        },
     ]
 }
 
+/// 读取依赖图参数
+fn load_graph(arguments: &Value) -> Result<DependencyGraph> {
+    let graph_path = arguments["graph_path"]
+        .as_str()
+        .ok_or_else(|| anyhow::anyhow!("缺少 graph_path 参数"))?;
+    DependencyGraph::load(Path::new(graph_path))
+}
+
+/// 读取 kinds 参数，未提供时为空（表示全部类型）
+fn edge_kinds(arguments: &Value) -> Result<Vec<EdgeKind>> {
+    if arguments["kinds"].is_null() {
+        return Ok(Vec::new());
+    }
+    serde_json::from_value(arguments["kinds"].clone())
+        .map_err(|e| anyhow::anyhow!("kinds 参数无效: {}", e))
+}
+
+/// 从实体文件所在目录向上查找项目根目录（包含 codebase.config.json 或 package.json 的目录），
+/// 实体文件可能位于任意配置的输出目录中
+fn infer_project_root(entities_json_path: &Path) -> Result<PathBuf> {
+    entities_json_path
+        .ancestors()
+        .skip(1)
+        .find(|dir| ProjectConfig::path_for(dir).exists() || dir.join("package.json").exists())
+        .map(Path::to_path_buf)
+        .ok_or_else(|| {
+            anyhow::anyhow!(
+                "无法推断项目路径: {} 的上级目录中没有 {} 或 package.json，请传入 project_path",
+                entities_json_path.display(),
+                PROJECT_CONFIG_FILE
+            )
+        })
+}
+
+/// 加载项目最近一次扫描保存的实体
+fn load_latest_entities(project_path: &str) -> Result<(PathBuf, Vec<CodeEntity>)> {
+    let project_config = ProjectConfig::load(project_path)?;
+    let entities_file =
+        FileWalker::latest_saved_entities(project_path, Some(project_config.output_dir()))
+            .ok_or_else(|| anyhow::anyhow!("未找到已保存的实体，请先执行 scan_project"))?;
+    let entities = load_entities(&entities_file, None)?;
+    Ok((entities_file, entities))
+}
+
+/// 对 diff 做影响分析
+///
+/// 提供 `graph_path` 时使用已保存的依赖图，否则对最近一次扫描的实体做静态分析构建依赖图
+async fn impact_report(
+    project_path: &str,
+    diff: &str,
+    graph_path: Option<&str>,
+    max_depth: Option<usize>,
+) -> Result<ImpactReport> {
+    let (_, entities) = load_latest_entities(project_path)?;
+    let graph = match graph_path {
+        Some(graph_path) => DependencyGraph::load(Path::new(graph_path))?,
+        None => {
+            StaticAnalyzer::new(project_path, entities.clone())
+                .build_dependency_graph()
+                .await?
+        }
+    };
+
+    Ok(analyze_impact(&entities, &graph, parse_unified_diff(diff), max_depth))
+}
+
+/// 架构检查：重新扫描项目（包括不产出实体的 barrel 文件），按 codebase.config.json 的 `architecture` 配置检查循环依赖与分层规则
+fn architecture_report(project_path: &str) -> Result<ArchitectureReport> {
+    let project_config = ProjectConfig::load(project_path)?;
+    let walker = FileWalker::new(project_config.scan_config());
+    let workspace = walker.resolve_workspace(Path::new(project_path));
+    let (entities, _, manifest) = walker.extract_with_workspace(project_path, None, workspace.as_ref())?;
+    let files: Vec<String> = manifest.files.keys().cloned().collect();
+
+    let imports = StaticAnalyzer::with_workspace(project_path, entities, workspace.as_ref()).file_imports(&files);
+    check_architecture(Path::new(project_path), &imports, &project_config.architecture)
+}
+
+/// 死代码检测：重新扫描项目（包括不产出实体的入口文件），只保留不低于 `min_confidence` 的结果
+async fn dead_code_report(project_path: &str, min_confidence: Confidence) -> Result<DeadCodeReport> {
+    let project_config = ProjectConfig::load(project_path)?;
+    let walker = FileWalker::new(project_config.scan_config());
+    let workspace = walker.resolve_workspace(Path::new(project_path));
+    let (entities, _, manifest) = walker.extract_with_workspace(project_path, None, workspace.as_ref())?;
+    let files: Vec<String> = manifest.files.keys().cloned().collect();
+
+    let mut report = find_dead_code(Path::new(project_path), entities, &files, workspace.as_ref()).await?;
+    report.unused.retain(|u| u.confidence <= min_confidence);
+    report.by_confidence.retain(|confidence, _| *confidence <= min_confidence);
+    Ok(report)
+}
+
+/// 路由表：重新扫描项目（路由配置文件通常不产出实体）
+fn route_map(project_path: &str) -> Result<(Vec<CodeEntity>, RouteMap)> {
+    let project_config = ProjectConfig::load(project_path)?;
+    let walker = FileWalker::new(project_config.scan_config());
+    let workspace = walker.resolve_workspace(Path::new(project_path));
+    let (entities, _, manifest) = walker.extract_with_workspace(project_path, None, workspace.as_ref())?;
+    let files: Vec<String> = manifest.files.keys().cloned().collect();
+
+    let routes = extract_routes(Path::new(project_path), entities.clone(), &files, workspace.as_ref());
+    Ok((entities, routes))
+}
+
+/// 显示匹配组件的路由：按 ID 或名称模糊匹配组件，每个候选列出其完整路径
+fn component_routes(entities: &[CodeEntity], routes: &RouteMap, component: &str) -> Value {
+    let candidates = resolve_entity_fuzzy(entities, component);
+    let matches: Vec<Value> = candidates
+        .iter()
+        .map(|entity| {
+            json!({
+                "component": entity.id,
+                "file": entity.file,
+                "routes": routes.routes_for_component(&entity.id).iter().map(|r| json!({
+                    "full_path": r.full_path,
+                    "name": r.name,
+                    "meta": r.meta,
+                    "file": r.file,
+                    "line": r.line
+                })).collect::<Vec<_>>()
+            })
+        })
+        .collect();
+
+    json!({
+        "success": !matches.is_empty(),
+        "component": component,
+        "matches": matches
+    })
+}
+
+/// 命令行模式：`codebase-mcp-server routes <project_path> [--component <name>] [--output <file>]`
+fn run_routes_cli(args: &[String]) -> Result<()> {
+    let mut positional = Vec::new();
+    let mut options: HashMap<&str, &str> = HashMap::new();
+    let mut iter = args.iter();
+    while let Some(arg) = iter.next() {
+        match arg.as_str() {
+            "--component" | "--output" => {
+                let value = iter
+                    .next()
+                    .ok_or_else(|| anyhow::anyhow!("{} 缺少参数值", arg))?;
+                options.insert(arg.as_str(), value.as_str());
+            }
+            _ => positional.push(arg.as_str()),
+        }
+    }
+
+    let project_path = positional.first().ok_or_else(|| {
+        anyhow::anyhow!("用法: codebase-mcp-server routes <project_path> [--component <name>] [--output <file>]")
+    })?;
+    let (entities, routes) = route_map(project_path)?;
+
+    eprintln!("🧭 路由表: {} 个文件, {} 条路由", routes.files.len(), routes.len());
+
+    let json = match options.get("--component") {
+        Some(component) => serde_json::to_string_pretty(&component_routes(&entities, &routes, component))?,
+        None => serde_json::to_string_pretty(&routes)?,
+    };
+    match options.get("--output") {
+        Some(output) => {
+            fs::write(output, json)?;
+            eprintln!("💾 路由表已保存到: {}", output);
+        }
+        None => println!("{}", json),
+    }
+
+    Ok(())
+}
+
+/// 解析置信度参数，未提供时为 low（全部结果）
+fn parse_confidence(value: Option<&str>) -> Result<Confidence> {
+    match value {
+        None => Ok(Confidence::Low),
+        Some(value) => serde_json::from_value(json!(value))
+            .map_err(|_| anyhow::anyhow!("置信度参数无效: {}（可选 high、medium、low）", value)),
+    }
+}
+
+/// 命令行模式：`codebase-mcp-server dead-code <project_path> [--min-confidence high|medium|low] [--output <file>]`
+async fn run_dead_code_cli(args: &[String]) -> Result<()> {
+    let mut positional = Vec::new();
+    let mut options: HashMap<&str, &str> = HashMap::new();
+    let mut iter = args.iter();
+    while let Some(arg) = iter.next() {
+        match arg.as_str() {
+            "--min-confidence" | "--output" => {
+                let value = iter
+                    .next()
+                    .ok_or_else(|| anyhow::anyhow!("{} 缺少参数值", arg))?;
+                options.insert(arg.as_str(), value.as_str());
+            }
+            _ => positional.push(arg.as_str()),
+        }
+    }
+
+    let project_path = positional.first().ok_or_else(|| {
+        anyhow::anyhow!("用法: codebase-mcp-server dead-code <project_path> [--min-confidence high|medium|low] [--output <file>]")
+    })?;
+    let min_confidence = parse_confidence(options.get("--min-confidence").copied())?;
+    let report = dead_code_report(project_path, min_confidence).await?;
+
+    eprintln!("🧹 死代码检测: {} 个未使用的实体", report.unused.len());
+    for (confidence, count) in &report.by_confidence {
+        eprintln!("   - {:?}: {}", confidence, count);
+    }
+
+    let json = serde_json::to_string_pretty(&report)?;
+    match options.get("--output") {
+        Some(output) => {
+            fs::write(output, json)?;
+            eprintln!("💾 死代码检测报告已保存到: {}", output);
+        }
+        None => println!("{}", json),
+    }
+
+    Ok(())
+}
+
+/// 命令行模式：`codebase-mcp-server check <project_path> [--format json|sarif] [--output <file>]`
+///
+/// 存在 error 级别的问题时以退出码 1 结束，用于合并前检查
+fn run_check_cli(args: &[String]) -> Result<()> {
+    let mut positional = Vec::new();
+    let mut options: HashMap<&str, &str> = HashMap::new();
+    let mut iter = args.iter();
+    while let Some(arg) = iter.next() {
+        match arg.as_str() {
+            "--format" | "--output" => {
+                let value = iter
+                    .next()
+                    .ok_or_else(|| anyhow::anyhow!("{} 缺少参数值", arg))?;
+                options.insert(arg.as_str(), value.as_str());
+            }
+            _ => positional.push(arg.as_str()),
+        }
+    }
+
+    let project_path = positional.first().ok_or_else(|| {
+        anyhow::anyhow!("用法: codebase-mcp-server check <project_path> [--format json|sarif] [--output <file>]")
+    })?;
+    let report = architecture_report(project_path)?;
+
+    eprintln!(
+        "🏛️  架构检查: {} 个循环依赖, {} 个分层规则违规 ({} error, {} warning)",
+        report.cycles.len(),
+        report.violations.len(),
+        report.errors,
+        report.warnings
+    );
+
+    let json = match options.get("--format").copied().unwrap_or("json") {
+        "sarif" => serde_json::to_string_pretty(&report.to_sarif())?,
+        "json" => serde_json::to_string_pretty(&report)?,
+        other => return Err(anyhow::anyhow!("不支持的输出格式: {}", other)),
+    };
+    match options.get("--output") {
+        Some(output) => {
+            fs::write(output, json)?;
+            eprintln!("💾 架构检查报告已保存到: {}", output);
+        }
+        None => println!("{}", json),
+    }
+
+    if report.has_errors() {
+        std::process::exit(1);
+    }
+    Ok(())
+}
+
+/// 命令行模式：`codebase-mcp-server impact <project_path> [range] [--patch <file>] [--graph <file>] [--max-depth <n>] [--output <file>]`
+///
+/// 未指定 range 和 --patch 时从标准输入读取 unified diff，
+/// 标准输入为终端或为空时对比工作区与 HEAD
+async fn run_impact_cli(args: &[String]) -> Result<()> {
+    let mut positional = Vec::new();
+    let mut options: HashMap<&str, &str> = HashMap::new();
+    let mut iter = args.iter();
+    while let Some(arg) = iter.next() {
+        match arg.as_str() {
+            "--patch" | "--graph" | "--max-depth" | "--output" => {
+                let value = iter
+                    .next()
+                    .ok_or_else(|| anyhow::anyhow!("{} 缺少参数值", arg))?;
+                options.insert(arg.as_str(), value.as_str());
+            }
+            _ => positional.push(arg.as_str()),
+        }
+    }
+
+    let project_path = positional.first().ok_or_else(|| {
+        anyhow::anyhow!("用法: codebase-mcp-server impact <project_path> [range] [--patch <file>] [--graph <file>] [--max-depth <n>] [--output <file>]")
+    })?;
+
+    let diff = if let Some(range) = positional.get(1) {
+        git_diff(Path::new(project_path), range)?
+    } else if let Some(patch) = options.get("--patch") {
+        fs::read_to_string(patch).map_err(|e| anyhow::anyhow!("无法读取补丁文件 {}: {}", patch, e))?
+    } else {
+        let mut diff = String::new();
+        if !io::stdin().is_terminal() {
+            io::stdin().read_to_string(&mut diff)?;
+        }
+        if diff.trim().is_empty() {
+            git_diff(Path::new(project_path), "")?
+        } else {
+            diff
+        }
+    };
+
+    let max_depth = options
+        .get("--max-depth")
+        .map(|d| d.parse::<usize>())
+        .transpose()
+        .map_err(|e| anyhow::anyhow!("--max-depth 参数无效: {}", e))?;
+    let report = impact_report(project_path, &diff, options.get("--graph").copied(), max_depth).await?;
+
+    eprintln!(
+        "📊 影响分析: {} 个实体被修改，{} 个实体受影响",
+        report.changed.len(),
+        report.affected.len()
+    );
+    for (category, count) in &report.by_category {
+        eprintln!("   - {:?}: {}", category, count);
+    }
+
+    let json = serde_json::to_string_pretty(&report)?;
+    match options.get("--output") {
+        Some(output) => {
+            fs::write(output, json)?;
+            eprintln!("💾 影响分析报告已保存到: {}", output);
+        }
+        None => println!("{}", json),
+    }
+
+    Ok(())
+}
+
 #[tokio::main]
 async fn main() -> Result<()> {
+    let args: Vec<String> = std::env::args().collect();
+    match args.get(1).map(String::as_str) {
+        Some("impact") => return run_impact_cli(&args[2..]).await,
+        Some("check") => return run_check_cli(&args[2..]),
+        Some("dead-code") => return run_dead_code_cli(&args[2..]).await,
+        Some("routes") => return run_routes_cli(&args[2..]),
+        _ => {}
+    }
+
     eprintln!("🚀 Codebase MCP 服务器启动");
     eprintln!("📡 监听 stdio 协议...\n");
 
@@ -228,13 +767,36 @@ async fn execute_tool(tool_name: &str, arguments: &Value) -> Result<Value> {
 
             eprintln!("📂 扫描项目: {}", project_path);
 
-            let walker = FileWalker::with_default();
+            // 读取项目配置（codebase.config.json），调用参数优先
+            let project_config = ProjectConfig::load(project_path)?;
+            let mut scan_config = project_config.scan_config();
+            if let Some(extensions) = arguments["extensions"].as_array() {
+                scan_config.extensions = extensions
+                    .iter()
+                    .filter_map(|v| v.as_str().map(|s| s.to_string()))
+                    .collect();
+            }
+
+            let walker = FileWalker::new(scan_config);
             let (entities, stats, file_path) = walker
-                .scan_and_save(project_path, Some("src/data"))
+                .scan_and_save(project_path, Some(project_config.output_dir()))
                 .map_err(|e| anyhow::anyhow!("扫描失败: {}", e))?;
 
             eprintln!("✅ 扫描完成: {} 个实体", entities.len());
 
+            // 按需静态分析构建依赖图（需要解析全部文件），失败不影响扫描结果
+            let graph_path = if arguments["build_graph"].as_bool().unwrap_or(false) {
+                match DependencyGraph::build_and_save(Path::new(project_path), entities.clone(), &file_path).await {
+                    Ok(path) => Some(path.to_string_lossy().to_string()),
+                    Err(e) => {
+                        eprintln!("⚠️  构建依赖图失败: {}", e);
+                        None
+                    }
+                }
+            } else {
+                None
+            };
+
             Ok(json!({
                 "success": true,
                 "entities_count": entities.len(),
@@ -242,11 +804,15 @@ async fn execute_tool(tool_name: &str, arguments: &Value) -> Result<Value> {
                     "total_files": stats.total_files,
                     "success_files": stats.success_files,
                     "failed_files": stats.failed_files,
+                    "unchanged_files": stats.unchanged_files,
+                    "removed_files": stats.removed_files,
                     "duration_ms": stats.duration_ms,
                     "by_extension": stats.by_extension,
                     "by_entity_type": stats.by_entity_type
                 },
-                "output_file": file_path.to_string_lossy().to_string()
+                "changes": stats.changes,
+                "output_file": file_path.to_string_lossy().to_string(),
+                "graph_file": graph_path
             }))
         }
 
@@ -260,13 +826,68 @@ async fn execute_tool(tool_name: &str, arguments: &Value) -> Result<Value> {
 
             eprintln!("🔍 分析实体: {}", entity_id);
 
-            // TODO: 实现实体分析逻辑
-            // 这里需要加载实体，然后使用 StaticAnalyzer 分析
+            let (entities_file, entities) = load_latest_entities(project_path)?;
+
+            // 按 ID 查找，找不到时按实体名模糊匹配
+            let candidates = resolve_entity_fuzzy(&entities, entity_id);
+            let entity = match candidates.as_slice() {
+                [] => return Err(anyhow::anyhow!("未找到实体: {}", entity_id)),
+                [entity] => (*entity).clone(),
+                _ => {
+                    return Ok(json!({
+                        "success": false,
+                        "message": format!("实体 {} 匹配到 {} 个候选，请使用规范 ID 重新查询", entity_id, candidates.len()),
+                        "candidates": candidates.iter().map(|e| json!({
+                            "id": e.id,
+                            "type": e.entity_type,
+                            "file": e.file,
+                            "raw_name": e.raw_name
+                        })).collect::<Vec<_>>()
+                    }));
+                }
+            };
+
+            // workspace 只解析一次，分析器与路由提取共用
+            let project_config = ProjectConfig::load(project_path)?;
+            let walker = FileWalker::new(project_config.scan_config());
+            let workspace = walker.resolve_workspace(Path::new(project_path));
+
+            let analyzer = StaticAnalyzer::with_workspace(project_path, entities.clone(), workspace.as_ref());
+            let analysis = analyzer.analyze_entity(&entity).await?;
+            let code = analyzer.code_snippet(&entity)?;
+            let dependents = analyzer.find_dependents(&entity).await?;
+            // 路由配置文件通常不产出实体，使用扫描清单中的全部文件
+            let files: Vec<String> = FileWalker::saved_manifest(project_path, Some(project_config.output_dir()))
+                .map(|manifest| manifest.files.into_keys().collect())
+                .unwrap_or_default();
+            let routes = extract_routes(Path::new(project_path), entities.clone(), &files, workspace.as_ref());
+            let urls: Vec<&str> = routes
+                .routes_for_component(&entity.id)
+                .iter()
+                .map(|r| r.full_path.as_str())
+                .collect();
+
+            eprintln!(
+                "✅ 分析完成: {} 个导入, {} 个调用, {} 个依赖方",
+                analysis.imports.len(),
+                analysis.calls.len(),
+                dependents.len()
+            );
 
             Ok(json!({
                 "success": true,
-                "message": "实体分析功能待实现",
-                "entity_id": entity_id
+                "entity": entity,
+                "imports": analysis.imports,
+                "calls": analysis.calls,
+                "emits": analysis.emits,
+                "template_components": analysis.template_components,
+                "template_usages": analysis.template_usages,
+                "store_usages": analysis.store_usages,
+                "routes": urls,
+                "annotation": analysis.annotation,
+                "code": code,
+                "dependents": dependents,
+                "entities_file": entities_file.to_string_lossy().to_string()
             }))
         }
 
@@ -278,15 +899,17 @@ async fn execute_tool(tool_name: &str, arguments: &Value) -> Result<Value> {
                 .as_str()
                 .unwrap_or("entities.enriched.json");
             let concurrency = arguments["concurrency"].as_u64().unwrap_or(5) as usize;
+            let resume = arguments["resume"].as_bool().unwrap_or(false);
 
-            eprintln!("✨ 富化代码: {} (并发: {})", entities_json_path, concurrency);
+            eprintln!(
+                "✨ 富化代码: {} (并发: {}, 恢复: {})",
+                entities_json_path, concurrency, resume
+            );
 
-            // 提取项目根目录
-            let project_path = std::path::Path::new(entities_json_path)
-                .parent()
-                .and_then(|p| p.parent())
-                .and_then(|p| p.parent())
-                .ok_or_else(|| anyhow::anyhow!("无法推断项目路径"))?;
+            let project_path = match arguments["project_path"].as_str() {
+                Some(project_path) => PathBuf::from(project_path),
+                None => infer_project_root(Path::new(entities_json_path))?,
+            };
 
             let config = EnrichmentConfig {
                 concurrency,
@@ -295,6 +918,11 @@ async fn execute_tool(tool_name: &str, arguments: &Value) -> Result<Value> {
                 input_path: entities_json_path.to_string(),
                 output_path: output_path.to_string(),
                 pre_initialize: false,
+                checkpoint_path: arguments["checkpoint_path"].as_str().map(String::from),
+                resume,
+                use_cache: arguments["use_cache"].as_bool().unwrap_or(true),
+                cache_path: None,
+                llm: None,
             };
 
             let mut orchestrator = EnrichmentOrchestrator::new(
@@ -312,7 +940,184 @@ async fn execute_tool(tool_name: &str, arguments: &Value) -> Result<Value> {
 
             Ok(json!({
                 "success": true,
-                "output_file": enriched_path
+                "output_file": enriched_path,
+                "resumed": orchestrator.resumed_count(),
+                "cache": orchestrator.cache_stats(),
+                "label_sources": orchestrator.label_stats(),
+                "tag_index_file": orchestrator.tag_index_path().map(|p| p.to_string_lossy().to_string()),
+                "checkpoint_file": orchestrator.checkpoint_path().map(|p| p.to_string_lossy().to_string()),
+                "graph_file": DependencyGraph::path_for(Path::new(&enriched_path)).to_string_lossy().to_string()
+            }))
+        }
+
+        "query_dependencies" => {
+            let graph = load_graph(arguments)?;
+            let entity_id = arguments["entity_id"]
+                .as_str()
+                .ok_or_else(|| anyhow::anyhow!("缺少 entity_id 参数"))?;
+            let direction: Direction = match arguments["direction"].as_str() {
+                Some(direction) => serde_json::from_value(json!(direction))
+                    .map_err(|e| anyhow::anyhow!("direction 参数无效: {}", e))?,
+                None => Direction::Dependents,
+            };
+            let kinds = edge_kinds(arguments)?;
+            let id = graph.resolve_id(entity_id)?;
+
+            eprintln!("🕸️  查询依赖: {} ({:?})", id, direction);
+
+            if arguments["transitive"].as_bool().unwrap_or(false) {
+                let max_depth = arguments["max_depth"].as_u64().map(|d| d as usize);
+                let reached = graph.closure(&id, direction, &kinds, max_depth);
+                Ok(json!({
+                    "entity_id": id,
+                    "direction": direction,
+                    "count": reached.len(),
+                    "entities": reached
+                }))
+            } else {
+                let edges = match direction {
+                    Direction::Dependents => graph.dependents(&id, &kinds),
+                    Direction::Dependencies => graph.dependencies(&id, &kinds),
+                };
+                Ok(json!({
+                    "entity_id": id,
+                    "direction": direction,
+                    "count": edges.len(),
+                    "edges": edges
+                }))
+            }
+        }
+
+        "analyze_impact" => {
+            let project_path = arguments["project_path"]
+                .as_str()
+                .ok_or_else(|| anyhow::anyhow!("缺少 project_path 参数"))?;
+
+            let diff = if let Some(diff) = arguments["diff"].as_str() {
+                diff.to_string()
+            } else if let Some(patch_file) = arguments["patch_file"].as_str() {
+                fs::read_to_string(patch_file)
+                    .map_err(|e| anyhow::anyhow!("无法读取补丁文件 {}: {}", patch_file, e))?
+            } else {
+                git_diff(Path::new(project_path), arguments["range"].as_str().unwrap_or(""))?
+            };
+
+            eprintln!("📊 影响分析: {}", project_path);
+
+            let max_depth = arguments["max_depth"].as_u64().map(|d| d as usize);
+            let report =
+                impact_report(project_path, &diff, arguments["graph_path"].as_str(), max_depth).await?;
+
+            eprintln!(
+                "✅ 影响分析完成: {} 个实体被修改，{} 个实体受影响",
+                report.changed.len(),
+                report.affected.len()
+            );
+
+            Ok(serde_json::to_value(report)?)
+        }
+
+        "check_architecture" => {
+            let project_path = arguments["project_path"]
+                .as_str()
+                .ok_or_else(|| anyhow::anyhow!("缺少 project_path 参数"))?;
+
+            eprintln!("🏛️  架构检查: {}", project_path);
+
+            let report = architecture_report(project_path)?;
+            let output = match arguments["format"].as_str().unwrap_or("json") {
+                "sarif" => report.to_sarif(),
+                "json" => serde_json::to_value(&report)?,
+                other => return Err(anyhow::anyhow!("不支持的输出格式: {}", other)),
+            };
+
+            eprintln!(
+                "✅ 架构检查完成: {} error, {} warning",
+                report.errors, report.warnings
+            );
+
+            if let Some(output_path) = arguments["output_path"].as_str() {
+                fs::write(output_path, serde_json::to_string_pretty(&output)?)?;
+                return Ok(json!({
+                    "success": true,
+                    "errors": report.errors,
+                    "warnings": report.warnings,
+                    "output_file": output_path
+                }));
+            }
+            Ok(output)
+        }
+
+        "find_dead_code" => {
+            let project_path = arguments["project_path"]
+                .as_str()
+                .ok_or_else(|| anyhow::anyhow!("缺少 project_path 参数"))?;
+            let min_confidence = parse_confidence(arguments["min_confidence"].as_str())?;
+
+            eprintln!("🧹 死代码检测: {}", project_path);
+
+            let report = dead_code_report(project_path, min_confidence).await?;
+
+            eprintln!("✅ 死代码检测完成: {} 个未使用的实体", report.unused.len());
+
+            if let Some(output_path) = arguments["output_path"].as_str() {
+                fs::write(output_path, serde_json::to_string_pretty(&report)?)?;
+                return Ok(json!({
+                    "success": true,
+                    "unused": report.unused.len(),
+                    "by_confidence": report.by_confidence,
+                    "output_file": output_path
+                }));
+            }
+            Ok(serde_json::to_value(report)?)
+        }
+
+        "get_routes" => {
+            let project_path = arguments["project_path"]
+                .as_str()
+                .ok_or_else(|| anyhow::anyhow!("缺少 project_path 参数"))?;
+
+            eprintln!("🧭 提取路由表: {}", project_path);
+
+            let (entities, routes) = route_map(project_path)?;
+
+            eprintln!("✅ 路由表提取完成: {} 个文件, {} 条路由", routes.files.len(), routes.len());
+
+            if let Some(component) = arguments["component"].as_str() {
+                return Ok(component_routes(&entities, &routes, component));
+            }
+            if let Some(output_path) = arguments["output_path"].as_str() {
+                fs::write(output_path, serde_json::to_string_pretty(&routes)?)?;
+                return Ok(json!({
+                    "success": true,
+                    "files": routes.files,
+                    "routes": routes.len(),
+                    "output_file": output_path
+                }));
+            }
+            Ok(serde_json::to_value(routes)?)
+        }
+
+        "find_dependency_path" => {
+            let graph = load_graph(arguments)?;
+            let from = arguments["from"]
+                .as_str()
+                .ok_or_else(|| anyhow::anyhow!("缺少 from 参数"))?;
+            let to = arguments["to"]
+                .as_str()
+                .ok_or_else(|| anyhow::anyhow!("缺少 to 参数"))?;
+            let kinds = edge_kinds(arguments)?;
+            let (from, to) = (graph.resolve_id(from)?, graph.resolve_id(to)?);
+
+            eprintln!("🕸️  查找依赖路径: {} -> {}", from, to);
+
+            let path = graph.shortest_path(&from, &to, &kinds);
+            Ok(json!({
+                "from": from,
+                "to": to,
+                "found": path.is_some(),
+                "length": path.as_ref().map(|p| p.len()),
+                "path": path.unwrap_or_default()
             }))
         }
 
@@ -17,6 +17,10 @@ use precise_analyzer::{PreciseAnalysisResult, PreciseAnalyzer};
 use std::fs;
 use std::path::{Path, PathBuf};
 use tauri::Manager;
+use tool_execution::codebase::{
+    DependencyEdge, DependencyGraph, Direction, EdgeKind, FileWalker, ProjectConfig,
+    ReachedEntity, ScanStats,
+};
 
 /// 寻找项目根目录的package.json文件
 fn find_package_json(start_path: &Path) -> Option<PathBuf> {
@@ -106,6 +110,86 @@ async fn analyze_entities(repo_path: String) -> Result<Vec<CodeEntity>, String>
     }
 }
 
+/// 使用 FileWalker 扫描项目并保存实体，遵循项目的 codebase.config.json
+#[tauri::command]
+async fn scan_codebase(repo_path: String, build_graph: Option<bool>) -> Result<(ScanStats, String), String> {
+    // 验证路径是否存在
+    if !Path::new(&repo_path).exists() {
+        return Err("Directory does not exist".to_string());
+    }
+
+    let project_config = ProjectConfig::load(&repo_path)
+        .map_err(|e| format!("Failed to load project config: {}", e))?;
+    let walker = FileWalker::new(project_config.scan_config());
+
+    let (entities, stats, file_path) = walker
+        .scan_and_save(&repo_path, Some(project_config.output_dir()))
+        .map_err(|e| format!("Codebase scan failed: {}", e))?;
+
+    // 按需静态分析构建依赖图，失败不影响扫描结果
+    if build_graph.unwrap_or(false) {
+        if let Err(e) = DependencyGraph::build_and_save(Path::new(&repo_path), entities, &file_path).await {
+            eprintln!("⚠️  构建依赖图失败: {}", e);
+        }
+    }
+
+    Ok((stats, file_path.to_string_lossy().to_string()))
+}
+
+fn load_dependency_graph(graph_path: &str) -> Result<DependencyGraph, String> {
+    DependencyGraph::load(Path::new(graph_path))
+        .map_err(|e| format!("Failed to load dependency graph: {}", e))
+}
+
+/// 查询实体的直接依赖方或直接依赖；`kinds` 为空时包含全部依赖类型
+#[tauri::command]
+async fn query_dependency_edges(
+    graph_path: String,
+    entity_id: String,
+    direction: Direction,
+    kinds: Option<Vec<EdgeKind>>,
+) -> Result<Vec<DependencyEdge>, String> {
+    let graph = load_dependency_graph(&graph_path)?;
+    let id = graph.resolve_id(&entity_id).map_err(|e| e.to_string())?;
+    let kinds = kinds.unwrap_or_default();
+
+    let edges = match direction {
+        Direction::Dependents => graph.dependents(&id, &kinds),
+        Direction::Dependencies => graph.dependencies(&id, &kinds),
+    };
+    Ok(edges.into_iter().cloned().collect())
+}
+
+/// 查询实体的传递依赖方或传递依赖（影响分析）
+#[tauri::command]
+async fn query_dependency_closure(
+    graph_path: String,
+    entity_id: String,
+    direction: Direction,
+    kinds: Option<Vec<EdgeKind>>,
+    max_depth: Option<usize>,
+) -> Result<Vec<ReachedEntity>, String> {
+    let graph = load_dependency_graph(&graph_path)?;
+    let id = graph.resolve_id(&entity_id).map_err(|e| e.to_string())?;
+
+    Ok(graph.closure(&id, direction, &kinds.unwrap_or_default(), max_depth))
+}
+
+/// 查找 `from` 依赖到 `to` 的最短路径，不可达时返回 `None`
+#[tauri::command]
+async fn find_dependency_path(
+    graph_path: String,
+    from: String,
+    to: String,
+    kinds: Option<Vec<EdgeKind>>,
+) -> Result<Option<Vec<DependencyEdge>>, String> {
+    let graph = load_dependency_graph(&graph_path)?;
+    let from = graph.resolve_id(&from).map_err(|e| e.to_string())?;
+    let to = graph.resolve_id(&to).map_err(|e| e.to_string())?;
+
+    Ok(graph.shortest_path(&from, &to, &kinds.unwrap_or_default()))
+}
+
 #[tauri::command]
 async fn save_entities_json(
     entities: Vec<CodeEntity>,
@@ -237,6 +321,10 @@ pub fn run() {
         .invoke_handler(tauri::generate_handler![
             analyze_repository,
             analyze_entities,
+            scan_codebase,
+            query_dependency_edges,
+            query_dependency_closure,
+            find_dependency_path,
             save_entities_json,
             test_analyze_after_sale_demo,
             save_analysis_result,
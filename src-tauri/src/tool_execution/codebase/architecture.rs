@@ -0,0 +1,651 @@
+//! 架构检查：循环依赖与分层规则
+//!
+//! 基于 [`StaticAnalyzer::file_imports`](super::enrichment::StaticAnalyzer::file_imports)
+//! 收集的文件级导入关系：
+//!
+//! - 检测文件之间、包之间的导入循环（含 barrel 文件的 `export ... from`；`import type` 不参与循环检测）
+//! - 校验项目配置中声明的分层规则，如 "`components/` 不能导入 `views/`"、"包 A 不能依赖包 B"
+//!
+//! 结果可输出为 JSON 或 SARIF 2.1.0，供合并前检查使用。
+//!
+//! 配置写在 `codebase.config.json` 的 `architecture` 字段：
+//!
+//! ```json
+//! {
+//!   "architecture": {
+//!     "file_cycles": "warning",
+//!     "package_cycles": "error",
+//!     "rules": [
+//!       { "name": "components-no-views", "from": ["src/components/**"], "to": ["src/views/**"] },
+//!       { "from_package": "@acme/ui", "to_package": "@acme/order", "message": "UI 包不能依赖业务包" }
+//!     ]
+//!   }
+//! }
+//! ```
+//!
+//! 规则描述的是被禁止的依赖：导入方匹配 `from` 一侧且被导入方匹配 `to` 一侧即违规
+
+use super::enrichment::FileImport;
+use super::entity_id::PackageResolver;
+use anyhow::{bail, Context, Result};
+use globset::{Glob, GlobBuilder, GlobMatcher, GlobSet, GlobSetBuilder};
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use std::collections::{BTreeMap, BTreeSet, HashMap, VecDeque};
+use std::path::Path;
+
+/// 违规级别
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Severity {
+    #[default]
+    Error,
+    Warning,
+    /// 关闭检查
+    Off,
+}
+
+impl Severity {
+    /// SARIF `level`
+    fn sarif_level(self) -> &'static str {
+        match self {
+            Severity::Error => "error",
+            Severity::Warning => "warning",
+            Severity::Off => "none",
+        }
+    }
+}
+
+/// 架构检查配置
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ArchitectureConfig {
+    /// 文件循环依赖的级别，默认 warning
+    #[serde(default = "default_file_cycles")]
+    pub file_cycles: Severity,
+
+    /// 包循环依赖的级别，默认 error
+    #[serde(default)]
+    pub package_cycles: Severity,
+
+    /// 分层规则
+    #[serde(default)]
+    pub rules: Vec<LayerRule>,
+}
+
+fn default_file_cycles() -> Severity {
+    Severity::Warning
+}
+
+impl Default for ArchitectureConfig {
+    fn default() -> Self {
+        Self {
+            file_cycles: default_file_cycles(),
+            package_cycles: Severity::Error,
+            rules: Vec::new(),
+        }
+    }
+}
+
+/// 分层规则：禁止 `from` 一侧的文件导入 `to` 一侧的文件
+///
+/// 每一侧可以用文件 glob（相对项目根目录）、包名 glob 或两者组合描述，同时给出时需要都匹配
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct LayerRule {
+    /// 规则名，用作 SARIF ruleId，默认为 `layer-rule-<序号>`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+
+    /// 导入方文件 glob
+    #[serde(default)]
+    pub from: Vec<String>,
+
+    /// 被导入方文件 glob
+    #[serde(default)]
+    pub to: Vec<String>,
+
+    /// 导入方所属包（支持 glob，如 `@acme/*`）
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub from_package: Option<String>,
+
+    /// 被导入方所属包
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub to_package: Option<String>,
+
+    /// 违规级别
+    #[serde(default)]
+    pub severity: Severity,
+
+    /// 违规说明
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub message: Option<String>,
+}
+
+/// 循环的粒度
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum CycleKind {
+    File,
+    Package,
+}
+
+/// 导入循环
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ImportCycle {
+    pub kind: CycleKind,
+
+    pub severity: Severity,
+
+    /// 强连通分量中的全部文件 / 包
+    pub members: Vec<String>,
+
+    /// 一条具体的环路，依次经过的导入语句，最后一条回到起点
+    pub path: Vec<FileImport>,
+}
+
+/// 分层规则违规
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RuleViolation {
+    /// 规则名
+    pub rule: String,
+
+    pub severity: Severity,
+
+    pub message: String,
+
+    /// 导入方所属包
+    pub from_package: String,
+
+    /// 被导入方所属包
+    pub to_package: String,
+
+    /// 违规的导入语句（含文件、行号与实体 ID）
+    pub import: FileImport,
+}
+
+/// 架构检查报告
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ArchitectureReport {
+    pub cycles: Vec<ImportCycle>,
+
+    pub violations: Vec<RuleViolation>,
+
+    /// error 级别的问题数
+    pub errors: usize,
+
+    /// warning 级别的问题数
+    pub warnings: usize,
+}
+
+/// 编译后的规则一侧
+struct RuleSide {
+    paths: Option<GlobSet>,
+    package: Option<GlobMatcher>,
+}
+
+impl RuleSide {
+    fn new(paths: &[String], package: Option<&str>) -> Result<Self> {
+        let paths = if paths.is_empty() {
+            None
+        } else {
+            let mut builder = GlobSetBuilder::new();
+            for pattern in paths {
+                let glob = GlobBuilder::new(pattern.trim_start_matches("./"))
+                    .literal_separator(true)
+                    .build()
+                    .context(format!("无效的 glob: {}", pattern))?;
+                builder.add(glob);
+            }
+            Some(builder.build()?)
+        };
+        let package = package
+            .map(|p| Glob::new(p).map(|g| g.compile_matcher()))
+            .transpose()
+            .context("无效的包名 glob")?;
+
+        Ok(Self { paths, package })
+    }
+
+    fn is_empty(&self) -> bool {
+        self.paths.is_none() && self.package.is_none()
+    }
+
+    fn matches(&self, file: &str, package: &str) -> bool {
+        self.paths.as_ref().map(|set| set.is_match(file)).unwrap_or(true)
+            && self.package.as_ref().map(|m| m.is_match(package)).unwrap_or(true)
+    }
+}
+
+/// 执行架构检查
+pub fn check_architecture(
+    root: &Path,
+    imports: &[FileImport],
+    config: &ArchitectureConfig,
+) -> Result<ArchitectureReport> {
+    let mut packages = PackageResolver::new(root);
+    let mut package_of: HashMap<String, String> = HashMap::new();
+    for import in imports {
+        for file in [&import.from_file, &import.to_file] {
+            if !package_of.contains_key(file) {
+                let (package, _) = packages.resolve(&root.join(file));
+                package_of.insert(file.clone(), package);
+            }
+        }
+    }
+
+    let mut report = ArchitectureReport::default();
+
+    // 1. 循环依赖
+    let runtime: Vec<&FileImport> = imports.iter().filter(|i| !i.type_only).collect();
+    if config.file_cycles != Severity::Off {
+        let edges: Vec<(String, String, &FileImport)> = runtime
+            .iter()
+            .map(|i| (i.from_file.clone(), i.to_file.clone(), *i))
+            .collect();
+        report.cycles.extend(find_cycles(CycleKind::File, config.file_cycles, &edges));
+    }
+    if config.package_cycles != Severity::Off {
+        let edges: Vec<(String, String, &FileImport)> = runtime
+            .iter()
+            .map(|i| (package_of[&i.from_file].clone(), package_of[&i.to_file].clone(), *i))
+            .filter(|(from, to, _)| from != to)
+            .collect();
+        report.cycles.extend(find_cycles(CycleKind::Package, config.package_cycles, &edges));
+    }
+
+    // 2. 分层规则
+    for (index, rule) in config.rules.iter().enumerate() {
+        if rule.severity == Severity::Off {
+            continue;
+        }
+        let name = rule.name.clone().unwrap_or_else(|| format!("layer-rule-{}", index + 1));
+        let from = RuleSide::new(&rule.from, rule.from_package.as_deref())?;
+        let to = RuleSide::new(&rule.to, rule.to_package.as_deref())?;
+        if from.is_empty() || to.is_empty() {
+            bail!("分层规则 {} 需要同时声明导入方（from / from_package）和被导入方（to / to_package）", name);
+        }
+
+        for import in imports {
+            let from_package = &package_of[&import.from_file];
+            let to_package = &package_of[&import.to_file];
+            if !from.matches(&import.from_file, from_package) || !to.matches(&import.to_file, to_package) {
+                continue;
+            }
+
+            let message = rule.message.clone().unwrap_or_else(|| {
+                format!("{} 不允许导入 {}（规则 {}）", import.from_file, import.to_file, name)
+            });
+            report.violations.push(RuleViolation {
+                rule: name.clone(),
+                severity: rule.severity,
+                message,
+                from_package: from_package.clone(),
+                to_package: to_package.clone(),
+                import: import.clone(),
+            });
+        }
+    }
+
+    let severities = report
+        .cycles
+        .iter()
+        .map(|c| c.severity)
+        .chain(report.violations.iter().map(|v| v.severity));
+    for severity in severities {
+        match severity {
+            Severity::Error => report.errors += 1,
+            Severity::Warning => report.warnings += 1,
+            Severity::Off => {}
+        }
+    }
+
+    Ok(report)
+}
+
+/// 在有向图中找出全部强连通分量（Tarjan），每个分量给出一条最短环路
+fn find_cycles(kind: CycleKind, severity: Severity, edges: &[(String, String, &FileImport)]) -> Vec<ImportCycle> {
+    let mut adjacency: BTreeMap<&str, Vec<(&str, &FileImport)>> = BTreeMap::new();
+    for (from, to, import) in edges {
+        adjacency.entry(from).or_default().push((to, import));
+        adjacency.entry(to).or_default();
+    }
+
+    let mut cycles = Vec::new();
+    for component in strongly_connected(&adjacency) {
+        let self_loop = component.len() == 1
+            && adjacency[component[0]].iter().any(|(to, _)| *to == component[0]);
+        if component.len() < 2 && !self_loop {
+            continue;
+        }
+
+        let members: BTreeSet<&str> = component.iter().copied().collect();
+        let start = *members.iter().next().unwrap();
+        cycles.push(ImportCycle {
+            kind,
+            severity,
+            members: members.iter().map(|m| m.to_string()).collect(),
+            path: shortest_cycle(&adjacency, &members, start),
+        });
+    }
+
+    cycles
+}
+
+/// 迭代实现的 Tarjan 强连通分量算法
+fn strongly_connected<'a>(adjacency: &BTreeMap<&'a str, Vec<(&'a str, &FileImport)>>) -> Vec<Vec<&'a str>> {
+    let mut index: HashMap<&str, usize> = HashMap::new();
+    let mut low: HashMap<&str, usize> = HashMap::new();
+    let mut on_stack: HashMap<&str, bool> = HashMap::new();
+    let mut stack: Vec<&str> = Vec::new();
+    let mut components = Vec::new();
+    let mut counter = 0;
+
+    for &root in adjacency.keys() {
+        if index.contains_key(root) {
+            continue;
+        }
+
+        // (节点, 下一个待访问的邻居下标)
+        let mut work: Vec<(&str, usize)> = vec![(root, 0)];
+        index.insert(root, counter);
+        low.insert(root, counter);
+        counter += 1;
+        stack.push(root);
+        on_stack.insert(root, true);
+
+        while let Some((node, next)) = work.last_mut() {
+            let node = *node;
+            if let Some((neighbor, _)) = adjacency[node].get(*next) {
+                *next += 1;
+                let neighbor = *neighbor;
+                if !index.contains_key(neighbor) {
+                    index.insert(neighbor, counter);
+                    low.insert(neighbor, counter);
+                    counter += 1;
+                    stack.push(neighbor);
+                    on_stack.insert(neighbor, true);
+                    work.push((neighbor, 0));
+                } else if on_stack.get(neighbor).copied().unwrap_or(false) {
+                    let value = low[node].min(index[neighbor]);
+                    low.insert(node, value);
+                }
+                continue;
+            }
+
+            work.pop();
+            if let Some((parent, _)) = work.last() {
+                let value = low[*parent].min(low[node]);
+                low.insert(*parent, value);
+            }
+            if low[node] == index[node] {
+                let mut component = Vec::new();
+                while let Some(member) = stack.pop() {
+                    on_stack.insert(member, false);
+                    component.push(member);
+                    if member == node {
+                        break;
+                    }
+                }
+                components.push(component);
+            }
+        }
+    }
+
+    components
+}
+
+/// 在分量内从 `start` 出发回到 `start` 的最短环路
+fn shortest_cycle(
+    adjacency: &BTreeMap<&str, Vec<(&str, &FileImport)>>,
+    members: &BTreeSet<&str>,
+    start: &str,
+) -> Vec<FileImport> {
+    let mut previous: HashMap<&str, (&str, &FileImport)> = HashMap::new();
+    let mut queue: VecDeque<&str> = VecDeque::from([start]);
+
+    while let Some(current) = queue.pop_front() {
+        for &(next, import) in &adjacency[current] {
+            if !members.contains(next) {
+                continue;
+            }
+            if next == start {
+                let mut path = vec![import.clone()];
+                let mut cursor = current;
+                while let Some((from, import)) = previous.get(cursor) {
+                    path.push((*import).clone());
+                    cursor = from;
+                }
+                path.reverse();
+                return path;
+            }
+            if !previous.contains_key(next) {
+                previous.insert(next, (current, import));
+                queue.push_back(next);
+            }
+        }
+    }
+
+    Vec::new()
+}
+
+impl ArchitectureReport {
+    /// 是否存在 error 级别的问题
+    pub fn has_errors(&self) -> bool {
+        self.errors > 0
+    }
+
+    /// 转换为 SARIF 2.1.0
+    pub fn to_sarif(&self) -> Value {
+        let mut rules: BTreeMap<String, String> = BTreeMap::new();
+        let mut results = Vec::new();
+
+        for cycle in &self.cycles {
+            let rule_id = match cycle.kind {
+                CycleKind::File => "import-cycle/file",
+                CycleKind::Package => "import-cycle/package",
+            };
+            rules.entry(rule_id.to_string()).or_insert_with(|| match cycle.kind {
+                CycleKind::File => "文件之间存在循环导入".to_string(),
+                CycleKind::Package => "包之间存在循环依赖".to_string(),
+            });
+
+            let chain: Vec<&str> = cycle
+                .path
+                .iter()
+                .map(|i| i.from_file.as_str())
+                .chain(cycle.path.first().map(|i| i.from_file.as_str()))
+                .collect();
+            let Some(first) = cycle.path.first() else {
+                continue;
+            };
+            results.push(json!({
+                "ruleId": rule_id,
+                "level": cycle.severity.sarif_level(),
+                "message": { "text": format!("循环依赖: {}", chain.join(" -> ")) },
+                "locations": [sarif_location(first)],
+                "relatedLocations": cycle.path.iter().skip(1).enumerate().map(|(i, import)| {
+                    let mut location = sarif_location(import);
+                    location["id"] = json!(i + 1);
+                    location
+                }).collect::<Vec<_>>(),
+            }));
+        }
+
+        for violation in &self.violations {
+            rules
+                .entry(violation.rule.clone())
+                .or_insert_with(|| format!("分层规则 {}", violation.rule));
+
+            results.push(json!({
+                "ruleId": violation.rule,
+                "level": violation.severity.sarif_level(),
+                "message": { "text": violation.message },
+                "locations": [sarif_location(&violation.import)],
+                "properties": {
+                    "fromPackage": violation.from_package,
+                    "toPackage": violation.to_package,
+                    "importedEntities": violation.import.imported,
+                },
+            }));
+        }
+
+        json!({
+            "$schema": "https://json.schemastore.org/sarif-2.1.0.json",
+            "version": "2.1.0",
+            "runs": [{
+                "tool": {
+                    "driver": {
+                        "name": "tauri-code-base-analyzer",
+                        "version": env!("CARGO_PKG_VERSION"),
+                        "rules": rules.iter().map(|(id, description)| json!({
+                            "id": id,
+                            "shortDescription": { "text": description },
+                        })).collect::<Vec<_>>(),
+                    }
+                },
+                "results": results,
+            }]
+        })
+    }
+}
+
+/// 导入语句的 SARIF 位置，实体 ID 放在 `logicalLocations` 中
+fn sarif_location(import: &FileImport) -> Value {
+    let mut location = json!({
+        "physicalLocation": {
+            "artifactLocation": { "uri": import.from_file.replace('\\', "/") },
+            "region": { "startLine": import.line },
+        }
+    });
+    if let Some(entity) = &import.from_entity {
+        location["logicalLocations"] = json!([{ "fullyQualifiedName": entity }]);
+    }
+    location
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tool_execution::codebase::enrichment::StaticAnalyzer;
+    use crate::tool_execution::codebase::FileWalker;
+    use std::fs;
+
+    fn import(from: &str, to: &str, line: usize) -> FileImport {
+        FileImport {
+            from_file: from.to_string(),
+            line,
+            specifier: format!("./{}", to),
+            to_file: to.to_string(),
+            imported: vec![format!("app:{}#Function:f", to)],
+            from_entity: None,
+            type_only: false,
+            dynamic: false,
+            reexport: false,
+        }
+    }
+
+    #[test]
+    fn test_detects_file_and_package_cycles() {
+        let root = Path::new("/tmp/test_architecture_cycles");
+        fs::remove_dir_all(root).ok();
+        fs::create_dir_all(root.join("packages/a/src")).unwrap();
+        fs::create_dir_all(root.join("packages/b/src")).unwrap();
+        fs::write(root.join("packages/a/package.json"), r#"{ "name": "@acme/a" }"#).unwrap();
+        fs::write(root.join("packages/b/package.json"), r#"{ "name": "@acme/b" }"#).unwrap();
+
+        let imports = [
+            import("packages/a/src/x.ts", "packages/b/src/z.ts", 1),
+            import("packages/b/src/z.ts", "packages/a/src/y.ts", 2),
+            import("packages/a/src/y.ts", "packages/a/src/x.ts", 3),
+            import("packages/a/src/x.ts", "packages/a/src/y.ts", 4),
+        ];
+        let report = check_architecture(root, &imports, &ArchitectureConfig::default()).unwrap();
+
+        let file_cycle = report.cycles.iter().find(|c| c.kind == CycleKind::File).unwrap();
+        assert_eq!(file_cycle.members.len(), 3);
+        assert_eq!(file_cycle.path.len(), 2);
+        assert_eq!(file_cycle.path[0].from_file, "packages/a/src/x.ts");
+        assert_eq!(file_cycle.path[1].to_file, "packages/a/src/x.ts");
+
+        let package_cycle = report.cycles.iter().find(|c| c.kind == CycleKind::Package).unwrap();
+        assert_eq!(package_cycle.members, vec!["@acme/a".to_string(), "@acme/b".to_string()]);
+        assert_eq!((report.errors, report.warnings), (1, 1));
+
+        // 只有类型导入的环不算
+        let mut type_only = import("packages/b/src/z.ts", "packages/a/src/x.ts", 1);
+        type_only.type_only = true;
+        let typed = [imports[0].clone(), type_only];
+        let report = check_architecture(root, &typed, &ArchitectureConfig::default()).unwrap();
+        assert!(report.cycles.is_empty());
+
+        fs::remove_dir_all(root).ok();
+    }
+
+    #[test]
+    fn test_detects_cycle_through_barrel_file() {
+        let root = Path::new("/tmp/test_architecture_barrel_cycle");
+        fs::remove_dir_all(root).ok();
+        fs::create_dir_all(root.join("src")).unwrap();
+        fs::write(root.join("package.json"), r#"{ "name": "app" }"#).unwrap();
+        fs::write(root.join("src/a.ts"), "import { b } from './b';\n\nexport function a() {\n    return b();\n}\n").unwrap();
+        fs::write(root.join("src/b.ts"), "import { a } from './index';\n\nexport function b() {\n    return a();\n}\n").unwrap();
+        // barrel 文件不产出实体，只有转发导出
+        fs::write(root.join("src/index.ts"), "export * from './a';\n").unwrap();
+
+        let walker = FileWalker::with_default();
+        let (entities, _, manifest) = walker.extract_incremental(root.to_str().unwrap(), None).unwrap();
+        let files: Vec<String> = manifest.files.keys().cloned().collect();
+        let imports = StaticAnalyzer::new(root, entities).file_imports(&files);
+
+        let barrel = imports.iter().find(|i| i.from_file == "src/index.ts").unwrap();
+        assert!(barrel.reexport);
+        assert_eq!(barrel.to_file, "src/a.ts");
+        assert_eq!(barrel.imported, vec!["app:src/a.ts#Function:a".to_string()]);
+
+        let report = check_architecture(root, &imports, &ArchitectureConfig::default()).unwrap();
+        assert_eq!(report.cycles.len(), 1);
+        assert_eq!(report.cycles[0].members, vec!["src/a.ts", "src/b.ts", "src/index.ts"]);
+        assert_eq!(report.cycles[0].path.len(), 3);
+
+        fs::remove_dir_all(root).ok();
+    }
+
+    #[test]
+    fn test_layer_rules_and_sarif() {
+        let root = Path::new("/tmp/test_architecture_rules");
+        fs::remove_dir_all(root).ok();
+        fs::create_dir_all(root).unwrap();
+
+        let config: ArchitectureConfig = serde_json::from_str(
+            r#"{
+                "file_cycles": "off",
+                "rules": [{ "name": "components-no-views", "from": ["src/components/**"], "to": ["src/views/**"] }]
+            }"#,
+        )
+        .unwrap();
+        let mut violating = import("src/components/OrderCard.vue", "src/views/OrderPage.vue", 12);
+        violating.from_entity = Some("app:src/components/OrderCard.vue#Component:setup".to_string());
+        let imports = vec![
+            import("src/views/OrderPage.vue", "src/components/OrderCard.vue", 3),
+            violating,
+        ];
+
+        let report = check_architecture(root, &imports, &config).unwrap();
+        assert_eq!(report.violations.len(), 1);
+        assert_eq!(report.violations[0].rule, "components-no-views");
+        assert_eq!(report.violations[0].import.line, 12);
+        assert!(report.has_errors());
+
+        let sarif = report.to_sarif();
+        let result = &sarif["runs"][0]["results"][0];
+        assert_eq!(result["ruleId"], "components-no-views");
+        assert_eq!(result["level"], "error");
+        assert_eq!(result["locations"][0]["physicalLocation"]["artifactLocation"]["uri"], "src/components/OrderCard.vue");
+        assert_eq!(result["locations"][0]["physicalLocation"]["region"]["startLine"], 12);
+        assert_eq!(
+            result["locations"][0]["logicalLocations"][0]["fullyQualifiedName"],
+            "app:src/components/OrderCard.vue#Component:setup"
+        );
+
+        fs::remove_dir_all(root).ok();
+    }
+}
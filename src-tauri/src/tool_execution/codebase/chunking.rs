@@ -18,7 +18,7 @@ lazy_static! {
 
     // 导出语句正则
     static ref EXPORT_REGEX: Regex = Regex::new(
-        r"export\s+(?:default\s+)?(?:const|let|var|function|class|interface|type)\s+(\w+)"
+        r"export\s+(?:default\s+)?(?:abstract\s+)?(?:const|let|var|function|class|interface|type|enum|namespace)\s+(\w+)"
     ).unwrap();
 
     // JSDoc 注释正则
@@ -42,6 +42,10 @@ pub struct CodeChunk {
     pub raw_name: String,
     pub loc: LocationInfo,
 
+    /// 所属实体 ID（类方法所属的类、SFC 子实体所属的组件）
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub parent: Option<String>,
+
     // ========== 代码内容 ==========
     /// 实际的代码文本
     pub code: String,
@@ -103,6 +107,15 @@ impl ChunkBuilder {
 
     /// 从 CodeEntity 创建 CodeChunk
     pub fn build_chunk(&self, entity: CodeEntity) -> Result<CodeChunk, Box<dyn std::error::Error>> {
+        self.build_chunk_with_children(entity, &[])
+    }
+
+    /// 从 CodeEntity 创建 CodeChunk，子实体（如类方法）的代码折叠为占位行
+    fn build_chunk_with_children(
+        &self,
+        entity: CodeEntity,
+        children: &[(String, LocationInfo)],
+    ) -> Result<CodeChunk, Box<dyn std::error::Error>> {
         // 1. 读取文件内容
         let full_path = Path::new(&self.workspace_root).join(&entity.file);
         let file_content = fs::read_to_string(&full_path)?;
@@ -113,6 +126,11 @@ impl ChunkBuilder {
             entity.loc.start_line,
             entity.loc.end_line,
         )?;
+        let code = if children.is_empty() {
+            code
+        } else {
+            self.collapse_children(&code, entity.loc.start_line, children)
+        };
 
         // 3. 分析上下文
         let imports = self.extract_imports(&code);
@@ -148,6 +166,7 @@ impl ChunkBuilder {
             file: entity.file,
             raw_name: entity.raw_name,
             loc: entity.loc,
+            parent: entity.parent,
             code: code.clone(),
             code_length: code.len(),
             imports,
@@ -185,6 +204,36 @@ impl ChunkBuilder {
         Ok(code)
     }
 
+    /// 将子实体所在的行折叠为一行占位注释，子实体各自生成独立的 chunk
+    fn collapse_children(
+        &self,
+        code: &str,
+        start_line: usize,
+        children: &[(String, LocationInfo)],
+    ) -> String {
+        let mut lines = Vec::new();
+
+        for (idx, line) in code.lines().enumerate() {
+            let line_no = start_line + idx;
+            match children
+                .iter()
+                .find(|(_, loc)| loc.start_line <= line_no && line_no <= loc.end_line)
+            {
+                Some((id, loc)) if loc.start_line == line_no => {
+                    let indent = &line[..line.len() - line.trim_start().len()];
+                    lines.push(format!(
+                        "{}// ... {} (lines {}-{})",
+                        indent, id, loc.start_line, loc.end_line
+                    ));
+                }
+                Some(_) => {}
+                None => lines.push(line.to_string()),
+            }
+        }
+
+        lines.join("\n")
+    }
+
     /// 提取导入语句
     fn extract_imports(&self, code: &str) -> Vec<String> {
         IMPORT_REGEX
@@ -291,6 +340,28 @@ impl ChunkBuilder {
             entity.loc.start_line, entity.loc.end_line
         ));
 
+        // 所属实体（方法所属的类等）
+        if let Some(parent) = &entity.parent {
+            parts.push(format!("Parent: {}", parent));
+        }
+
+        // 签名、导出状态、修饰符、装饰器与 JSDoc
+        if let Some(signature) = &entity.signature {
+            parts.push(format!("Signature: {}", signature.display(&entity.raw_name)));
+        }
+        if entity.is_exported {
+            parts.push("Exported: true".to_string());
+        }
+        if !entity.modifiers.is_empty() {
+            parts.push(format!("Modifiers: {}", entity.modifiers.join(" ")));
+        }
+        if !entity.decorators.is_empty() {
+            parts.push(format!("Decorators: {}", entity.decorators.join(" ")));
+        }
+        if let Some(doc) = &entity.doc_comment {
+            parts.push(format!("Doc: {}", doc.replace('\n', " ")));
+        }
+
         // 4. 导入信息（显示依赖）
         if !imports.is_empty() {
             parts.push(format!("Imports: {}", imports.join(", ")));
@@ -328,8 +399,23 @@ impl ChunkBuilder {
             by_type: HashMap::new(),
         };
 
+        // 父实体 ID -> 直接子实体的位置
+        let mut children: HashMap<String, Vec<(String, LocationInfo)>> = HashMap::new();
+        for entity in &entities {
+            if let Some(parent) = &entity.parent {
+                children
+                    .entry(parent.clone())
+                    .or_default()
+                    .push((entity.id.clone(), entity.loc.clone()));
+            }
+        }
+
         for entity in entities {
-            match self.build_chunk(entity) {
+            let entity_children = children
+                .get(&entity.id)
+                .map(|c| c.as_slice())
+                .unwrap_or(&[]);
+            match self.build_chunk_with_children(entity, entity_children) {
                 Ok(chunk) => {
                     // 更新统计
                     stats.total_code_size += chunk.code_length;
@@ -398,6 +484,22 @@ mod tests {
         assert!(complex_complexity > simple_complexity);
     }
 
+    #[test]
+    fn test_collapse_children() {
+        let builder = ChunkBuilder::new("/workspace".to_string());
+        let code = "class OrderService {\n    refund() {\n        return 1;\n    }\n}";
+        let children = vec![(
+            "Method:OrderService.refund".to_string(),
+            LocationInfo::with_range(11, 13),
+        )];
+
+        let collapsed = builder.collapse_children(code, 10, &children);
+        assert_eq!(
+            collapsed,
+            "class OrderService {\n    // ... Method:OrderService.refund (lines 11-13)\n}"
+        );
+    }
+
     #[test]
     fn test_is_test_file() {
         let builder = ChunkBuilder::new("/workspace".to_string());
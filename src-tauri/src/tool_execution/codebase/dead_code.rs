@@ -0,0 +1,468 @@
+//! 死代码与未使用导出检测
+//!
+//! 找出没有被其他实体导入、渲染或调用的导出函数、组件、组合式函数、类和 store。
+//! 使用关系来自两部分：依赖图中的调用 / 渲染边，以及全部源文件（含不产出实体的 `main.ts`、
+//! 路由配置等）的文件级导入。
+//!
+//! 静态分析看不到的使用方式会降低结论的置信度，而不是直接排除：
+//! - 入口文件（`main.ts`、`*.config.ts`、测试文件、`.d.ts`）中的实体不参与检测
+//! - 字面量 `import('./x.vue')` 等同于普通导入；模板字符串 `import()` 与 `import.meta.glob`
+//!   覆盖目录下的实体为 low，前缀无法解析时全部结果最高为 medium
+//! - 路由表（Vue Router / React Router 配置）引用的页面组件是入口，不参与检测
+//! - `pages/`、`views/` 下未出现在路由表中的组件可能由文件系统路由加载：项目没有路由配置时为 low，
+//!   有路由配置时为 medium
+//! - workspace 包入口（`exports` / `main` 等）导出的实体：私有包为 medium，公开包为 low
+
+use super::dependency_graph::DependencyGraph;
+use super::enrichment::{DynamicImport, FileImport, StaticAnalyzer};
+use super::extractors::{CodeEntity, LocationInfo};
+use super::impact::{categorize, ImpactCategory};
+use super::module_resolver::package_entry_files;
+use super::routes::RouteMap;
+use super::workspace::WorkspaceInfo;
+use anyhow::Result;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// 结论的置信度，按从高到低排序
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Confidence {
+    /// 没有已知的隐式使用方式，可以放心删除
+    High,
+    /// 可能被 workspace 内无法静态分析的方式使用
+    Medium,
+    /// 很可能被路由、动态导入或外部项目使用，需要人工确认
+    Low,
+}
+
+/// 未使用的类型
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum UnusedKind {
+    /// 没有任何引用
+    Unreferenced,
+    /// 只在所在文件内使用，导出是多余的
+    LocalOnly,
+}
+
+/// 一个未使用的实体
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct UnusedEntity {
+    pub id: String,
+
+    #[serde(rename = "type")]
+    pub entity_type: String,
+
+    pub file: String,
+
+    pub loc: LocationInfo,
+
+    pub category: ImpactCategory,
+
+    pub kind: UnusedKind,
+
+    pub confidence: Confidence,
+
+    /// 置信度的依据
+    pub reasons: Vec<String>,
+}
+
+/// workspace 包的一个公开入口
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PackageEntry {
+    /// 包名
+    pub package: String,
+
+    /// 是否为私有包
+    pub private: bool,
+
+    /// 入口文件（相对项目根目录）
+    pub file: String,
+
+    /// 入口导出的实体 ID（含转发导出）
+    pub exports: Vec<String>,
+}
+
+/// 死代码检测报告
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct DeadCodeReport {
+    /// 未使用的实体，按置信度、文件、行号排序
+    pub unused: Vec<UnusedEntity>,
+
+    /// 识别出的入口文件
+    pub entry_files: Vec<String>,
+
+    /// 目标无法静态确定的动态导入
+    pub dynamic_imports: Vec<DynamicImport>,
+
+    /// 未使用实体按置信度计数
+    pub by_confidence: BTreeMap<Confidence, usize>,
+}
+
+/// 检测所需的输入
+pub struct DeadCodeInput<'a> {
+    pub entities: &'a [CodeEntity],
+
+    /// 实体之间的依赖图（调用、渲染）
+    pub graph: &'a DependencyGraph,
+
+    /// 全部源文件的导入
+    pub imports: &'a [FileImport],
+
+    pub dynamic_imports: &'a [DynamicImport],
+
+    pub package_entries: &'a [PackageEntry],
+
+    /// 全部源文件（相对项目根目录），用于识别入口文件
+    pub files: &'a [String],
+
+    /// 路由表，被路由引用的组件视为入口
+    pub routes: &'a RouteMap,
+}
+
+/// 扫描项目并检测死代码
+///
+/// `files` 为全部源文件（包括不产出实体的文件），为空时使用实体所在的文件；
+/// `workspace` 为扫描时已解析的 workspace 信息
+pub async fn find_dead_code(
+    root: &Path,
+    entities: Vec<CodeEntity>,
+    files: &[String],
+    workspace: Option<&WorkspaceInfo>,
+) -> Result<DeadCodeReport> {
+    let files: Vec<String> = if files.is_empty() {
+        let mut files: Vec<String> = entities.iter().map(|e| e.file.clone()).collect();
+        files.sort();
+        files.dedup();
+        files
+    } else {
+        files.to_vec()
+    };
+
+    let analyzer = StaticAnalyzer::with_workspace(root, entities.clone(), workspace);
+    let graph = analyzer.build_dependency_graph().await?;
+    let imports: Vec<FileImport> = files.iter().flat_map(|f| analyzer.imports_of_file(f)).collect();
+    let dynamic_imports: Vec<DynamicImport> = files
+        .iter()
+        .flat_map(|f| analyzer.dynamic_imports_of_file(f))
+        .collect();
+
+    let package_entries: Vec<PackageEntry> = package_dirs(root, workspace)
+        .into_iter()
+        .flat_map(|(package, private, dir)| {
+            package_entry_files(&dir)
+                .into_iter()
+                .filter_map(|file| file.strip_prefix(root).ok().map(|p| p.to_string_lossy().replace('\\', "/")))
+                .map(|file| PackageEntry {
+                    package: package.clone(),
+                    private,
+                    exports: analyzer.exports_of(&file),
+                    file,
+                })
+                .collect::<Vec<_>>()
+        })
+        .collect();
+
+    let mut routes = RouteMap::default();
+    for file in &files {
+        let file_routes = analyzer.routes_of_file(file);
+        if !file_routes.is_empty() {
+            routes.files.push(file.clone());
+            routes.routes.extend(file_routes);
+        }
+    }
+
+    Ok(detect_dead_code(&DeadCodeInput {
+        entities: &entities,
+        graph: &graph,
+        imports: &imports,
+        dynamic_imports: &dynamic_imports,
+        package_entries: &package_entries,
+        files: &files,
+        routes: &routes,
+    }))
+}
+
+/// 检测未使用的导出实体
+pub fn detect_dead_code(input: &DeadCodeInput) -> DeadCodeReport {
+    let entry_files: Vec<String> = input.files.iter().filter(|f| is_entry_file(f)).cloned().collect();
+
+    // 实体 ID -> 引用它的文件（不含实体自身及其子实体）
+    let file_of: HashMap<&str, &str> = input
+        .entities
+        .iter()
+        .map(|e| (e.id.as_str(), e.file.as_str()))
+        .collect();
+    let mut users: HashMap<&str, HashSet<&str>> = HashMap::new();
+    // 转发导出本身不算使用：经 barrel 文件的导入已解析到源模块的实体
+    for import in input.imports.iter().filter(|i| !i.reexport) {
+        for id in &import.imported {
+            users.entry(id.as_str()).or_default().insert(import.from_file.as_str());
+        }
+    }
+
+    let unresolved: Vec<&DynamicImport> = input
+        .dynamic_imports
+        .iter()
+        .filter(|d| dynamic_dir(d).is_none())
+        .collect();
+
+    let routed: HashSet<&str> = input.routes.components().into_iter().collect();
+
+    let mut unused = Vec::new();
+    for entity in input.entities.iter().filter(|e| is_candidate(e, input.entities)) {
+        if is_entry_file(&entity.file) || routed.contains(entity.id.as_str()) {
+            continue;
+        }
+
+        // 实体及其子实体（类的方法、store 的 action）的全部使用者
+        let family = descendants(entity, input.entities);
+        let mut using_files: HashSet<&str> = HashSet::new();
+        for id in &family {
+            if let Some(files) = users.get(id.as_str()) {
+                using_files.extend(files);
+            }
+            for edge in input.graph.dependents(id, &[]) {
+                if family.contains(&edge.from) {
+                    continue;
+                }
+                let from_file = input
+                    .graph
+                    .nodes
+                    .get(&edge.from)
+                    .map(|n| n.file.as_str())
+                    .or_else(|| file_of.get(edge.from.as_str()).copied());
+                if let Some(file) = from_file {
+                    using_files.insert(file);
+                }
+            }
+        }
+
+        let kind = if using_files.iter().any(|f| *f != entity.file) {
+            continue;
+        } else if using_files.is_empty() {
+            UnusedKind::Unreferenced
+        } else {
+            UnusedKind::LocalOnly
+        };
+
+        let category = categorize(entity, input.entities);
+        let mut confidence = Confidence::High;
+        let mut reasons = Vec::new();
+        let mut lower = |to: Confidence, reason: String| {
+            confidence = confidence.max(to);
+            reasons.push(reason);
+        };
+
+        match kind {
+            UnusedKind::Unreferenced => lower(Confidence::High, "没有被任何实体导入、渲染或调用".to_string()),
+            UnusedKind::LocalOnly => lower(Confidence::High, "只在所在文件内使用，可以去掉导出".to_string()),
+        }
+        if category == ImpactCategory::Page {
+            if input.routes.is_empty() {
+                lower(Confidence::Low, "页面组件可能由文件系统路由或路由配置加载".to_string());
+            } else {
+                lower(Confidence::Medium, "页面组件不在路由表中，可能由文件系统路由加载".to_string());
+            }
+        }
+        for dynamic in input.dynamic_imports {
+            if dynamic_dir(dynamic).is_some_and(|dir| in_dir(&entity.file, &dir)) {
+                lower(
+                    Confidence::Low,
+                    format!("可能被动态导入 `{}` 加载 ({}:{})", dynamic.prefix, dynamic.from_file, dynamic.line),
+                );
+            }
+        }
+        for entry in input.package_entries.iter().filter(|p| p.exports.contains(&entity.id)) {
+            if entry.private {
+                lower(
+                    Confidence::Medium,
+                    format!("由私有包 {} 的入口 {} 导出", entry.package, entry.file),
+                );
+            } else {
+                lower(
+                    Confidence::Low,
+                    format!("由公开包 {} 的入口 {} 导出，可能被外部项目使用", entry.package, entry.file),
+                );
+            }
+        }
+        if let Some(first) = unresolved.first() {
+            lower(
+                Confidence::Medium,
+                format!(
+                    "项目中有 {} 处目标无法解析的动态导入，如 `{}` ({}:{})",
+                    unresolved.len(),
+                    first.prefix,
+                    first.from_file,
+                    first.line
+                ),
+            );
+        }
+
+        unused.push(UnusedEntity {
+            id: entity.id.clone(),
+            entity_type: entity.entity_type.clone(),
+            file: entity.file.clone(),
+            loc: entity.loc.clone(),
+            category,
+            kind,
+            confidence,
+            reasons,
+        });
+    }
+
+    unused.sort_by(|a, b| {
+        (a.confidence, &a.file, a.loc.start_line).cmp(&(b.confidence, &b.file, b.loc.start_line))
+    });
+
+    let mut by_confidence = BTreeMap::new();
+    for entity in &unused {
+        *by_confidence.entry(entity.confidence).or_insert(0) += 1;
+    }
+
+    DeadCodeReport {
+        unused,
+        entry_files,
+        dynamic_imports: input.dynamic_imports.to_vec(),
+        by_confidence,
+    }
+}
+
+/// 参与检测的实体：顶层的导出函数、组件、组合式函数、类以及 store 声明
+fn is_candidate(entity: &CodeEntity, entities: &[CodeEntity]) -> bool {
+    if entity.parent.is_some() {
+        return false;
+    }
+    // SFC 组件由默认导出隐式导出
+    let exported = entity.is_exported || (entity.entity_type == "component" && entity.file.ends_with(".vue"));
+    if !exported {
+        return false;
+    }
+
+    matches!(entity.entity_type.as_str(), "function" | "component" | "composable" | "class")
+        || categorize(entity, entities) == ImpactCategory::Store
+}
+
+/// 入口文件：应用入口、构建配置、测试与类型声明
+fn is_entry_file(file: &str) -> bool {
+    let name = file.rsplit('/').next().unwrap_or(file);
+    let stem = name.split('.').next().unwrap_or(name);
+
+    stem == "main"
+        || name.contains(".config.")
+        || name.contains(".test.")
+        || name.contains(".spec.")
+        || name.ends_with(".d.ts")
+        || file.split('/').any(|segment| segment == "__tests__")
+}
+
+/// 实体及其全部子孙实体的 ID
+fn descendants(entity: &CodeEntity, entities: &[CodeEntity]) -> HashSet<String> {
+    let mut family: HashSet<String> = HashSet::from([entity.id.clone()]);
+    loop {
+        let before = family.len();
+        for e in entities {
+            if e.parent.as_ref().is_some_and(|p| family.contains(p)) {
+                family.insert(e.id.clone());
+            }
+        }
+        if family.len() == before {
+            return family;
+        }
+    }
+}
+
+/// 动态导入覆盖的目录：相对路径前缀取解析结果，别名前缀（`@/views/`）去掉别名段后按目录名匹配；
+/// 没有确定目录时返回 `None`
+fn dynamic_dir(dynamic: &DynamicImport) -> Option<String> {
+    if let Some(dir) = &dynamic.dir {
+        return Some(dir.clone());
+    }
+
+    let (alias, rest) = dynamic.prefix.split_once('/')?;
+    if alias.is_empty() || alias.starts_with('.') {
+        return None;
+    }
+    let dir = rest.rsplit_once('/').map(|(dir, _)| dir)?;
+    (!dir.is_empty()).then(|| format!("*/{}", dir))
+}
+
+/// 文件是否位于目录下；`*/` 开头的目录匹配任意层级
+fn in_dir(file: &str, dir: &str) -> bool {
+    match dir.strip_prefix("*/") {
+        Some(dir) => file.starts_with(&format!("{}/", dir)) || file.contains(&format!("/{}/", dir)),
+        None => dir.is_empty() || file.starts_with(&format!("{}/", dir.trim_end_matches('/'))),
+    }
+}
+
+/// 项目中需要检查公开入口的包：workspace 包，或带入口字段的项目根 package.json
+fn package_dirs(root: &Path, workspace: Option<&WorkspaceInfo>) -> Vec<(String, bool, PathBuf)> {
+    if let Some(workspace) = workspace {
+        return workspace
+            .packages
+            .iter()
+            .map(|p| (p.name.clone(), p.private, workspace.root.join(&p.path)))
+            .filter(|(_, _, dir)| dir.starts_with(root))
+            .collect();
+    }
+
+    let Ok(content) = fs::read_to_string(root.join("package.json")) else {
+        return Vec::new();
+    };
+    let json: serde_json::Value = serde_json::from_str(&content).unwrap_or_default();
+    let has_entry = ["exports", "main", "module", "source"].iter().any(|key| json.get(key).is_some());
+    match json.get("name").and_then(|v| v.as_str()) {
+        Some(name) if has_entry => {
+            let private = json.get("private").and_then(|v| v.as_bool()).unwrap_or(false);
+            vec![(name.to_string(), private, root.to_path_buf())]
+        }
+        _ => Vec::new(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_detects_unused_exports_with_confidence() {
+        let root = Path::new("/tmp/test_dead_code");
+        fs::remove_dir_all(root).ok();
+        fs::create_dir_all(root.join("src/utils")).unwrap();
+        fs::create_dir_all(root.join("src/views")).unwrap();
+        fs::create_dir_all(root.join("src/widgets")).unwrap();
+
+        fs::write(root.join("src/main.ts"), "import { used } from './utils/math';\nused();\n").unwrap();
+        fs::write(
+            root.join("src/utils/math.ts"),
+            "export function used() { return helper(); }\n\nexport function helper() { return 1; }\n\nexport function orphan() { return 2; }\n",
+        )
+        .unwrap();
+        fs::write(
+            root.join("src/router.ts"),
+            "export const load = (name: string) => import(`./widgets/${name}.ts`);\n",
+        )
+        .unwrap();
+        fs::write(root.join("src/widgets/chart.ts"), "export function renderChart() {}\n").unwrap();
+        fs::write(root.join("src/views/Home.vue"), "<template><div /></template>\n").unwrap();
+
+        let walker = crate::tool_execution::codebase::FileWalker::with_default();
+        let (entities, _, manifest) = walker.extract_incremental(root.to_str().unwrap(), None).unwrap();
+        let files: Vec<String> = manifest.files.keys().cloned().collect();
+        let report = find_dead_code(root, entities, &files, None).await.unwrap();
+
+        let find = |name: &str| report.unused.iter().find(|u| u.id.contains(name));
+        assert!(find("Function:used").is_none());
+        assert_eq!(find("Function:helper").map(|u| u.kind), Some(UnusedKind::LocalOnly));
+        assert_eq!(find("Function:orphan").map(|u| u.confidence), Some(Confidence::High));
+        assert_eq!(find("Function:renderChart").map(|u| u.confidence), Some(Confidence::Low));
+        assert_eq!(find("src/views/Home.vue#").map(|u| u.confidence), Some(Confidence::Low));
+        assert!(report.entry_files.contains(&"src/main.ts".to_string()));
+        assert_eq!(report.dynamic_imports[0].dir.as_deref(), Some("src/widgets"));
+
+        fs::remove_dir_all(root).ok();
+    }
+}
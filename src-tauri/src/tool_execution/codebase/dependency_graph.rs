@@ -0,0 +1,584 @@
+//! 项目级依赖图
+//!
+//! 由富化阶段的静态分析结果（IMPORTS / CALLS / TEMPLATE_COMPONENTS）构建实体之间的有向依赖图，
+//! 与富化结果一起持久化，用于回答：
+//!
+//! - 谁导入 / 调用 / 渲染了某个实体（反向查询）
+//! - 某个实体直接或间接依赖了哪些实体（传递闭包）
+//! - 两个实体之间的最短依赖路径
+//!
+//! 边的方向为 "依赖方 -> 被依赖方"，重构前的影响分析即对目标实体求传递依赖方
+
+use super::entity_id::{id_matches, short_name};
+use super::enrichment::{ComponentUsage, EnrichedEntity, StaticAnalysisResult, StaticAnalyzer};
+use super::extractors::CodeEntity;
+use anyhow::{bail, Context, Result};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap, HashSet, VecDeque};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// 依赖图格式版本
+pub const GRAPH_VERSION: u32 = 1;
+
+/// 依赖关系类型
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum EdgeKind {
+    /// 导入
+    Imports,
+    /// 调用
+    Calls,
+    /// 模板中渲染组件
+    Renders,
+}
+
+/// 查询方向
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Direction {
+    /// 依赖该实体的实体（反向）
+    Dependents,
+    /// 该实体依赖的实体（正向）
+    Dependencies,
+}
+
+/// 图节点
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GraphNode {
+    /// 规范实体 ID
+    pub id: String,
+
+    /// 短 ID
+    #[serde(default)]
+    pub short_id: String,
+
+    /// 实体类型
+    #[serde(rename = "type")]
+    pub entity_type: String,
+
+    /// 所在文件
+    pub file: String,
+}
+
+/// 依赖边
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct DependencyEdge {
+    /// 依赖方
+    pub from: String,
+
+    /// 被依赖方
+    pub to: String,
+
+    /// 依赖类型
+    pub kind: EdgeKind,
+}
+
+/// 传递查询中到达的实体
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ReachedEntity {
+    /// 实体 ID
+    pub id: String,
+
+    /// 距离起点的跳数
+    pub depth: usize,
+
+    /// 最短路径上的前一个实体
+    pub via: String,
+
+    /// 经由的依赖类型
+    pub kind: EdgeKind,
+}
+
+/// 项目级依赖图
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct DependencyGraph {
+    /// 格式版本
+    pub version: u32,
+
+    /// 节点（key 为规范实体 ID）
+    pub nodes: BTreeMap<String, GraphNode>,
+
+    /// 边
+    pub edges: Vec<DependencyEdge>,
+
+    /// 出边索引：实体 ID -> 边下标
+    #[serde(skip)]
+    outgoing: HashMap<String, Vec<usize>>,
+
+    /// 入边索引：实体 ID -> 边下标
+    #[serde(skip)]
+    incoming: HashMap<String, Vec<usize>>,
+
+    /// 组件名索引，解析模板标签时按需构建，加入节点后失效
+    #[serde(skip)]
+    components: Option<HashMap<String, Vec<String>>>,
+}
+
+impl DependencyGraph {
+    /// 创建空图
+    pub fn new() -> Self {
+        Self {
+            version: GRAPH_VERSION,
+            ..Default::default()
+        }
+    }
+
+    /// 由富化结果构建
+    ///
+    /// `entities` 为项目的全部实体（被依赖方可能没有参与富化），富化实体本身也会作为节点加入
+    pub fn from_enriched(entities: &[CodeEntity], enriched: &[EnrichedEntity]) -> Self {
+        let mut graph = Self::new();
+        for entity in entities.iter().chain(enriched.iter().map(|e| &e.base)) {
+            graph.add_entity(entity);
+        }
+
+        for entity in enriched {
+            graph.add_dependencies(
+                &entity.base.id,
+                &entity.imports,
+                &entity.calls,
+                entity.template_components.as_deref(),
+                entity.template_usages.as_deref(),
+            );
+        }
+
+        graph
+    }
+
+    /// 静态分析全部实体构建依赖图，保存到实体文件旁并返回路径
+    ///
+    /// 需要逐个解析实体所在文件，耗时与项目规模成正比，在阻塞线程池中执行
+    pub async fn build_and_save(root: &Path, entities: Vec<CodeEntity>, entities_path: &Path) -> Result<PathBuf> {
+        let root = root.to_path_buf();
+        let path = Self::path_for(entities_path);
+        tokio::task::spawn_blocking(move || {
+            let analyzer = StaticAnalyzer::new(&root, entities);
+            futures::executor::block_on(analyzer.build_dependency_graph())?.save(&path)?;
+            Ok(path)
+        })
+        .await
+        .context("构建依赖图的任务异常退出")?
+    }
+
+    /// 依赖图文件路径：与实体文件同目录，`entities.enriched.json` -> `entities.enriched.graph.json`
+    pub fn path_for(entities_path: &Path) -> PathBuf {
+        entities_path.with_extension("graph.json")
+    }
+
+    /// 加入节点
+    pub fn add_entity(&mut self, entity: &CodeEntity) {
+        if entity.entity_type == "component" && !self.nodes.contains_key(&entity.id) {
+            self.components = None;
+        }
+        self.nodes.entry(entity.id.clone()).or_insert_with(|| GraphNode {
+            id: entity.id.clone(),
+            short_id: entity.short_id.clone(),
+            entity_type: entity.entity_type.clone(),
+            file: entity.file.clone(),
+        });
+    }
+
+    /// 加入单个实体的静态分析结果
+    ///
+    /// 模板中的组件标签按已加入的组件节点解析，调用前应先加入全部实体
+    pub fn add_analysis(&mut self, entity_id: &str, analysis: &StaticAnalysisResult) {
+        self.add_dependencies(
+            entity_id,
+            &analysis.imports,
+            &analysis.calls,
+            analysis.template_components.as_deref(),
+            analysis.template_usages.as_deref(),
+        );
+    }
+
+    fn add_dependencies(
+        &mut self,
+        from: &str,
+        imports: &[String],
+        calls: &[String],
+        template_components: Option<&[String]>,
+        template_usages: Option<&[ComponentUsage]>,
+    ) {
+        for to in imports {
+            self.add_edge(from, to, EdgeKind::Imports);
+        }
+        for to in calls {
+            self.add_edge(from, to, EdgeKind::Calls);
+        }
+
+        // 静态分析已解析到组件实体的标签直接连边，其余按组件名解析
+        let mut resolved_tags: Vec<&str> = Vec::new();
+        for usage in template_usages.unwrap_or(&[]) {
+            if let Some(to) = &usage.component {
+                self.add_edge(from, to, EdgeKind::Renders);
+                resolved_tags.push(&usage.tag);
+            }
+        }
+        let template_components: Vec<&String> = template_components
+            .unwrap_or(&[])
+            .iter()
+            .filter(|tag| !resolved_tags.contains(&tag.as_str()))
+            .collect();
+
+        if template_components.is_empty() {
+            return;
+        }
+        let index = match self.components.take() {
+            Some(index) => index,
+            None => self.component_index(),
+        };
+        for tag in template_components {
+            for to in Self::resolve_component(&index, tag, imports) {
+                self.add_edge(from, &to, EdgeKind::Renders);
+            }
+        }
+        self.components = Some(index);
+    }
+
+    /// 加入一条边，忽略自环和重复边
+    pub fn add_edge(&mut self, from: &str, to: &str, kind: EdgeKind) {
+        if from == to {
+            return;
+        }
+        let exists = self
+            .outgoing
+            .get(from)
+            .map(|list| list.iter().any(|&i| self.edges[i].to == to && self.edges[i].kind == kind))
+            .unwrap_or(false);
+        if exists {
+            return;
+        }
+
+        let index = self.edges.len();
+        self.edges.push(DependencyEdge {
+            from: from.to_string(),
+            to: to.to_string(),
+            kind,
+        });
+        self.outgoing.entry(from.to_string()).or_default().push(index);
+        self.incoming.entry(to.to_string()).or_default().push(index);
+    }
+
+    /// 组件名索引：归一化的组件名 -> 组件实体 ID
+    ///
+    /// Vue 单文件组件以文件名为组件名，其余组件以实体名为组件名
+    fn component_index(&self) -> HashMap<String, Vec<String>> {
+        let mut index: HashMap<String, Vec<String>> = HashMap::new();
+        for node in self.nodes.values().filter(|n| n.entity_type == "component") {
+            let name = if node.file.ends_with(".vue") {
+                Path::new(&node.file)
+                    .file_stem()
+                    .map(|s| s.to_string_lossy().to_string())
+                    .unwrap_or_default()
+            } else {
+                let short_id = if node.short_id.is_empty() { &node.id } else { &node.short_id };
+                short_name(short_id).to_string()
+            };
+            index.entry(normalize_component_name(&name)).or_default().push(node.id.clone());
+        }
+        index
+    }
+
+    /// 解析模板标签：`<OrderList>` 与 `<order-list>` 等价；
+    /// 同名组件有多个时只取被导入的那些，无法区分时放弃
+    fn resolve_component(index: &HashMap<String, Vec<String>>, tag: &str, imports: &[String]) -> Vec<String> {
+        let Some(candidates) = index.get(&normalize_component_name(tag)) else {
+            return Vec::new();
+        };
+        if candidates.len() == 1 {
+            return candidates.clone();
+        }
+        candidates.iter().filter(|id| imports.contains(id)).cloned().collect()
+    }
+
+    /// 重建邻接索引
+    fn reindex(&mut self) {
+        self.outgoing.clear();
+        self.incoming.clear();
+        for (index, edge) in self.edges.iter().enumerate() {
+            self.outgoing.entry(edge.from.clone()).or_default().push(index);
+            self.incoming.entry(edge.to.clone()).or_default().push(index);
+        }
+    }
+
+    /// 由规范 ID、路径 + 短 ID 或短 ID 查找节点
+    ///
+    /// 找不到或匹配到多个实体时返回错误，错误信息中列出候选
+    pub fn resolve_id(&self, query: &str) -> Result<String> {
+        if self.nodes.contains_key(query) {
+            return Ok(query.to_string());
+        }
+
+        let candidates: Vec<&String> = self
+            .nodes
+            .values()
+            .filter(|n| id_matches(&n.id, &n.short_id, query))
+            .map(|n| &n.id)
+            .collect();
+
+        match candidates.as_slice() {
+            [] => bail!("依赖图中未找到实体: {}", query),
+            [id] => Ok(id.to_string()),
+            _ => bail!(
+                "实体 {} 匹配到多个候选，请使用规范 ID:\n{}",
+                query,
+                candidates.iter().map(|id| format!("  - {}", id)).collect::<Vec<_>>().join("\n")
+            ),
+        }
+    }
+
+    /// 直接依赖方（谁导入 / 调用 / 渲染了该实体）；`kinds` 为空表示全部类型
+    pub fn dependents(&self, id: &str, kinds: &[EdgeKind]) -> Vec<&DependencyEdge> {
+        self.edges_of(id, Direction::Dependents, kinds)
+    }
+
+    /// 直接依赖（该实体导入 / 调用 / 渲染了谁）；`kinds` 为空表示全部类型
+    pub fn dependencies(&self, id: &str, kinds: &[EdgeKind]) -> Vec<&DependencyEdge> {
+        self.edges_of(id, Direction::Dependencies, kinds)
+    }
+
+    /// 传递依赖方，按距离排序
+    pub fn transitive_dependents(&self, id: &str, kinds: &[EdgeKind]) -> Vec<ReachedEntity> {
+        self.closure(id, Direction::Dependents, kinds, None)
+    }
+
+    /// 传递依赖，按距离排序
+    pub fn transitive_dependencies(&self, id: &str, kinds: &[EdgeKind]) -> Vec<ReachedEntity> {
+        self.closure(id, Direction::Dependencies, kinds, None)
+    }
+
+    /// 沿指定方向广度优先遍历，`max_depth` 限制最大跳数；结果不含起点
+    pub fn closure(
+        &self,
+        id: &str,
+        direction: Direction,
+        kinds: &[EdgeKind],
+        max_depth: Option<usize>,
+    ) -> Vec<ReachedEntity> {
+        let mut visited: HashSet<&str> = HashSet::from([id]);
+        let mut queue: VecDeque<(&str, usize)> = VecDeque::from([(id, 0)]);
+        let mut reached = Vec::new();
+
+        while let Some((current, depth)) = queue.pop_front() {
+            if max_depth.is_some_and(|max| depth >= max) {
+                continue;
+            }
+            for edge in self.edges_of(current, direction, kinds) {
+                let next = Self::other_end(edge, direction);
+                if !visited.insert(next) {
+                    continue;
+                }
+                reached.push(ReachedEntity {
+                    id: next.to_string(),
+                    depth: depth + 1,
+                    via: current.to_string(),
+                    kind: edge.kind,
+                });
+                queue.push_back((next, depth + 1));
+            }
+        }
+
+        reached
+    }
+
+    /// `from` 沿依赖方向到 `to` 的最短路径，返回路径上的边；不可达时返回 `None`
+    ///
+    /// 路径是有向的：`shortest_path(a, b)` 回答 "a 如何依赖到 b"，
+    /// 反过来的问题交换参数即可
+    pub fn shortest_path(&self, from: &str, to: &str, kinds: &[EdgeKind]) -> Option<Vec<DependencyEdge>> {
+        if from == to {
+            return Some(Vec::new());
+        }
+
+        let mut previous: HashMap<&str, &DependencyEdge> = HashMap::new();
+        let mut visited: HashSet<&str> = HashSet::from([from]);
+        let mut queue: VecDeque<&str> = VecDeque::from([from]);
+
+        while let Some(current) = queue.pop_front() {
+            for edge in self.edges_of(current, Direction::Dependencies, kinds) {
+                let next = edge.to.as_str();
+                if !visited.insert(next) {
+                    continue;
+                }
+                previous.insert(next, edge);
+
+                if next == to {
+                    let mut path = Vec::new();
+                    let mut cursor = to;
+                    while let Some(edge) = previous.get(cursor) {
+                        path.push((*edge).clone());
+                        cursor = edge.from.as_str();
+                    }
+                    path.reverse();
+                    return Some(path);
+                }
+                queue.push_back(next);
+            }
+        }
+
+        None
+    }
+
+    fn edges_of(&self, id: &str, direction: Direction, kinds: &[EdgeKind]) -> Vec<&DependencyEdge> {
+        let index = match direction {
+            Direction::Dependents => &self.incoming,
+            Direction::Dependencies => &self.outgoing,
+        };
+        index
+            .get(id)
+            .map(|list| list.as_slice())
+            .unwrap_or(&[])
+            .iter()
+            .map(|&i| &self.edges[i])
+            .filter(|edge| kinds.is_empty() || kinds.contains(&edge.kind))
+            .collect()
+    }
+
+    fn other_end(edge: &DependencyEdge, direction: Direction) -> &str {
+        match direction {
+            Direction::Dependents => &edge.from,
+            Direction::Dependencies => &edge.to,
+        }
+    }
+
+    /// 加载依赖图
+    pub fn load(path: &Path) -> Result<Self> {
+        let content =
+            fs::read_to_string(path).context(format!("无法读取依赖图: {}", path.display()))?;
+        let mut graph: DependencyGraph =
+            serde_json::from_str(&content).context(format!("解析依赖图失败: {}", path.display()))?;
+        if graph.version != GRAPH_VERSION {
+            bail!(
+                "依赖图版本不匹配 ({} != {})，请重新执行富化",
+                graph.version,
+                GRAPH_VERSION
+            );
+        }
+
+        graph.reindex();
+        Ok(graph)
+    }
+
+    /// 保存依赖图
+    pub fn save(&self, path: &Path) -> Result<()> {
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent).context(format!("无法创建目录: {}", parent.display()))?;
+        }
+
+        let json = serde_json::to_string_pretty(self).context("序列化依赖图失败")?;
+        fs::write(path, json).context(format!("写入依赖图失败: {}", path.display()))?;
+
+        println!(
+            "🕸️  依赖图已保存到: {} ({} 个节点, {} 条边)",
+            path.display(),
+            self.nodes.len(),
+            self.edges.len()
+        );
+        Ok(())
+    }
+}
+
+/// 组件名归一化：`OrderList` / `order-list` / `order_list` -> `orderlist`
+fn normalize_component_name(name: &str) -> String {
+    name.chars()
+        .filter(|c| *c != '-' && *c != '_')
+        .flat_map(|c| c.to_lowercase())
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tool_execution::codebase::LocationInfo;
+
+    fn entity(id: &str, entity_type: &str, file: &str) -> CodeEntity {
+        let short_id = id.split_once('#').map(|(_, s)| s).unwrap_or(id);
+        CodeEntity {
+            id: id.to_string(),
+            short_id: short_id.to_string(),
+            entity_type: entity_type.to_string(),
+            file: file.to_string(),
+            loc: LocationInfo::new(1),
+            raw_name: short_name(short_id).to_string(),
+            ..Default::default()
+        }
+    }
+
+    fn enriched(base: CodeEntity, imports: &[&str], calls: &[&str], components: Option<&[&str]>) -> EnrichedEntity {
+        let owned = |ids: &[&str]| ids.iter().map(|s| s.to_string()).collect::<Vec<_>>();
+        EnrichedEntity {
+            base,
+            imports: owned(imports),
+            calls: owned(calls),
+            emits: Vec::new(),
+            template_components: components.map(owned),
+            template_usages: None,
+            store_usages: None,
+            annotation: None,
+            summary: String::new(),
+            tags: Vec::new(),
+            label_source: None,
+        }
+    }
+
+    #[test]
+    fn test_reverse_lookup_closure_and_path() {
+        let page = entity("app:src/views/OrderPage.vue#Component:setup", "component", "src/views/OrderPage.vue");
+        let list = entity("app:src/components/OrderList.vue#Component:setup", "component", "src/components/OrderList.vue");
+        let service = entity("app:src/service.ts#Function:loadOrders", "function", "src/service.ts");
+        let api = entity("app:src/api.ts#Function:get", "function", "src/api.ts");
+
+        let graph = DependencyGraph::from_enriched(
+            &[],
+            &[
+                enriched(page.clone(), &[&list.id], &[], Some(&["order-list"])),
+                enriched(list.clone(), &[&service.id], &[&service.id], Some(&["el-table"])),
+                enriched(service.clone(), &[&api.id], &[&api.id], None),
+                enriched(api.clone(), &[], &[], None),
+            ],
+        );
+
+        // kebab-case 标签解析到组件，未知标签被忽略
+        let renders = graph.dependents(&list.id, &[EdgeKind::Renders]);
+        assert_eq!(renders.len(), 1);
+        assert_eq!(renders[0].from, page.id);
+
+        let callers: Vec<&str> = graph.dependents(&api.id, &[EdgeKind::Calls]).iter().map(|e| e.from.as_str()).collect();
+        assert_eq!(callers, vec![service.id.as_str()]);
+
+        let impact = graph.transitive_dependents(&api.id, &[]);
+        let reached: Vec<(&str, usize)> = impact.iter().map(|r| (r.id.as_str(), r.depth)).collect();
+        assert_eq!(reached, vec![(service.id.as_str(), 1), (list.id.as_str(), 2), (page.id.as_str(), 3)]);
+
+        let path = graph.shortest_path(&page.id, &api.id, &[]).unwrap();
+        let hops: Vec<&str> = path.iter().map(|e| e.to.as_str()).collect();
+        assert_eq!(hops, vec![list.id.as_str(), service.id.as_str(), api.id.as_str()]);
+        assert!(graph.shortest_path(&api.id, &page.id, &[]).is_none());
+
+        assert_eq!(graph.resolve_id("src/api.ts#Function:get").unwrap(), api.id);
+        assert!(graph.resolve_id("Component:setup").is_err());
+    }
+
+    #[test]
+    fn test_save_and_load_rebuilds_index() {
+        let dir = Path::new("/tmp/test_dependency_graph");
+        fs::remove_dir_all(dir).ok();
+
+        let a = entity("app:a.ts#Function:a", "function", "a.ts");
+        let b = entity("app:b.ts#Function:b", "function", "b.ts");
+        let graph = DependencyGraph::from_enriched(std::slice::from_ref(&b), &[enriched(a.clone(), &[&b.id], &[&b.id], None)]);
+
+        let path = DependencyGraph::path_for(&dir.join("entities.enriched.json"));
+        assert_eq!(path, dir.join("entities.enriched.graph.json"));
+        graph.save(&path).unwrap();
+
+        let loaded = DependencyGraph::load(&path).unwrap();
+        assert_eq!(loaded.nodes.len(), 2);
+        let kinds: Vec<EdgeKind> = loaded.dependents(&b.id, &[]).iter().map(|e| e.kind).collect();
+        assert_eq!(kinds, vec![EdgeKind::Imports, EdgeKind::Calls]);
+        assert_eq!(loaded.dependencies(&a.id, &[EdgeKind::Calls]).len(), 1);
+
+        fs::remove_dir_all(dir).ok();
+    }
+}
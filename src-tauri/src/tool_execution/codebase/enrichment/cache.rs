@@ -0,0 +1,227 @@
+use super::interfaces::{LLMResponse, LabelSource, StaticAnalysisResult};
+use crate::tool_execution::codebase::scan_manifest::hash_content;
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Mutex;
+
+/// 缓存文件名，与富化输出放在同一目录
+pub const CACHE_FILE: &str = "enrichment_cache.json";
+
+/// 缓存文件格式版本
+const CACHE_FORMAT_VERSION: u32 = 3;
+
+/// 一条缓存：LLM 生成的摘要与标签
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CacheEntry {
+    pub summary: String,
+
+    pub tags: Vec<String>,
+
+    /// 结果是 LLM 直接生成还是经过修复
+    pub source: LabelSource,
+
+    /// 最近一次使用该结果的实体（仅供排查）
+    pub entity: String,
+
+    /// 写入时间
+    pub updated_at: String,
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct CacheFile {
+    version: u32,
+    entries: HashMap<String, CacheEntry>,
+}
+
+/// 缓存命中统计
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CacheStats {
+    pub hits: usize,
+    pub misses: usize,
+
+    /// 缓存中的条目数
+    pub entries: usize,
+}
+
+impl CacheStats {
+    /// 命中率（0-1），没有查询时为 0
+    pub fn hit_rate(&self) -> f64 {
+        let total = self.hits + self.misses;
+        if total == 0 {
+            0.0
+        } else {
+            self.hits as f64 / total as f64
+        }
+    }
+}
+
+/// 内容寻址的富化缓存
+///
+/// 键为提示词版本、实体代码片段与静态分析结果的哈希：三者都不变时 LLM 的输入不变，
+/// 直接复用上次的摘要和标签。只缓存 LLM 生成（含修复后）的结果，回退逻辑生成的标签不写入
+pub struct EnrichmentCache {
+    path: PathBuf,
+    entries: Mutex<HashMap<String, CacheEntry>>,
+    hits: AtomicUsize,
+    misses: AtomicUsize,
+}
+
+impl EnrichmentCache {
+    /// 输出文件对应的缓存路径
+    pub fn path_for(output_path: &Path) -> PathBuf {
+        output_path.with_file_name(CACHE_FILE)
+    }
+
+    /// 加载缓存，文件不存在、格式版本不同或无法解析时从空缓存开始
+    pub fn load(path: &Path) -> Self {
+        let entries = match fs::read_to_string(path) {
+            Ok(content) => match serde_json::from_str::<CacheFile>(&content) {
+                Ok(file) if file.version == CACHE_FORMAT_VERSION => file.entries,
+                Ok(_) => {
+                    println!("⚠️  缓存格式版本不同，忽略旧缓存: {}", path.display());
+                    HashMap::new()
+                }
+                Err(e) => {
+                    println!("⚠️  缓存文件无法解析，忽略旧缓存: {} ({})", path.display(), e);
+                    HashMap::new()
+                }
+            },
+            Err(_) => HashMap::new(),
+        };
+
+        Self {
+            path: path.to_path_buf(),
+            entries: Mutex::new(entries),
+            hits: AtomicUsize::new(0),
+            misses: AtomicUsize::new(0),
+        }
+    }
+
+    /// 缓存键
+    pub fn key(prompt_version: &str, code: &str, analysis: &StaticAnalysisResult) -> String {
+        let analysis = serde_json::to_string(analysis).unwrap_or_default();
+        let content = [prompt_version, code, analysis.as_str()].join("\0");
+        hash_content(content.as_bytes())
+    }
+
+    /// 查询缓存并计入命中统计
+    pub fn get(&self, key: &str) -> Option<(LLMResponse, LabelSource)> {
+        let entries = self.entries.lock().unwrap_or_else(|e| e.into_inner());
+        match entries.get(key) {
+            Some(entry) => {
+                self.hits.fetch_add(1, Ordering::Relaxed);
+                let response = LLMResponse {
+                    summary: entry.summary.clone(),
+                    tags: entry.tags.clone(),
+                };
+                Some((response, entry.source))
+            }
+            None => {
+                self.misses.fetch_add(1, Ordering::Relaxed);
+                None
+            }
+        }
+    }
+
+    /// 写入通过校验的 LLM 结果，本地强制约束与回退逻辑生成的结果被忽略
+    pub fn insert(&self, key: String, entity_id: &str, response: &LLMResponse, source: LabelSource) {
+        if !matches!(source, LabelSource::Llm | LabelSource::Repair) {
+            return;
+        }
+        let entry = CacheEntry {
+            summary: response.summary.clone(),
+            tags: response.tags.clone(),
+            source,
+            entity: entity_id.to_string(),
+            updated_at: chrono::Local::now().to_rfc3339(),
+        };
+        self.entries
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .insert(key, entry);
+    }
+
+    /// 写回缓存文件（先写临时文件再替换，避免中断时损坏缓存）
+    pub fn save(&self) -> Result<()> {
+        if let Some(parent) = self.path.parent() {
+            fs::create_dir_all(parent).context(format!("无法创建目录: {}", parent.display()))?;
+        }
+
+        let file = CacheFile {
+            version: CACHE_FORMAT_VERSION,
+            entries: self.entries.lock().unwrap_or_else(|e| e.into_inner()).clone(),
+        };
+        let json = serde_json::to_string_pretty(&file).context("序列化缓存失败")?;
+
+        let tmp = self.path.with_extension("json.tmp");
+        fs::write(&tmp, json).context(format!("写入缓存失败: {}", tmp.display()))?;
+        fs::rename(&tmp, &self.path).context(format!("写入缓存失败: {}", self.path.display()))?;
+        Ok(())
+    }
+
+    pub fn stats(&self) -> CacheStats {
+        CacheStats {
+            hits: self.hits.load(Ordering::Relaxed),
+            misses: self.misses.load(Ordering::Relaxed),
+            entries: self.entries.lock().unwrap_or_else(|e| e.into_inner()).len(),
+        }
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn analysis(calls: &[&str]) -> StaticAnalysisResult {
+        StaticAnalysisResult {
+            imports: vec![],
+            calls: calls.iter().map(|c| c.to_string()).collect(),
+            emits: vec![],
+            template_components: None,
+            template_usages: None,
+            store_usages: None,
+            annotation: None,
+        }
+    }
+
+    #[test]
+    fn test_cache_keys_hits_and_persistence() {
+        let path = Path::new("/tmp/test_enrichment_cache/enrichment_cache.json");
+        fs::remove_dir_all(path.parent().unwrap()).ok();
+
+        let code = "export function total() { return sum(); }";
+        let key = EnrichmentCache::key("1", code, &analysis(&["sum"]));
+        assert_eq!(key, EnrichmentCache::key("1", code, &analysis(&["sum"])));
+        assert_ne!(key, EnrichmentCache::key("2", code, &analysis(&["sum"])));
+        assert_ne!(key, EnrichmentCache::key("1", code, &analysis(&["sum", "format"])));
+        assert_ne!(key, EnrichmentCache::key("1", "export function total() {}", &analysis(&["sum"])));
+
+        let cache = EnrichmentCache::load(path);
+        assert!(cache.get(&key).is_none());
+        let response = LLMResponse {
+            summary: "计算合计".to_string(),
+            tags: vec!["金额".to_string()],
+        };
+        cache.insert(key.clone(), "Function:total", &response, LabelSource::Repair);
+        cache.insert("enforced".to_string(), "Function:total", &response, LabelSource::Enforced);
+        cache.insert("fallback".to_string(), "Function:total", &response, LabelSource::Fallback);
+        cache.save().unwrap();
+
+        let cache = EnrichmentCache::load(path);
+        let (cached, source) = cache.get(&key).unwrap();
+        assert_eq!((cached.summary.as_str(), source), ("计算合计", LabelSource::Repair));
+        assert!(cache.get("missing").is_none());
+        assert_eq!(cache.stats(), CacheStats { hits: 1, misses: 1, entries: 1 });
+        assert_eq!(cache.stats().hit_rate(), 0.5);
+
+        fs::remove_dir_all(path.parent().unwrap()).ok();
+    }
+}
@@ -0,0 +1,213 @@
+use super::interfaces::{EnrichedEntity, LabelSource};
+use crate::tool_execution::codebase::scan_manifest::hash_content;
+use crate::tool_execution::codebase::CodeEntity;
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fs::{self, File, OpenOptions};
+use std::io::{BufRead, BufReader, Write};
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+
+/// 检查点中的一行：完成富化的实体及其输入指纹
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CheckpointRecord {
+    /// 实体与代码的指纹，实体变化后记录失效
+    pub fingerprint: String,
+
+    pub entity: EnrichedEntity,
+}
+
+/// 富化检查点（JSONL）
+///
+/// 每完成一个实体就追加一行并刷新到磁盘，进程中断时最多丢失正在处理的实体；
+/// 恢复时跳过指纹未变化的实体。富化失败或只得到回退标注（如 LLM 调用出错）的实体不写入，
+/// 恢复时会重新处理
+pub struct Checkpoint {
+    path: PathBuf,
+    file: Mutex<File>,
+}
+
+impl Checkpoint {
+    /// 输出文件对应的检查点路径：`entities.enriched.json` -> `entities.enriched.checkpoint.jsonl`
+    pub fn path_for(output_path: &Path) -> PathBuf {
+        let stem = output_path
+            .file_stem()
+            .map(|s| s.to_string_lossy().to_string())
+            .unwrap_or_else(|| "entities.enriched".to_string());
+        output_path.with_file_name(format!("{}.checkpoint.jsonl", stem))
+    }
+
+    /// 打开检查点
+    ///
+    /// `resume` 为 true 时读取已有记录（实体 ID -> 记录）并在末尾继续追加，否则清空文件重新开始
+    pub fn open(path: &Path, resume: bool) -> Result<(Self, HashMap<String, CheckpointRecord>)> {
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent).context(format!("无法创建目录: {}", parent.display()))?;
+        }
+
+        let records = if resume && path.exists() {
+            Self::load(path)?
+        } else {
+            HashMap::new()
+        };
+
+        let mut file = OpenOptions::new()
+            .create(true)
+            .append(resume)
+            .write(true)
+            .truncate(!resume)
+            .open(path)
+            .context(format!("无法打开检查点文件: {}", path.display()))?;
+
+        // 上次中断时写了一半的行补上换行，新记录从新的一行开始
+        if resume && fs::read(path).is_ok_and(|content| content.last().is_some_and(|b| *b != b'\n')) {
+            file.write_all(b"\n")?;
+        }
+
+        Ok((
+            Self {
+                path: path.to_path_buf(),
+                file: Mutex::new(file),
+            },
+            records,
+        ))
+    }
+
+    /// 读取检查点记录，同一实体以最后一条为准；中断时写了一半的行被忽略
+    pub fn load(path: &Path) -> Result<HashMap<String, CheckpointRecord>> {
+        let file = File::open(path).context(format!("无法读取检查点文件: {}", path.display()))?;
+        let mut records = HashMap::new();
+        let mut skipped = 0;
+
+        for line in BufReader::new(file).lines() {
+            let line = line?;
+            if line.trim().is_empty() {
+                continue;
+            }
+            match serde_json::from_str::<CheckpointRecord>(&line) {
+                Ok(record) if !is_final(&record.entity) => {}
+                Ok(record) => {
+                    records.insert(record.entity.base.id.clone(), record);
+                }
+                Err(_) => skipped += 1,
+            }
+        }
+
+        if skipped > 0 {
+            println!("⚠️  检查点中有 {} 行无法解析，已忽略", skipped);
+        }
+        Ok(records)
+    }
+
+    /// 追加一条记录并刷新到磁盘，未得到 LLM 标注的实体被跳过
+    pub fn append(&self, fingerprint: &str, entity: &EnrichedEntity) -> Result<()> {
+        if !is_final(entity) {
+            return Ok(());
+        }
+        let record = CheckpointRecord {
+            fingerprint: fingerprint.to_string(),
+            entity: entity.clone(),
+        };
+        let mut line = serde_json::to_string(&record).context("序列化检查点记录失败")?;
+        line.push('\n');
+
+        let mut file = self.file.lock().unwrap_or_else(|e| e.into_inner());
+        file.write_all(line.as_bytes())
+            .and_then(|_| file.flush())
+            .context(format!("写入检查点失败: {}", self.path.display()))
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+}
+
+/// 实体的标注来自 LLM（含修复与本地强制约束），可以在恢复时直接复用
+fn is_final(entity: &EnrichedEntity) -> bool {
+    matches!(
+        entity.label_source,
+        Some(LabelSource::Llm | LabelSource::Repair | LabelSource::Enforced)
+    )
+}
+
+/// 实体的输入指纹：实体信息与代码片段
+pub fn fingerprint(entity: &CodeEntity, code: &str) -> String {
+    let mut content = serde_json::to_vec(entity).unwrap_or_default();
+    content.extend_from_slice(code.as_bytes());
+    hash_content(&content)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tool_execution::codebase::LocationInfo;
+
+    fn enriched(id: &str) -> EnrichedEntity {
+        EnrichedEntity {
+            base: CodeEntity {
+                id: id.to_string(),
+                entity_type: "function".to_string(),
+                file: "src/a.ts".to_string(),
+                loc: LocationInfo { start_line: 1, end_line: 3 },
+                raw_name: id.to_string(),
+                ..Default::default()
+            },
+            imports: vec![],
+            calls: vec![],
+            emits: vec![],
+            template_components: None,
+            template_usages: None,
+            store_usages: None,
+            annotation: None,
+            summary: format!("{} 的摘要", id),
+            tags: vec![],
+            label_source: Some(LabelSource::Llm),
+        }
+    }
+
+    #[test]
+    fn test_checkpoint_resume_and_truncated_line() {
+        let path = Path::new("/tmp/test_enrichment_checkpoint/entities.enriched.checkpoint.jsonl");
+        fs::remove_dir_all(path.parent().unwrap()).ok();
+
+        let (checkpoint, records) = Checkpoint::open(path, true).unwrap();
+        assert!(records.is_empty());
+        checkpoint.append("fp-a", &enriched("a")).unwrap();
+        checkpoint.append("fp-b", &enriched("b")).unwrap();
+        drop(checkpoint);
+
+        // 模拟写到一半时进程被终止
+        let mut file = OpenOptions::new().append(true).open(path).unwrap();
+        file.write_all(b"{\"fingerprint\":\"fp-c\",\"entity\":{\"id\":").unwrap();
+        drop(file);
+
+        let (checkpoint, records) = Checkpoint::open(path, true).unwrap();
+        assert_eq!(records.len(), 2);
+        assert_eq!(records["a"].fingerprint, "fp-a");
+        assert_eq!(records["b"].entity.summary, "b 的摘要");
+        checkpoint.append("fp-c", &enriched("c")).unwrap();
+
+        // 回退标注与处理失败的实体不写入，恢复时重新处理
+        let mut fallback = enriched("d");
+        fallback.label_source = Some(LabelSource::Fallback);
+        checkpoint.append("fp-d", &fallback).unwrap();
+        let mut failed = enriched("e");
+        failed.label_source = None;
+        checkpoint.append("fp-e", &failed).unwrap();
+        drop(checkpoint);
+        assert_eq!(fs::read_to_string(path).unwrap().lines().count(), 4);
+        assert_eq!(Checkpoint::load(path).unwrap().len(), 3);
+
+        let (_, records) = Checkpoint::open(path, false).unwrap();
+        assert!(records.is_empty());
+        assert_eq!(fs::read_to_string(path).unwrap(), "");
+
+        assert_eq!(
+            Checkpoint::path_for(Path::new("/p/src/data/entities.enriched.json")),
+            Path::new("/p/src/data/entities.enriched.checkpoint.jsonl")
+        );
+
+        fs::remove_dir_all(path.parent().unwrap()).ok();
+    }
+}
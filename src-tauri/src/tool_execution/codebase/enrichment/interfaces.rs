@@ -1,3 +1,4 @@
+use super::llm::LlmConfig;
 use crate::tool_execution::codebase::CodeEntity;
 use serde::{Deserialize, Serialize};
 
@@ -27,6 +28,22 @@ pub struct EnrichedEntity {
     )]
     pub template_components: Option<Vec<String>>,
 
+    /// 模板中每次子组件使用的接口：props、事件、插槽、指令（仅Vue组件）
+    #[serde(
+        rename = "TEMPLATE_USAGES",
+        default,
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub template_usages: Option<Vec<ComponentUsage>>,
+
+    /// 使用的 store 及其成员（Pinia / Vuex）
+    #[serde(
+        rename = "STORE_USAGES",
+        default,
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub store_usages: Option<Vec<StoreUsage>>,
+
     /// 注释/文档
     #[serde(rename = "ANNOTATION", skip_serializing_if = "Option::is_none")]
     pub annotation: Option<String>,
@@ -36,6 +53,52 @@ pub struct EnrichedEntity {
 
     /// LLM 生成的标签
     pub tags: Vec<String>,
+
+    /// 摘要与标签的来源，用于审计标注质量；处理失败的实体为空
+    #[serde(rename = "LABEL_SOURCE", default, skip_serializing_if = "Option::is_none")]
+    pub label_source: Option<LabelSource>,
+}
+
+/// 摘要与标签的来源
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum LabelSource {
+    /// LLM 输出直接通过校验
+    Llm,
+    /// LLM 输出未通过校验，经修复请求后通过校验
+    Repair,
+    /// 修复后仍未通过校验，在本地截断摘要、补足或截取标签后得到
+    Enforced,
+    /// 未配置 LLM、调用失败或输出无法使用，由回退逻辑生成
+    Fallback,
+}
+
+/// 各来源的标注数量
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct LabelStats {
+    pub llm: usize,
+    pub repair: usize,
+    pub enforced: usize,
+    pub fallback: usize,
+
+    /// 处理失败、没有标注的实体
+    pub failed: usize,
+}
+
+impl LabelStats {
+    pub fn count(entities: &[EnrichedEntity]) -> Self {
+        let mut stats = Self::default();
+        for entity in entities {
+            match entity.label_source {
+                Some(LabelSource::Llm) => stats.llm += 1,
+                Some(LabelSource::Repair) => stats.repair += 1,
+                Some(LabelSource::Enforced) => stats.enforced += 1,
+                Some(LabelSource::Fallback) => stats.fallback += 1,
+                None => stats.failed += 1,
+            }
+        }
+        stats
+    }
 }
 
 /// 静态分析结果
@@ -60,11 +123,156 @@ pub struct StaticAnalysisResult {
     )]
     pub template_components: Option<Vec<String>>,
 
+    /// 模板中每次子组件使用的接口：props、事件、插槽、指令（仅Vue组件）
+    #[serde(
+        rename = "TEMPLATE_USAGES",
+        default,
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub template_usages: Option<Vec<ComponentUsage>>,
+
+    /// 使用的 store 及其成员（Pinia / Vuex）
+    #[serde(
+        rename = "STORE_USAGES",
+        default,
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub store_usages: Option<Vec<StoreUsage>>,
+
     /// 注释/文档
     #[serde(rename = "ANNOTATION", skip_serializing_if = "Option::is_none")]
     pub annotation: Option<String>,
 }
 
+/// 模板中的一次子组件使用
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ComponentUsage {
+    /// 标签名，保持模板中的写法（`order-list` / `OrderList`）
+    pub tag: String,
+
+    /// 解析到的组件实体ID；第三方组件或无法确定时为空
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub component: Option<String>,
+
+    /// 标签所在行
+    pub line: usize,
+
+    /// 传入的 props（camelCase）
+    pub props: Vec<String>,
+
+    /// 监听的事件
+    pub events: Vec<String>,
+
+    /// 使用的插槽
+    pub slots: Vec<String>,
+
+    /// 使用的指令
+    pub directives: Vec<String>,
+}
+
+/// 对一个 store 的使用
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct StoreUsage {
+    /// store 实体ID
+    pub store: String,
+
+    /// store 标识：Pinia 的 `defineStore` id、Vuex 模块名或 `root`
+    pub store_id: String,
+
+    /// 访问的 state / getters / actions / mutations
+    pub members: Vec<String>,
+}
+
+/// 文件级导入关系（一条导入语句）
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FileImport {
+    /// 导入方文件
+    pub from_file: String,
+
+    /// 导入语句所在行
+    pub line: usize,
+
+    /// 模块说明符
+    pub specifier: String,
+
+    /// 被导入的文件
+    pub to_file: String,
+
+    /// 被导入的实体ID列表
+    pub imported: Vec<String>,
+
+    /// 导入语句所在的实体（如 Vue 组件、包含动态导入的函数），顶层导入为空
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub from_entity: Option<String>,
+
+    /// 是否为 `import type`
+    pub type_only: bool,
+
+    /// 是否为动态 `import()`
+    pub dynamic: bool,
+
+    /// 是否为转发导出 `export ... from`
+    #[serde(default)]
+    pub reexport: bool,
+}
+
+/// 目标无法静态确定的动态导入（模板字符串 `import()`、`import.meta.glob`）
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DynamicImport {
+    /// 导入方文件
+    pub from_file: String,
+
+    /// 导入语句所在行
+    pub line: usize,
+
+    /// 说明符中确定的前缀，如 `./views/`、`@/pages/`
+    pub prefix: String,
+
+    /// 前缀对应的项目内目录（仅相对路径前缀可解析）
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub dir: Option<String>,
+}
+
+/// 一条路由：路径、名称、meta 与页面组件
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct Route {
+    /// 配置中的路径，React Router 的 index 路由为空
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub path: Option<String>,
+
+    /// 与父路由拼接后的完整路径
+    pub full_path: String,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub meta: Option<serde_json::Value>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub redirect: Option<String>,
+
+    /// 页面组件实体ID；布局路由、重定向或第三方组件为空
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub component: Option<String>,
+
+    /// 配置中引用组件的写法：绑定名或懒加载的模块说明符
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub component_ref: Option<String>,
+
+    /// 是否懒加载（`() => import(...)`）
+    pub lazy: bool,
+
+    /// 定义所在文件
+    pub file: String,
+
+    /// 定义所在行
+    pub line: usize,
+
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub children: Vec<Route>,
+}
+
 /// LLM 响应
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct LLMResponse {
@@ -95,6 +303,21 @@ pub struct EnrichmentConfig {
 
     /// 是否预初始化
     pub pre_initialize: bool,
+
+    /// 检查点文件（JSONL），为空时使用输出文件旁的 `<输出文件名>.checkpoint.jsonl`
+    pub checkpoint_path: Option<String>,
+
+    /// 从检查点恢复：跳过检查点中已完成且输入未变化的实体，否则清空检查点重新开始
+    pub resume: bool,
+
+    /// 是否使用富化缓存：代码与静态分析结果未变化的实体复用上次 LLM 生成的摘要和标签
+    pub use_cache: bool,
+
+    /// 缓存文件，为空时使用输出文件旁的 `enrichment_cache.json`
+    pub cache_path: Option<String>,
+
+    /// LLM 提供方配置，为空时读取项目配置 `codebase.config.json` 的 `llm` 字段
+    pub llm: Option<LlmConfig>,
 }
 
 impl Default for EnrichmentConfig {
@@ -106,6 +329,11 @@ impl Default for EnrichmentConfig {
             input_path: "entities.json".to_string(),
             output_path: "entities.enriched.json".to_string(),
             pre_initialize: false,
+            checkpoint_path: None,
+            resume: false,
+            use_cache: true,
+            cache_path: None,
+            llm: None,
         }
     }
 }
@@ -0,0 +1,454 @@
+//! LLM 提供方
+//!
+//! 富化流程通过 [`LlmProvider`] 调用模型，内置三种实现：
+//! - `anthropic`：官方 Anthropic Messages API
+//! - `openai`：OpenAI 兼容的 Chat Completions，也适用于 vLLM、llama.cpp、Ollama 等本地服务
+//! - `mock`：确定性的模拟提供方，不发出网络请求，用于测试
+//!
+//! 地址、模型、温度和最大输出长度在项目配置 `codebase.config.json` 的 `llm` 字段中设置。
+//! 结构化输出时 Anthropic 使用强制的工具调用，OpenAI 兼容服务使用 `response_format` 的 JSON Schema 模式
+
+use anyhow::{Context, Result};
+use async_trait::async_trait;
+use reqwest::Client;
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+
+const ANTHROPIC_BASE_URL: &str = "https://api.anthropic.com";
+const ANTHROPIC_API_VERSION: &str = "2023-06-01";
+const ANTHROPIC_MODEL: &str = "claude-3-7-sonnet-latest";
+const OPENAI_BASE_URL: &str = "https://api.openai.com/v1";
+const OPENAI_MODEL: &str = "gpt-4o-mini";
+
+/// 结构化输出使用的工具 / schema 名称
+const STRUCTURED_OUTPUT_NAME: &str = "record_output";
+
+/// 提供方类型
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ProviderKind {
+    /// 官方 Anthropic Messages API
+    #[default]
+    Anthropic,
+    /// OpenAI 兼容的 Chat Completions
+    #[serde(alias = "openai-compatible")]
+    OpenAi,
+    /// 确定性的模拟提供方
+    Mock,
+}
+
+/// LLM 配置
+///
+/// 示例（本地 Ollama）：
+/// ```json
+/// {
+///   "provider": "openai",
+///   "base_url": "http://localhost:11434/v1",
+///   "model": "qwen2.5-coder:7b",
+///   "temperature": 0.2
+/// }
+/// ```
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct LlmConfig {
+    pub provider: ProviderKind,
+
+    /// API 地址，为空时使用提供方的默认地址。
+    /// Anthropic 为不含 `/v1` 的根地址，OpenAI 兼容服务为含 `/v1` 的地址
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub base_url: Option<String>,
+
+    /// 模型名，为空时使用提供方的默认模型
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub model: Option<String>,
+
+    pub temperature: f32,
+
+    pub max_tokens: u32,
+
+    /// 读取 API key 的环境变量，为空时为 `ANTHROPIC_API_KEY` / `OPENAI_API_KEY`。
+    /// OpenAI 兼容服务未设置 key 时不发送认证头（本地服务通常不需要）
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub api_key_env: Option<String>,
+
+    /// 单次请求超时（秒）
+    pub timeout_secs: u64,
+
+    /// 是否使用工具调用 / JSON 模式约束输出格式，服务不支持 `response_format` 时关闭
+    pub structured_output: bool,
+}
+
+impl Default for LlmConfig {
+    fn default() -> Self {
+        Self {
+            provider: ProviderKind::default(),
+            base_url: None,
+            model: None,
+            temperature: 0.7,
+            max_tokens: 2048,
+            api_key_env: None,
+            timeout_secs: 120,
+            structured_output: true,
+        }
+    }
+}
+
+impl LlmConfig {
+    pub fn base_url(&self) -> String {
+        let default = match self.provider {
+            ProviderKind::Anthropic => ANTHROPIC_BASE_URL,
+            ProviderKind::OpenAi => OPENAI_BASE_URL,
+            ProviderKind::Mock => "",
+        };
+        self.base_url
+            .as_deref()
+            .unwrap_or(default)
+            .trim_end_matches('/')
+            .to_string()
+    }
+
+    pub fn model(&self) -> String {
+        let default = match self.provider {
+            ProviderKind::Anthropic => ANTHROPIC_MODEL,
+            ProviderKind::OpenAi => OPENAI_MODEL,
+            ProviderKind::Mock => "mock",
+        };
+        self.model.clone().unwrap_or_else(|| default.to_string())
+    }
+
+    pub fn api_key_env(&self) -> String {
+        let default = match self.provider {
+            ProviderKind::OpenAi => "OPENAI_API_KEY",
+            _ => "ANTHROPIC_API_KEY",
+        };
+        self.api_key_env.clone().unwrap_or_else(|| default.to_string())
+    }
+
+    fn http_client(&self) -> Result<Client> {
+        Client::builder()
+            .timeout(Duration::from_secs(self.timeout_secs))
+            .build()
+            .context("创建 HTTP 客户端失败")
+    }
+}
+
+/// LLM 提供方
+#[async_trait]
+pub trait LlmProvider: Send + Sync {
+    /// 提供方名称（日志用）
+    fn name(&self) -> &str;
+
+    /// 使用的模型，参与富化缓存的键
+    fn model(&self) -> &str;
+
+    /// 发送单轮对话，返回模型输出的文本
+    async fn complete(&self, system: &str, prompt: &str) -> Result<String>;
+
+    /// 请求符合 `schema`（JSON Schema）的结构化输出，返回 JSON 文本。
+    /// 默认实现为普通对话，由调用方校验输出
+    async fn complete_json(&self, system: &str, prompt: &str, _schema: &Value) -> Result<String> {
+        self.complete(system, prompt).await
+    }
+}
+
+/// 按配置创建提供方
+pub fn create_provider(config: &LlmConfig) -> Result<Arc<dyn LlmProvider>> {
+    let provider: Arc<dyn LlmProvider> = match config.provider {
+        ProviderKind::Anthropic => Arc::new(AnthropicProvider::new(config)?),
+        ProviderKind::OpenAi => Arc::new(OpenAiProvider::new(config)?),
+        ProviderKind::Mock => Arc::new(MockProvider::new()),
+    };
+    Ok(provider)
+}
+
+/// 官方 Anthropic Messages API
+pub struct AnthropicProvider {
+    client: Client,
+    base_url: String,
+    api_key: String,
+    model: String,
+    temperature: f32,
+    max_tokens: u32,
+    structured_output: bool,
+}
+
+impl AnthropicProvider {
+    pub fn new(config: &LlmConfig) -> Result<Self> {
+        let key_env = config.api_key_env();
+        let api_key = std::env::var(&key_env).map_err(|_| anyhow::anyhow!("环境变量 {} 未设置", key_env))?;
+
+        Ok(Self {
+            client: config.http_client()?,
+            base_url: config.base_url(),
+            api_key,
+            model: config.model(),
+            temperature: config.temperature,
+            max_tokens: config.max_tokens,
+            structured_output: config.structured_output,
+        })
+    }
+
+    async fn send(&self, request: &Value) -> Result<Value> {
+        post_json(
+            self.client
+                .post(format!("{}/v1/messages", self.base_url))
+                .header("x-api-key", &self.api_key)
+                .header("anthropic-version", ANTHROPIC_API_VERSION),
+            request,
+        )
+        .await
+    }
+
+    fn request(&self, system: &str, prompt: &str) -> Value {
+        json!({
+            "model": self.model,
+            "max_tokens": self.max_tokens,
+            "temperature": self.temperature,
+            "system": system,
+            "messages": [{ "role": "user", "content": prompt }]
+        })
+    }
+}
+
+#[async_trait]
+impl LlmProvider for AnthropicProvider {
+    fn name(&self) -> &str {
+        "anthropic"
+    }
+
+    fn model(&self) -> &str {
+        &self.model
+    }
+
+    async fn complete(&self, system: &str, prompt: &str) -> Result<String> {
+        let body = self.send(&self.request(system, prompt)).await?;
+
+        let text: String = body["content"]
+            .as_array()
+            .into_iter()
+            .flatten()
+            .filter(|block| block["type"] == "text")
+            .filter_map(|block| block["text"].as_str())
+            .collect();
+        if text.is_empty() {
+            return Err(anyhow::anyhow!("响应中没有文本内容: {}", body));
+        }
+        Ok(text)
+    }
+
+    /// 强制调用以 `schema` 为输入的工具，工具参数即结构化输出
+    async fn complete_json(&self, system: &str, prompt: &str, schema: &Value) -> Result<String> {
+        if !self.structured_output {
+            return self.complete(system, prompt).await;
+        }
+
+        let mut request = self.request(system, prompt);
+        request["tools"] = json!([{
+            "name": STRUCTURED_OUTPUT_NAME,
+            "description": "记录结构化输出",
+            "input_schema": schema
+        }]);
+        request["tool_choice"] = json!({ "type": "tool", "name": STRUCTURED_OUTPUT_NAME });
+        let body = self.send(&request).await?;
+
+        body["content"]
+            .as_array()
+            .into_iter()
+            .flatten()
+            .find(|block| block["type"] == "tool_use")
+            .map(|block| block["input"].to_string())
+            .ok_or_else(|| anyhow::anyhow!("响应中没有工具调用: {}", body))
+    }
+}
+
+/// OpenAI 兼容的 Chat Completions
+pub struct OpenAiProvider {
+    client: Client,
+    base_url: String,
+    api_key: Option<String>,
+    model: String,
+    temperature: f32,
+    max_tokens: u32,
+    structured_output: bool,
+}
+
+impl OpenAiProvider {
+    pub fn new(config: &LlmConfig) -> Result<Self> {
+        Ok(Self {
+            client: config.http_client()?,
+            base_url: config.base_url(),
+            api_key: std::env::var(config.api_key_env()).ok(),
+            model: config.model(),
+            temperature: config.temperature,
+            max_tokens: config.max_tokens,
+            structured_output: config.structured_output,
+        })
+    }
+
+    fn request(&self, system: &str, prompt: &str) -> Value {
+        json!({
+            "model": self.model,
+            "max_tokens": self.max_tokens,
+            "temperature": self.temperature,
+            "messages": [
+                { "role": "system", "content": system },
+                { "role": "user", "content": prompt }
+            ]
+        })
+    }
+
+    async fn send(&self, request: &Value) -> Result<String> {
+        let mut builder = self.client.post(format!("{}/chat/completions", self.base_url));
+        if let Some(api_key) = &self.api_key {
+            builder = builder.bearer_auth(api_key);
+        }
+        let body = post_json(builder, request).await?;
+
+        body["choices"][0]["message"]["content"]
+            .as_str()
+            .map(String::from)
+            .ok_or_else(|| anyhow::anyhow!("响应中没有文本内容: {}", body))
+    }
+}
+
+#[async_trait]
+impl LlmProvider for OpenAiProvider {
+    fn name(&self) -> &str {
+        "openai"
+    }
+
+    fn model(&self) -> &str {
+        &self.model
+    }
+
+    async fn complete(&self, system: &str, prompt: &str) -> Result<String> {
+        self.send(&self.request(system, prompt)).await
+    }
+
+    /// 使用 `response_format` 的 JSON Schema 模式
+    async fn complete_json(&self, system: &str, prompt: &str, schema: &Value) -> Result<String> {
+        if !self.structured_output {
+            return self.complete(system, prompt).await;
+        }
+
+        let mut request = self.request(system, prompt);
+        request["response_format"] = json!({
+            "type": "json_schema",
+            "json_schema": { "name": STRUCTURED_OUTPUT_NAME, "schema": schema }
+        });
+        self.send(&request).await
+    }
+}
+
+/// 确定性的模拟提供方
+///
+/// 默认按提示词中的实体类型和名称生成摘要与标签，也可以按顺序返回指定文本；记录调用次数
+#[derive(Default)]
+pub struct MockProvider {
+    responses: Vec<String>,
+    calls: AtomicUsize,
+}
+
+impl MockProvider {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// 每次调用都返回指定文本
+    pub fn with_response(response: impl Into<String>) -> Self {
+        Self::with_responses(vec![response.into()])
+    }
+
+    /// 按顺序返回指定文本，用完后重复最后一条
+    pub fn with_responses(responses: Vec<String>) -> Self {
+        Self {
+            responses,
+            calls: AtomicUsize::new(0),
+        }
+    }
+
+    /// 已收到的调用次数
+    pub fn calls(&self) -> usize {
+        self.calls.load(Ordering::Relaxed)
+    }
+}
+
+#[async_trait]
+impl LlmProvider for MockProvider {
+    fn name(&self) -> &str {
+        "mock"
+    }
+
+    fn model(&self) -> &str {
+        "mock"
+    }
+
+    async fn complete(&self, _system: &str, prompt: &str) -> Result<String> {
+        let call = self.calls.fetch_add(1, Ordering::Relaxed);
+        if let Some(last) = self.responses.last() {
+            return Ok(self.responses.get(call).unwrap_or(last).clone());
+        }
+
+        let field = |label: &str| {
+            prompt
+                .lines()
+                .find_map(|line| line.trim().strip_prefix(label))
+                .map(|value| value.trim().to_string())
+                .unwrap_or_default()
+        };
+        let (entity_type, name) = (field("- 类型:"), field("- 名称:"));
+        Ok(json!({
+            "summary": format!("{} {} 的模拟摘要", entity_type, name),
+            "tags": [entity_type, name, "模拟"]
+        })
+        .to_string())
+    }
+}
+
+/// 发送 JSON 请求，非 2xx 状态返回带响应正文的错误
+async fn post_json(builder: reqwest::RequestBuilder, request: &Value) -> Result<Value> {
+    let response = builder.json(request).send().await.context("请求发送失败")?;
+    let status = response.status();
+    if !status.is_success() {
+        let text = response.text().await.unwrap_or_default();
+        return Err(anyhow::anyhow!("API 错误 ({}): {}", status.as_u16(), text));
+    }
+    response.json().await.context("响应解析失败")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_config_defaults_and_mock_provider() {
+        let config: LlmConfig = serde_json::from_str(
+            r#"{ "provider": "openai", "base_url": "http://localhost:11434/v1/", "model": "qwen2.5-coder" }"#,
+        )
+        .unwrap();
+        assert_eq!(config.provider, ProviderKind::OpenAi);
+        assert_eq!(config.base_url(), "http://localhost:11434/v1");
+        assert_eq!(config.api_key_env(), "OPENAI_API_KEY");
+        assert_eq!(config.max_tokens, 2048);
+
+        let config = LlmConfig::default();
+        assert_eq!(config.base_url(), ANTHROPIC_BASE_URL);
+        assert_eq!(config.model(), ANTHROPIC_MODEL);
+
+        let mock = MockProvider::new();
+        let prompt = "实体信息:\n- 类型: function\n- 名称: formatDate\n";
+        let first = mock.complete("", prompt).await.unwrap();
+        assert_eq!(first, mock.complete("", prompt).await.unwrap());
+        let value: Value = serde_json::from_str(&first).unwrap();
+        assert_eq!(value["summary"], "function formatDate 的模拟摘要");
+        assert_eq!(mock.calls(), 2);
+
+        let sequence = MockProvider::with_responses(vec!["a".to_string(), "b".to_string()]);
+        for expected in ["a", "b", "b"] {
+            assert_eq!(sequence.complete_json("", "", &Value::Null).await.unwrap(), expected);
+        }
+    }
+}
@@ -2,17 +2,32 @@
 ///
 /// 负责对提取的代码实体进行富化处理：
 /// 1. 静态分析：提取导入、调用、事件等信息
-/// 2. LLM 标注：生成摘要和标签
-/// 3. 持久化：保存富化后的实体
+/// 2. LLM 标注：通过可配置的 LLM 提供方生成摘要和标签，输出经校验与修复，输入未变化的实体复用缓存结果
+/// 3. 标签归一化：按项目标签表合并同义标签、丢弃表外标签，生成标签索引
+/// 4. 持久化：保存富化后的实体，运行过程中写入检查点以便中断后恢复
+pub mod cache;
+pub mod checkpoint;
 pub mod interfaces;
+pub mod llm;
 pub mod loader;
 pub mod orchestrator;
 pub mod persistence;
 pub mod static_analyzer;
+mod syntax;
+pub mod tags;
+mod template;
+pub mod validation;
 
 // 重新导出核心类型
-pub use interfaces::{EnrichedEntity, EnrichmentConfig, StaticAnalysisResult};
+pub use cache::{CacheStats, EnrichmentCache};
+pub use checkpoint::Checkpoint;
+pub use interfaces::{
+    ComponentUsage, DynamicImport, EnrichedEntity, EnrichmentConfig, FileImport, LabelSource, LabelStats, Route,
+    StaticAnalysisResult, StoreUsage,
+};
+pub use llm::{create_provider, LlmConfig, LlmProvider, MockProvider, ProviderKind};
 pub use loader::load_entities;
 pub use orchestrator::EnrichmentOrchestrator;
 pub use persistence::save_enriched_entities;
 pub use static_analyzer::StaticAnalyzer;
+pub use tags::{TagDefinition, TagIndex, TagTaxonomy};
@@ -1,14 +1,29 @@
-use super::interfaces::{EnrichedEntity, EnrichmentConfig, LLMResponse, StaticAnalysisResult};
+use super::cache::{CacheStats, EnrichmentCache};
+use super::checkpoint::{self, Checkpoint};
+use super::interfaces::{
+    EnrichedEntity, EnrichmentConfig, LLMResponse, LabelSource, LabelStats, StaticAnalysisResult,
+};
+use super::llm::{create_provider, LlmProvider};
 use super::loader::{load_entities, validate_entities};
-use super::persistence::save_enriched_entities;
+use super::persistence::{resolve_output_path, save_enriched_entities};
 use super::static_analyzer::StaticAnalyzer;
-use crate::claude_client::{ClaudeClient, Message};
-use crate::tool_execution::codebase::CodeEntity;
+use super::tags::{
+    normalize_tags, synonym_prompt, tag_counts, taxonomy_schema, NormalizedTags, TagIndex, TagTaxonomy,
+    BOOTSTRAP_MIN_ENTITIES,
+};
+use super::validation::{enforce_constraints, label_schema, repair_prompt, validate_labels};
+use crate::tool_execution::codebase::scan_manifest::hash_content;
+use crate::tool_execution::codebase::{CodeEntity, DependencyGraph, ProjectConfig};
 use anyhow::{Context, Result};
 use futures::stream::{self, StreamExt};
+use std::fs;
+use std::path::{Path, PathBuf};
 use std::sync::Arc;
 use tokio::time::{sleep, Duration};
 
+/// 提示词版本，参与富化缓存的键：修改提示词、系统提示或响应解析后递增，使旧缓存失效
+pub const PROMPT_VERSION: &str = "3";
+
 /// Enrichment 编排器
 ///
 /// 协调实体加载、静态分析、LLM标注和结果持久化
@@ -17,7 +32,21 @@ pub struct EnrichmentOrchestrator {
     root_dir: String,
     static_analyzer: Option<Arc<StaticAnalyzer>>,
     full_entities: Option<Vec<CodeEntity>>,
-    claude_client: Option<Arc<ClaudeClient>>,
+    provider: Option<Arc<dyn LlmProvider>>,
+    /// 缓存键中的提示词版本与模型，换用模型后旧缓存不再命中
+    cache_version: String,
+    /// 最近一次运行使用的检查点文件
+    checkpoint_path: Option<PathBuf>,
+    /// 最近一次运行从检查点恢复的实体数
+    resumed: usize,
+    /// 最近一次运行使用的富化缓存
+    cache: Option<Arc<EnrichmentCache>>,
+    /// 最近一次运行各来源的标注数量
+    label_stats: LabelStats,
+    /// 项目标签表（配置的或自动生成的），用于约束提示词与归一化标签
+    taxonomy: Option<TagTaxonomy>,
+    /// 最近一次运行写入的标签索引
+    tag_index_path: Option<PathBuf>,
 }
 
 impl EnrichmentOrchestrator {
@@ -37,14 +66,20 @@ impl EnrichmentOrchestrator {
             None
         };
 
-        // 尝试创建 Claude 客户端
-        let claude_client = match ClaudeClient::new() {
-            Ok(client) => {
-                println!("✅ Claude 客户端初始化成功");
-                Some(Arc::new(client))
+        // 按配置创建 LLM 提供方，未显式配置时读取项目配置
+        let project = ProjectConfig::load(&root_dir).unwrap_or_else(|e| {
+            println!("⚠️  {}，使用默认项目配置", e);
+            Default::default()
+        });
+        let llm_config = config.llm.clone().unwrap_or(project.llm);
+        let cache_version = format!("{}:{}", PROMPT_VERSION, llm_config.model());
+        let provider = match create_provider(&llm_config) {
+            Ok(provider) => {
+                println!("✅ LLM 提供方初始化成功: {} ({})", provider.name(), provider.model());
+                Some(provider)
             }
             Err(e) => {
-                println!("⚠️  Claude 客户端初始化失败: {}，将使用回退逻辑", e);
+                println!("⚠️  LLM 提供方初始化失败: {}，将使用回退逻辑", e);
                 None
             }
         };
@@ -54,10 +89,54 @@ impl EnrichmentOrchestrator {
             root_dir,
             static_analyzer,
             full_entities,
-            claude_client,
+            provider,
+            cache_version,
+            checkpoint_path: None,
+            resumed: 0,
+            cache: None,
+            label_stats: LabelStats::default(),
+            taxonomy: project.taxonomy,
+            tag_index_path: None,
         }
     }
 
+    /// 使用指定的 LLM 提供方（替换按配置创建的提供方）
+    pub fn with_provider(mut self, provider: Arc<dyn LlmProvider>) -> Self {
+        self.cache_version = format!("{}:{}", PROMPT_VERSION, provider.model());
+        self.provider = Some(provider);
+        self
+    }
+
+    /// 最近一次运行使用的检查点文件
+    pub fn checkpoint_path(&self) -> Option<&Path> {
+        self.checkpoint_path.as_deref()
+    }
+
+    /// 最近一次运行从检查点恢复（跳过）的实体数
+    pub fn resumed_count(&self) -> usize {
+        self.resumed
+    }
+
+    /// 最近一次运行的缓存命中统计，未启用缓存时为空
+    pub fn cache_stats(&self) -> Option<CacheStats> {
+        self.cache.as_ref().map(|cache| cache.stats())
+    }
+
+    /// 最近一次运行各来源（LLM / 修复 / 回退）的标注数量
+    pub fn label_stats(&self) -> LabelStats {
+        self.label_stats
+    }
+
+    /// 使用的项目标签表
+    pub fn taxonomy(&self) -> Option<&TagTaxonomy> {
+        self.taxonomy.as_ref()
+    }
+
+    /// 最近一次运行写入的标签索引（标签 -> 实体 ID）
+    pub fn tag_index_path(&self) -> Option<&Path> {
+        self.tag_index_path.as_deref()
+    }
+
     /// 运行完整的富化流程
     pub async fn run(&mut self) -> Result<String> {
         println!("🚀 开始实体富化流程...");
@@ -75,18 +154,40 @@ impl EnrichmentOrchestrator {
             .full_entities
             .clone()
             .unwrap_or_else(|| entities.clone());
-        let static_analyzer = Arc::new(StaticAnalyzer::new(&self.root_dir, entities_to_use));
+        let static_analyzer = Arc::new(StaticAnalyzer::new(&self.root_dir, entities_to_use.clone()));
         self.static_analyzer = Some(static_analyzer.clone());
 
-        // 步骤2: 为每个实体执行富化
-        let enriched_entities = self.enrich_entities(entities, static_analyzer).await?;
+        // 步骤2: 为每个实体执行富化，完成的实体追加到检查点
+        let output_path = resolve_output_path(self.config.output_path.as_str(), Some(self.root_dir.as_str()));
+        let checkpoint_path = match &self.config.checkpoint_path {
+            Some(path) => resolve_output_path(path.as_str(), Some(self.root_dir.as_str())),
+            None => Checkpoint::path_for(&output_path),
+        };
+        self.cache = self.open_cache(Some(&output_path));
+        if self.taxonomy.is_none() {
+            self.taxonomy = TagTaxonomy::load(&TagTaxonomy::path_for(&output_path))?;
+            if self.taxonomy.is_some() {
+                println!("🏷️  使用自动生成的标签表: {}", TagTaxonomy::path_for(&output_path).display());
+            }
+        }
+        let mut enriched_entities = self
+            .enrich_with_checkpoint(entities, static_analyzer, &checkpoint_path)
+            .await?;
+        self.label_stats = LabelStats::count(&enriched_entities);
+
+        // 步骤3: 标签归一化与标签索引
+        self.process_tags(&mut enriched_entities, &output_path).await?;
 
-        // 步骤3: 保存结果
+        // 步骤4: 构建依赖图
+        let graph = DependencyGraph::from_enriched(&entities_to_use, &enriched_entities);
+
+        // 步骤5: 保存结果，依赖图与富化实体放在同一目录
         let output_path = save_enriched_entities(
             enriched_entities,
             &self.config.output_path,
             Some(&self.root_dir),
         )?;
+        graph.save(&DependencyGraph::path_for(Path::new(&output_path)))?;
 
         println!("🎉 富化流程完成!");
         Ok(output_path)
@@ -118,65 +219,238 @@ impl EnrichmentOrchestrator {
             analyzer
         };
 
-        // 执行富化处理
-        let enriched_entities = self
-            .enrich_entities(entities_to_enrich, static_analyzer)
-            .await?;
+        // 执行富化处理，配置了检查点时写入检查点，配置了缓存路径时使用缓存
+        self.cache = self.open_cache(None);
+        let enriched_entities = match self.config.checkpoint_path.clone() {
+            Some(path) => {
+                let path = resolve_output_path(path.as_str(), Some(self.root_dir.as_str()));
+                self.enrich_with_checkpoint(entities_to_enrich, static_analyzer, &path)
+                    .await?
+            }
+            None => {
+                self.enrich_entities(entities_to_enrich, static_analyzer, None)
+                    .await?
+            }
+        };
 
+        let mut enriched_entities = enriched_entities;
+        normalize_tags(&mut enriched_entities, self.taxonomy.as_ref());
+        self.label_stats = LabelStats::count(&enriched_entities);
         println!("✅ 直接富化完成，处理了 {} 个实体", enriched_entities.len());
         Ok(enriched_entities)
     }
 
+    /// 打开富化缓存：优先使用配置的路径，否则放在输出文件旁；未启用缓存时为空
+    fn open_cache(&self, output_path: Option<&Path>) -> Option<Arc<EnrichmentCache>> {
+        if !self.config.use_cache {
+            return None;
+        }
+        let path = match (&self.config.cache_path, output_path) {
+            (Some(path), _) => resolve_output_path(path.as_str(), Some(self.root_dir.as_str())),
+            (None, Some(output_path)) => EnrichmentCache::path_for(output_path),
+            (None, None) => return None,
+        };
+
+        let cache = EnrichmentCache::load(&path);
+        println!("🗄️  富化缓存: {} ({} 条)", cache.path().display(), cache.stats().entries);
+        Some(Arc::new(cache))
+    }
+
+    /// 归一化标签并在输出文件旁写入标签索引；没有标签表时从本次结果生成标签表
+    async fn process_tags(&mut self, entities: &mut [EnrichedEntity], output_path: &Path) -> Result<()> {
+        let normalized = normalize_tags(entities, self.taxonomy.as_ref());
+        if let Some(parent) = output_path.parent() {
+            fs::create_dir_all(parent).context(format!("无法创建目录: {}", parent.display()))?;
+        }
+
+        let index = TagIndex::build(entities, &normalized);
+        let index_path = TagIndex::path_for(output_path);
+        index.save(&index_path)?;
+        println!("🏷️  标签索引: {} ({} 个标签)", index_path.display(), index.tags.len());
+        self.tag_index_path = Some(index_path);
+
+        if self.taxonomy.is_none() {
+            let taxonomy = self.bootstrap_taxonomy(entities, &normalized).await;
+            if !taxonomy.is_empty() {
+                let path = TagTaxonomy::path_for(output_path);
+                taxonomy.save(&path)?;
+                println!(
+                    "🏷️  生成标签表: {} ({} 个标签)，后续运行按标签表约束标签",
+                    path.display(),
+                    taxonomy.tags.len()
+                );
+            }
+        }
+        Ok(())
+    }
+
+    /// 从本次结果生成标签表
+    ///
+    /// 配置了 LLM 时先请其归并同义标签（如 `order` 与 `订单`），失败时只按使用次数生成
+    async fn bootstrap_taxonomy(&self, entities: &[EnrichedEntity], normalized: &NormalizedTags) -> TagTaxonomy {
+        let counts = tag_counts(entities);
+        let groups = match &self.provider {
+            Some(provider) if counts.len() > 1 => {
+                let system_prompt = "你是一个代码库标签整理助手。请按要求归并同义标签，只返回 JSON。";
+                let result = provider
+                    .complete_json(system_prompt, &synonym_prompt(&counts), &taxonomy_schema())
+                    .await
+                    .and_then(|output| TagTaxonomy::parse(&output));
+                match result {
+                    Ok(groups) => Some(groups),
+                    Err(e) => {
+                        println!("⚠️  LLM 归并同义标签失败，按使用次数生成标签表: {}", e);
+                        None
+                    }
+                }
+            }
+            _ => None,
+        };
+        TagTaxonomy::bootstrap(entities, &normalized.variants, groups.as_ref(), BOOTSTRAP_MIN_ENTITIES)
+    }
+
     /// 加载并验证实体
     fn load_and_validate_entities(&self, input_path: &str) -> Result<Vec<CodeEntity>> {
         let entities = load_entities(input_path, Some(&self.root_dir))?;
         Ok(validate_entities(entities))
     }
 
+    /// 带检查点的批量富化
+    ///
+    /// 恢复模式下跳过检查点中输入指纹未变化的实体，其余实体完成后逐个追加到检查点
+    /// （回退标注的实体不追加，下次恢复时重新请求 LLM）
+    async fn enrich_with_checkpoint(
+        &mut self,
+        entities: Vec<CodeEntity>,
+        static_analyzer: Arc<StaticAnalyzer>,
+        checkpoint_path: &Path,
+    ) -> Result<Vec<EnrichedEntity>> {
+        let (checkpoint, records) = Checkpoint::open(checkpoint_path, self.config.resume)?;
+        println!("📝 检查点: {}", checkpoint.path().display());
+
+        let mut completed = Vec::new();
+        let mut pending = Vec::new();
+        for entity in entities {
+            let code = static_analyzer.code_snippet(&entity).unwrap_or_default();
+            match records.get(&entity.id) {
+                Some(record) if record.fingerprint == checkpoint::fingerprint(&entity, &code) => {
+                    completed.push(record.entity.clone());
+                }
+                _ => pending.push(entity),
+            }
+        }
+        if !completed.is_empty() {
+            println!(
+                "⏩ 从检查点恢复 {} 个实体，剩余 {} 个待处理",
+                completed.len(),
+                pending.len()
+            );
+        }
+        self.checkpoint_path = Some(checkpoint_path.to_path_buf());
+        self.resumed = completed.len();
+
+        completed.extend(
+            self.enrich_entities(pending, static_analyzer, Some(Arc::new(checkpoint)))
+                .await?,
+        );
+        Ok(completed)
+    }
+
     /// 批量富化实体
     async fn enrich_entities(
         &self,
         entities: Vec<CodeEntity>,
         static_analyzer: Arc<StaticAnalyzer>,
+        checkpoint: Option<Arc<Checkpoint>>,
     ) -> Result<Vec<EnrichedEntity>> {
         println!("📦 开始处理 {} 个实体...", entities.len());
 
         let concurrency = self.config.concurrency;
         let max_retries = self.config.max_retries;
         let retry_delay = self.config.retry_delay;
-        let claude_client = self.claude_client.clone();
+        let provider = self.provider.clone();
+        let cache = self.cache.clone();
+
+        // 标签表写入提示词，也参与缓存键
+        let vocabulary = self
+            .taxonomy
+            .as_ref()
+            .filter(|taxonomy| !taxonomy.is_empty())
+            .map(|taxonomy| taxonomy.prompt_text());
+        let cache_version = match &vocabulary {
+            Some(vocabulary) => format!("{}:{}", self.cache_version, hash_content(vocabulary.as_bytes())),
+            None => self.cache_version.clone(),
+        };
+        let cache_version = cache_version.as_str();
+        let vocabulary = vocabulary.as_deref();
 
         // 使用 futures 流处理并发
         let results: Vec<EnrichedEntity> = stream::iter(entities)
             .map(|entity| {
                 let analyzer = static_analyzer.clone();
-                let client = claude_client.clone();
+                let provider = provider.clone();
+                let checkpoint = checkpoint.clone();
+                let cache = cache.clone();
                 let retries = max_retries;
                 let delay = retry_delay;
 
                 async move {
-                    Self::enrich_entity_with_retry(entity, analyzer, client, retries, delay).await
+                    let cache = cache.as_deref().map(|cache| (cache, cache_version));
+                    match Self::enrich_entity_with_retry(&entity, &analyzer, provider, vocabulary, cache, retries, delay).await {
+                        Ok(enriched) => {
+                            if let Some(checkpoint) = checkpoint {
+                                let code = analyzer.code_snippet(&entity).unwrap_or_default();
+                                if let Err(e) = checkpoint.append(&checkpoint::fingerprint(&entity, &code), &enriched) {
+                                    eprintln!("⚠️  {}", e);
+                                }
+                            }
+                            enriched
+                        }
+                        Err(e) => Self::failed_entity(&entity, &e),
+                    }
                 }
             })
             .buffer_unordered(concurrency)
             .collect()
             .await;
 
+        let stats = LabelStats::count(&results);
         println!("✅ 完成 {} 个实体的富化", results.len());
+        println!(
+            "🏷️  标注来源: LLM {}，修复 {}，强制约束 {}，回退 {}，失败 {}",
+            stats.llm, stats.repair, stats.enforced, stats.fallback, stats.failed
+        );
+
+        if let Some(cache) = &self.cache {
+            let stats = cache.stats();
+            println!(
+                "🗄️  缓存命中 {} 次，未命中 {} 次（命中率 {:.1}%），共 {} 条",
+                stats.hits,
+                stats.misses,
+                stats.hit_rate() * 100.0,
+                stats.entries
+            );
+            if let Err(e) = cache.save() {
+                eprintln!("⚠️  {}", e);
+            }
+        }
+
         Ok(results)
     }
 
     /// 富化单个实体（带重试）
     async fn enrich_entity_with_retry(
-        entity: CodeEntity,
-        static_analyzer: Arc<StaticAnalyzer>,
-        claude_client: Option<Arc<ClaudeClient>>,
+        entity: &CodeEntity,
+        static_analyzer: &StaticAnalyzer,
+        provider: Option<Arc<dyn LlmProvider>>,
+        vocabulary: Option<&str>,
+        cache: Option<(&EnrichmentCache, &str)>,
         mut retries_left: usize,
         retry_delay: u64,
-    ) -> EnrichedEntity {
+    ) -> Result<EnrichedEntity> {
         loop {
-            match Self::enrich_entity(&entity, &static_analyzer, &claude_client).await {
-                Ok(enriched) => return enriched,
+            match Self::enrich_entity(entity, static_analyzer, provider.as_deref(), vocabulary, cache).await {
+                Ok(enriched) => return Ok(enriched),
                 Err(e) => {
                     if retries_left > 0 {
                         println!(
@@ -187,28 +461,40 @@ impl EnrichmentOrchestrator {
                         sleep(Duration::from_millis(retry_delay)).await;
                     } else {
                         println!("❌ 处理实体 {} 最终失败: {}", entity.id, e);
-                        // 返回带有错误信息的部分富化实体
-                        return EnrichedEntity {
-                            base: entity.clone(),
-                            imports: vec![],
-                            calls: vec![],
-                            emits: vec![],
-                            template_components: None,
-                            annotation: None,
-                            summary: format!("处理失败: {}", e),
-                            tags: vec!["处理失败".to_string()],
-                        };
+                        return Err(e);
                     }
                 }
             }
         }
     }
 
+    /// 带有错误信息的部分富化实体（不写入检查点，恢复时重新处理）
+    fn failed_entity(entity: &CodeEntity, error: &anyhow::Error) -> EnrichedEntity {
+        EnrichedEntity {
+            base: entity.clone(),
+            imports: vec![],
+            calls: vec![],
+            emits: vec![],
+            template_components: None,
+            template_usages: None,
+            store_usages: None,
+            annotation: None,
+            summary: format!("处理失败: {}", error),
+            tags: vec!["处理失败".to_string()],
+            label_source: None,
+        }
+    }
+
     /// 富化单个实体
+    ///
+    /// 代码片段与静态分析结果都未变化时复用缓存中的摘要和标签，不再调用 LLM。
+    /// `vocabulary` 为写入提示词的项目标签表，`cache` 为缓存及其键中的提示词版本与模型
     async fn enrich_entity(
         entity: &CodeEntity,
         static_analyzer: &StaticAnalyzer,
-        claude_client: &Option<Arc<ClaudeClient>>,
+        provider: Option<&dyn LlmProvider>,
+        vocabulary: Option<&str>,
+        cache: Option<(&EnrichmentCache, &str)>,
     ) -> Result<EnrichedEntity> {
         println!("🔍 处理实体: {}", entity.id);
 
@@ -218,12 +504,26 @@ impl EnrichmentOrchestrator {
             .await
             .context("静态分析失败")?;
 
-        // 步骤2: 调用LLM生成摘要和标签
-        let llm_response = if let Some(client) = claude_client {
-            match Self::generate_labels_with_llm(entity, &analysis_result, client).await {
-                Ok(response) => {
-                    println!("✅ LLM 分析成功: {}", entity.id);
-                    response
+        // 步骤2: 查询缓存
+        let cache_key = cache.map(|(_, version)| {
+            let code = static_analyzer.code_snippet(entity).unwrap_or_default();
+            EnrichmentCache::key(version, &code, &analysis_result)
+        });
+        let cache = cache.map(|(cache, _)| cache);
+        let cached = cache.zip(cache_key.as_deref()).and_then(|(cache, key)| cache.get(key));
+
+        // 步骤3: 调用LLM生成摘要和标签
+        let (llm_response, label_source) = if let Some(cached) = cached {
+            println!("♻️  命中缓存: {}", entity.id);
+            cached
+        } else if let Some(provider) = provider {
+            match Self::generate_labels_with_llm(entity, &analysis_result, provider, vocabulary).await {
+                Ok((response, source)) => {
+                    println!("✅ LLM 分析完成: {} ({:?})", entity.id, source);
+                    if let (Some(cache), Some(key)) = (cache, cache_key) {
+                        cache.insert(key, &entity.id, &response, source);
+                    }
+                    (response, source)
                 }
                 Err(e) => {
                     // 详细打印错误信息
@@ -251,12 +551,12 @@ impl EnrichmentOrchestrator {
                     eprintln!("{:#?}", e);
                     eprintln!("=====================================\n");
 
-                    Self::generate_labels_fallback(entity, &analysis_result)
+                    (Self::generate_labels_fallback(entity, &analysis_result), LabelSource::Fallback)
                 }
             }
         } else {
             println!("ℹ️  使用回退逻辑生成标签: {}", entity.id);
-            Self::generate_labels_fallback(entity, &analysis_result)
+            (Self::generate_labels_fallback(entity, &analysis_result), LabelSource::Fallback)
         };
 
         // 返回富化后的实体
@@ -266,20 +566,27 @@ impl EnrichmentOrchestrator {
             calls: analysis_result.calls,
             emits: analysis_result.emits,
             template_components: analysis_result.template_components,
+            template_usages: analysis_result.template_usages,
+            store_usages: analysis_result.store_usages,
             annotation: analysis_result.annotation,
             summary: llm_response.summary,
             tags: llm_response.tags,
+            label_source: Some(label_source),
         })
     }
 
     /// 使用 LLM 生成标签和摘要
+    ///
+    /// 输出按 schema 校验，未通过时带上问题列表请求一次修复；修复后仍不合格时在本地强制约束，
+    /// 无法得到摘要时使用回退逻辑
     async fn generate_labels_with_llm(
         entity: &CodeEntity,
         analysis: &StaticAnalysisResult,
-        claude_client: &ClaudeClient,
-    ) -> Result<LLMResponse> {
+        provider: &dyn LlmProvider,
+        vocabulary: Option<&str>,
+    ) -> Result<(LLMResponse, LabelSource)> {
         // 1. 构建提示词
-        let prompt = Self::build_llm_prompt(entity, analysis);
+        let prompt = Self::build_llm_prompt(entity, analysis, vocabulary);
 
         // 2. 构建系统提示
         let system_prompt = r#"你是一个代码理解助手。请为以下代码实体生成简洁的业务摘要和标签。
@@ -297,24 +604,44 @@ impl EnrichmentOrchestrator {
 4. 摘要必须在160个字符以内。
 5. 标签数量为3-5个。"#;
 
-        // 3. 调用 Claude API
-        let response = claude_client
-            .send_message(
-                vec![Message::user(prompt)],
-                Some(system_prompt.to_string()),
-                2048,
-                None, // 暂时不使用工具，后续可以扩展
-            )
+        // 3. 调用 LLM 并校验输出
+        let schema = label_schema();
+        let output = provider
+            .complete_json(system_prompt, &prompt, &schema)
             .await
-            .context("Claude API 调用失败")?;
+            .context(format!("LLM 调用失败 ({}: {})", provider.name(), provider.model()))?;
+        let issues = match validate_labels(&output) {
+            Ok(response) => return Ok((response, LabelSource::Llm)),
+            Err(issues) => issues,
+        };
 
-        // 4. 解析响应
-        let response_text = response.get_text();
-        Self::parse_llm_response(&response_text, entity, analysis)
+        // 4. 请求修复
+        println!("🔧 LLM 输出未通过校验，请求修复: {} ({})", entity.id, issues.problems.join("; "));
+        let repair = format!("{}\n\n{}", prompt, repair_prompt(&output, &issues.problems));
+        let candidate = match provider.complete_json(system_prompt, &repair, &schema).await {
+            Ok(output) => match validate_labels(&output) {
+                Ok(response) => return Ok((response, LabelSource::Repair)),
+                Err(repaired) => repaired.candidate.or(issues.candidate),
+            },
+            Err(e) => {
+                println!("⚠️  修复请求失败: {}", e);
+                issues.candidate
+            }
+        };
+
+        // 5. 本地强制约束，仍不可用时回退
+        let fallback = Self::generate_labels_fallback(entity, analysis);
+        match candidate.and_then(|candidate| enforce_constraints(candidate, &fallback.tags)) {
+            Some(response) => Ok((response, LabelSource::Enforced)),
+            None => {
+                println!("⚠️  LLM 输出无法修复，使用回退逻辑: {}", entity.id);
+                Ok((fallback, LabelSource::Fallback))
+            }
+        }
     }
 
     /// 构建 LLM 提示词
-    fn build_llm_prompt(entity: &CodeEntity, analysis: &StaticAnalysisResult) -> String {
+    fn build_llm_prompt(entity: &CodeEntity, analysis: &StaticAnalysisResult, vocabulary: Option<&str>) -> String {
         let imports_text = if analysis.imports.is_empty() {
             "  无".to_string()
         } else {
@@ -349,6 +676,30 @@ impl EnrichmentOrchestrator {
             String::new()
         };
 
+        let parent_text = if let Some(parent) = &entity.parent {
+            format!("- 所属: {}\n", parent)
+        } else {
+            String::new()
+        };
+
+        let details_text = {
+            let mut lines = Vec::new();
+            if let Some(signature) = &entity.signature {
+                lines.push(format!("- 签名: {}", signature.display(&entity.raw_name)));
+            }
+            lines.push(format!("- 导出: {}", if entity.is_exported { "是" } else { "否" }));
+            if !entity.modifiers.is_empty() {
+                lines.push(format!("- 修饰符: {}", entity.modifiers.join(" ")));
+            }
+            if !entity.decorators.is_empty() {
+                lines.push(format!("- 装饰器: {}", entity.decorators.join(" ")));
+            }
+            if let Some(doc) = &entity.doc_comment {
+                lines.push(format!("- 文档注释: {}", doc.replace('\n', " ")));
+            }
+            lines.iter().map(|l| format!("{}\n", l)).collect::<String>()
+        };
+
         let template_components_text = if let Some(components) = &analysis.template_components {
             if !components.is_empty() {
                 format!("- 模板组件: {}\n", components.join(", "))
@@ -359,12 +710,60 @@ impl EnrichmentOrchestrator {
             String::new()
         };
 
+        // 子组件的使用方式：传入的 props、监听的事件和插槽
+        let template_usages_text: String = analysis
+            .template_usages
+            .iter()
+            .flatten()
+            .filter(|u| !u.props.is_empty() || !u.events.is_empty() || !u.slots.is_empty())
+            .map(|u| {
+                let mut parts = Vec::new();
+                if !u.props.is_empty() {
+                    parts.push(format!("props: {}", u.props.join(", ")));
+                }
+                if !u.events.is_empty() {
+                    parts.push(format!("事件: {}", u.events.join(", ")));
+                }
+                if !u.slots.is_empty() {
+                    parts.push(format!("插槽: {}", u.slots.join(", ")));
+                }
+                format!("  - <{}> {}\n", u.tag, parts.join("; "))
+            })
+            .collect();
+        let template_components_text = if template_usages_text.is_empty() {
+            template_components_text
+        } else {
+            format!("{}- 子组件接口:\n{}", template_components_text, template_usages_text)
+        };
+
+        // 使用的 store 成员
+        let store_usages_text: String = analysis
+            .store_usages
+            .iter()
+            .flatten()
+            .map(|u| format!("  - {}: {}\n", u.store_id, u.members.join(", ")))
+            .collect();
+        let template_components_text = if store_usages_text.is_empty() {
+            template_components_text
+        } else {
+            format!("{}- 使用的 store:\n{}", template_components_text, store_usages_text)
+        };
+
+        // 项目标签表
+        let vocabulary_text = match vocabulary {
+            Some(vocabulary) => format!(
+                "\n   - 标签只能从项目标签表中选择，使用表中的原文：{}\n   - 不要使用标签表之外的标签",
+                vocabulary
+            ),
+            None => String::new(),
+        };
+
         format!(
             r#"实体信息:
 - 类型: {}
 - 名称: {}
 - 文件: {}
-{}
+{}{}{}
 代码分析:
 - 导入:
 {}
@@ -389,59 +788,21 @@ impl EnrichmentOrchestrator {
    - 标签应该反映实体的功能、类型、用途等特征
    - 优先使用业务相关的标签
    - 避免过于宽泛的标签（如"组件"、"函数"等）
-   - 如果注释中包含业务信息，请优先使用注释内容来生成标签"#,
+   - 如果注释中包含业务信息，请优先使用注释内容来生成标签{}"#,
             entity.entity_type,
             entity.raw_name,
             entity.file,
+            parent_text,
+            details_text,
             annotation_text,
             imports_text,
             calls_text,
             emits_text,
-            template_components_text
+            template_components_text,
+            vocabulary_text
         )
     }
 
-    /// 解析 LLM 响应
-    fn parse_llm_response(
-        text: &str,
-        entity: &CodeEntity,
-        analysis: &StaticAnalysisResult,
-    ) -> Result<LLMResponse> {
-        use regex::Regex;
-
-        // 尝试提取 JSON 部分
-        let json_regex = Regex::new(r"\{[\s\S]*?\}").unwrap();
-
-        if let Some(json_match) = json_regex.find(text) {
-            let json_text = json_match.as_str();
-
-            // 尝试解析 JSON
-            match serde_json::from_str::<LLMResponse>(json_text) {
-                Ok(mut response) => {
-                    // 确保摘要不超过 160 个字符
-                    if response.summary.chars().count() > 160 {
-                        response.summary = response.summary.chars().take(160).collect();
-                    }
-
-                    // 确保标签数量在 3-5 个
-                    if response.tags.is_empty() {
-                        response.tags = vec![entity.entity_type.clone()];
-                    }
-                    response.tags.truncate(5);
-
-                    Ok(response)
-                }
-                Err(e) => {
-                    println!("⚠️  解析 LLM 响应失败: {}", e);
-                    Ok(Self::generate_labels_fallback(entity, analysis))
-                }
-            }
-        } else {
-            println!("⚠️  LLM 响应中未找到 JSON 格式");
-            Ok(Self::generate_labels_fallback(entity, analysis))
-        }
-    }
-
     /// LLM 标签生成的回退逻辑（简化版本）
     fn generate_labels_fallback(
         entity: &CodeEntity,
@@ -486,3 +847,84 @@ impl EnrichmentOrchestrator {
         LLMResponse { summary, tags }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tool_execution::codebase::enrichment::MockProvider;
+    use crate::tool_execution::codebase::LocationInfo;
+    use std::fs;
+
+    #[tokio::test]
+    async fn test_enrich_with_mock_provider_repair_and_cache() {
+        let root = Path::new("/tmp/test_orchestrator_provider");
+        fs::remove_dir_all(root).ok();
+        fs::create_dir_all(root.join("src")).unwrap();
+        fs::write(
+            root.join("src/date.ts"),
+            "export function formatDate(d: Date) {\n  return d.toISOString();\n}\n",
+        )
+        .unwrap();
+
+        let entity = CodeEntity {
+            id: "Function:formatDate".to_string(),
+            entity_type: "function".to_string(),
+            file: "src/date.ts".to_string(),
+            loc: LocationInfo { start_line: 1, end_line: 3 },
+            raw_name: "formatDate".to_string(),
+            ..Default::default()
+        };
+        let config = || EnrichmentConfig {
+            cache_path: Some("cache.json".to_string()),
+            ..Default::default()
+        };
+
+        let mock = Arc::new(MockProvider::new());
+        for _ in 0..2 {
+            let mut orchestrator =
+                EnrichmentOrchestrator::new(root.to_string_lossy().to_string(), Some(config()), None)
+                    .with_provider(mock.clone());
+            let enriched = orchestrator
+                .enrich_entities_directly(vec![entity.clone()], vec![entity.clone()])
+                .await
+                .unwrap();
+            assert_eq!(enriched[0].summary, "function formatDate 的模拟摘要");
+            assert_eq!(enriched[0].label_source, Some(LabelSource::Llm));
+        }
+
+        // 第二次运行命中缓存，不再调用提供方
+        assert_eq!(mock.calls(), 1);
+
+        // 标签不足时请求修复；修复后仍不足时本地补足标签；两次输出都无法解析时回退
+        let cases = [
+            (r#"{"summary": "格式化日期", "tags": ["日期"]}"#, LabelSource::Repair),
+            (r#"{"summary": "格式化日期", "tags": ["日期"]}"#, LabelSource::Enforced),
+            ("无法分析", LabelSource::Fallback),
+        ];
+        for (first, source) in cases {
+            let mock = Arc::new(MockProvider::with_responses(vec![
+                first.to_string(),
+                match source {
+                    LabelSource::Repair => r#"{"summary": "格式化日期", "tags": ["日期", "格式化", "工具"]}"#,
+                    LabelSource::Enforced => r#"{"summary": "格式化日期", "tags": ["日期", "日期"]}"#,
+                    _ => "仍然无法分析",
+                }
+                .to_string(),
+            ]));
+            let config = EnrichmentConfig {
+                use_cache: false,
+                ..Default::default()
+            };
+            let mut orchestrator = EnrichmentOrchestrator::new(root.to_string_lossy().to_string(), Some(config), None)
+                .with_provider(mock.clone());
+            let enriched = orchestrator
+                .enrich_entities_directly(vec![entity.clone()], vec![entity.clone()])
+                .await
+                .unwrap();
+            assert_eq!(enriched[0].label_source, Some(source));
+            assert_eq!(mock.calls(), 2);
+        }
+
+        fs::remove_dir_all(root).ok();
+    }
+}
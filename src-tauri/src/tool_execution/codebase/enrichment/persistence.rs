@@ -1,24 +1,28 @@
 use super::interfaces::EnrichedEntity;
 use anyhow::{Context, Result};
 use std::fs;
-use std::path::Path;
+use std::path::{Path, PathBuf};
 
-/// 保存富化后的实体到文件
-pub fn save_enriched_entities<P: AsRef<Path>>(
-    entities: Vec<EnrichedEntity>,
-    output_path: P,
-    root_dir: Option<P>,
-) -> Result<String> {
+/// 输出文件的完整路径：相对路径拼接根目录
+pub fn resolve_output_path<P: AsRef<Path>>(output_path: P, root_dir: Option<P>) -> PathBuf {
     let path_ref = output_path.as_ref();
 
-    // 如果是相对路径，拼接根目录
-    let full_path = if path_ref.is_absolute() {
+    if path_ref.is_absolute() {
         path_ref.to_path_buf()
     } else if let Some(root) = root_dir {
         root.as_ref().join(path_ref)
     } else {
         path_ref.to_path_buf()
-    };
+    }
+}
+
+/// 保存富化后的实体到文件
+pub fn save_enriched_entities<P: AsRef<Path>>(
+    entities: Vec<EnrichedEntity>,
+    output_path: P,
+    root_dir: Option<P>,
+) -> Result<String> {
+    let full_path = resolve_output_path(output_path, root_dir);
 
     println!("💾 保存富化实体到: {}", full_path.display());
 
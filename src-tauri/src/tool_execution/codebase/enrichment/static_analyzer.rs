@@ -1,10 +1,56 @@
 use anyhow::{Context, Result};
+use lazy_static::lazy_static;
 use regex::Regex;
-use std::collections::{HashMap, HashSet};
+use std::collections::{BTreeSet, HashMap, HashSet};
 use std::fs;
 use std::path::{Path, PathBuf};
-use crate::tool_execution::codebase::CodeEntity;
-use super::interfaces::StaticAnalysisResult;
+use std::sync::OnceLock;
+use crate::tool_execution::codebase::entity_id::short_name;
+use crate::tool_execution::codebase::module_resolver::{normalize_path, ModuleResolver};
+use crate::tool_execution::codebase::{CodeEntity, DependencyEdge, DependencyGraph, StoreStyle, WorkspaceInfo};
+use super::interfaces::{ComponentUsage, DynamicImport, FileImport, Route, StaticAnalysisResult, StoreUsage};
+use super::syntax::{self, Callee, ImportedName, RouteComponent, RouteDefinition, StoreAccess};
+use super::template;
+
+/// `export ... from` 转发链的最大深度
+const MAX_REEXPORT_DEPTH: usize = 8;
+
+lazy_static! {
+    // Vue SFC 的 <script> 块及其属性
+    static ref SCRIPT_BLOCK_REGEX: Regex = Regex::new(r"<script(\s[^>]*)?>([\s\S]*?)</script>").unwrap();
+    static ref SCRIPT_LANG_REGEX: Regex = Regex::new(r#"\blang\s*=\s*["']([^"']+)["']"#).unwrap();
+    static ref SCRIPT_SETUP_REGEX: Regex = Regex::new(r"\bsetup\b").unwrap();
+
+    // emit('event-name') 或 $emit('event-name')
+    static ref VUE_EMIT_REGEX: Regex = Regex::new(r#"(?:\$?emit)\s*\(\s*['"]([^'"]+)['"]"#).unwrap();
+
+    // props.onXxx()
+    static ref JSX_EMIT_REGEX: Regex = Regex::new(r#"props\.on([A-Z]\w+)\s*\("#).unwrap();
+
+    // 文件开头的块注释 / 单行注释
+    static ref BLOCK_COMMENT_REGEX: Regex = Regex::new(r"^[\s\n]*/\*\*?([\s\S]*?)\*/").unwrap();
+    static ref LINE_COMMENT_REGEX: Regex = Regex::new(r"^[\s\n]*//\s*(.+)$").unwrap();
+}
+
+/// 文件级导入的解析结果
+#[derive(Debug, Default)]
+struct ResolvedImports {
+    /// 导入的实体 ID
+    entities: Vec<String>,
+    /// 本地绑定名 -> 实体 ID（`foo()`）
+    bindings: HashMap<String, Vec<String>>,
+    /// 命名空间绑定名 -> (成员名 -> 实体 ID)（`ns.foo()`）
+    namespaces: HashMap<String, HashMap<String, Vec<String>>>,
+}
+
+/// 待分析的一段脚本：TS/JS 文件整体或 Vue 的一个 `<script>` 块
+struct ScriptBlock {
+    source: String,
+    /// 脚本首行之前的行数（Vue 文件中 `<script>` 标签所在行）
+    line_offset: usize,
+    /// `<script lang="...">`
+    lang: Option<String>,
+}
 
 /// 静态分析器
 /// 
@@ -16,11 +62,29 @@ use super::interfaces::StaticAnalysisResult;
 pub struct StaticAnalyzer {
     root_dir: PathBuf,
     entities: Vec<CodeEntity>,
-    entity_map: HashMap<String, HashMap<String, CodeEntity>>,
+    entity_map: HashMap<String, Vec<CodeEntity>>,
+    resolver: ModuleResolver,
+    /// 全局注册的组件（按需计算）
+    global_components: OnceLock<HashMap<String, Vec<String>>>,
 }
 
 impl StaticAnalyzer {
     pub fn new<P: AsRef<Path>>(root_dir: P, entities: Vec<CodeEntity>) -> Self {
+        let resolver = ModuleResolver::new(root_dir.as_ref());
+        Self::with_resolver(root_dir, entities, resolver)
+    }
+    
+    /// 使用已解析的 workspace 信息创建分析器，避免重复遍历 workspace
+    pub fn with_workspace<P: AsRef<Path>>(
+        root_dir: P,
+        entities: Vec<CodeEntity>,
+        workspace: Option<&WorkspaceInfo>,
+    ) -> Self {
+        let resolver = ModuleResolver::with_workspace(root_dir.as_ref(), workspace);
+        Self::with_resolver(root_dir, entities, resolver)
+    }
+    
+    fn with_resolver<P: AsRef<Path>>(root_dir: P, entities: Vec<CodeEntity>, resolver: ModuleResolver) -> Self {
         let root_dir = root_dir.as_ref().to_path_buf();
         let entity_map = Self::build_entity_map(&entities);
         
@@ -28,6 +92,8 @@ impl StaticAnalyzer {
             root_dir,
             entities,
             entity_map,
+            resolver,
+            global_components: OnceLock::new(),
         }
     }
     
@@ -35,16 +101,20 @@ impl StaticAnalyzer {
     pub fn set_entities(&mut self, entities: Vec<CodeEntity>) {
         self.entity_map = Self::build_entity_map(&entities);
         self.entities = entities;
+        self.global_components = OnceLock::new();
     }
     
-    /// 构建实体映射表（file -> rawName -> entity）
-    fn build_entity_map(entities: &[CodeEntity]) -> HashMap<String, HashMap<String, CodeEntity>> {
-        let mut map: HashMap<String, HashMap<String, CodeEntity>> = HashMap::new();
+    /// 构建实体映射表（file -> entities）
+    ///
+    /// 只收录顶层实体，类方法等子实体不能被单独导入；
+    /// 同名实体（如同名的类型与常量）都会保留
+    fn build_entity_map(entities: &[CodeEntity]) -> HashMap<String, Vec<CodeEntity>> {
+        let mut map: HashMap<String, Vec<CodeEntity>> = HashMap::new();
         
-        for entity in entities {
+        for entity in entities.iter().filter(|e| e.parent.is_none()) {
             map.entry(entity.file.clone())
-                .or_insert_with(HashMap::new)
-                .insert(entity.raw_name.clone(), entity.clone());
+                .or_default()
+                .push(entity.clone());
         }
         
         map
@@ -63,6 +133,8 @@ impl StaticAnalyzer {
                 calls: vec![],
                 emits: vec![],
                 template_components: None,
+                template_usages: None,
+                store_usages: None,
                 annotation: None,
             });
         }
@@ -74,7 +146,10 @@ impl StaticAnalyzer {
         // 根据文件类型选择分析方法
         let result = if entity.file.ends_with(".vue") {
             self.analyze_vue_file(&content, entity)?
-        } else if entity.file.ends_with(".ts") || entity.file.ends_with(".tsx") {
+        } else if [".ts", ".tsx", ".js", ".jsx", ".mjs", ".cjs"]
+            .iter()
+            .any(|ext| entity.file.ends_with(ext))
+        {
             self.analyze_ts_file(&content, entity)?
         } else {
             StaticAnalysisResult {
@@ -82,6 +157,8 @@ impl StaticAnalyzer {
                 calls: vec![],
                 emits: vec![],
                 template_components: None,
+                template_usages: None,
+                store_usages: None,
                 annotation: None,
             }
         };
@@ -89,27 +166,361 @@ impl StaticAnalyzer {
         Ok(result)
     }
     
-    /// 分析 Vue 文件
-    fn analyze_vue_file(&self, content: &str, entity: &CodeEntity) -> Result<StaticAnalysisResult> {
-        let mut result = StaticAnalysisResult {
-            imports: vec![],
-            calls: vec![],
-            emits: vec![],
-            template_components: None,
-            annotation: None,
+    /// 实体的代码片段
+    pub fn code_snippet(&self, entity: &CodeEntity) -> Result<String> {
+        let file_path = self.root_dir.join(&entity.file);
+        let content = fs::read_to_string(&file_path)
+            .context(format!("无法读取文件: {}", file_path.display()))?;
+        
+        Ok(Self::entity_code(&content, entity))
+    }
+    
+    /// 查找直接依赖目标实体的实体（谁导入 / 调用 / 渲染了它）
+    ///
+    /// 先按文件内容是否出现目标名称筛选候选实体，再逐个静态分析，
+    /// 结果经 [`DependencyGraph`] 汇总为反向边
+    pub async fn find_dependents(&self, target: &CodeEntity) -> Result<Vec<DependencyEdge>> {
+        let names = Self::reference_names(target);
+        let mut graph = DependencyGraph::new();
+        for entity in &self.entities {
+            graph.add_entity(entity);
+        }
+        
+        let mut mentions: HashMap<&str, bool> = HashMap::new();
+        for entity in self.entities.iter().filter(|e| e.id != target.id) {
+            let mentioned = *mentions.entry(entity.file.as_str()).or_insert_with(|| {
+                fs::read_to_string(self.root_dir.join(&entity.file))
+                    .map(|content| names.iter().any(|name| content.contains(name.as_str())))
+                    .unwrap_or(false)
+            });
+            if !mentioned {
+                continue;
+            }
+            
+            let analysis = self.analyze_entity(entity).await?;
+            graph.add_analysis(&entity.id, &analysis);
+        }
+        
+        Ok(graph.dependents(&target.id, &[]).into_iter().cloned().collect())
+    }
+    
+    /// 静态分析全部实体，构建项目级依赖图（不经过 LLM 富化）
+    pub async fn build_dependency_graph(&self) -> Result<DependencyGraph> {
+        let mut graph = DependencyGraph::new();
+        for entity in &self.entities {
+            graph.add_entity(entity);
+        }
+        
+        for entity in &self.entities {
+            let analysis = self.analyze_entity(entity).await?;
+            graph.add_analysis(&entity.id, &analysis);
+        }
+        
+        Ok(graph)
+    }
+    
+    /// 收集项目内文件之间的导入关系
+    ///
+    /// `files` 为扫描到的全部文件（不产出实体的 barrel 文件、入口文件也在其中），
+    /// 为空时使用实体所在的文件。只保留解析到项目内文件的导入，第三方依赖被忽略
+    pub fn file_imports(&self, files: &[String]) -> Vec<FileImport> {
+        let files: BTreeSet<&str> = if files.is_empty() {
+            self.entities.iter().map(|e| e.file.as_str()).collect()
+        } else {
+            files.iter().map(|f| f.as_str()).collect()
         };
+        files.into_iter().flat_map(|file| self.imports_of_file(file)).collect()
+    }
+    
+    /// 单个文件中解析到项目内文件的导入（文件可以不包含任何实体，如只有启动代码的 `main.ts`）
+    ///
+    /// 转发导出 `export ... from` 同样是对源模块的依赖，记为 `reexport` 导入
+    pub fn imports_of_file(&self, file: &str) -> Vec<FileImport> {
+        let mut result = Vec::new();
         
-        // 提取 <script> 部分
-        if let Some(script_content) = self.extract_script_section(content) {
-            result.imports = self.extract_imports(&script_content, &entity.file);
-            result.calls = self.extract_calls(&script_content, &result.imports);
-            result.emits = self.extract_vue_emits(&script_content);
-            result.annotation = self.extract_annotation(&script_content);
+        for (block, tree) in self.parse_file(file) {
+            for statement in syntax::import_statements(&tree, &block.source) {
+                let Some(target) = self.resolver.resolve(&statement.specifier, Path::new(file)) else {
+                    continue;
+                };
+                let to_file = self.resolver.entity_file(&target);
+                if Path::new(&to_file).is_absolute() || to_file.contains("node_modules") {
+                    continue;
+                }
+                
+                let mut imported: Vec<String> = Vec::new();
+                for (name, _) in &statement.names {
+                    for id in self.find_export(&to_file, name, 0) {
+                        if !imported.contains(&id) {
+                            imported.push(id);
+                        }
+                    }
+                }
+                
+                let line = statement.line + block.line_offset;
+                result.push(FileImport {
+                    from_file: file.to_string(),
+                    line,
+                    specifier: statement.specifier,
+                    to_file,
+                    imported,
+                    from_entity: self.innermost_entity(file, line),
+                    type_only: statement.type_only,
+                    dynamic: statement.dynamic,
+                    reexport: false,
+                });
+            }
+            
+            for reexport in syntax::reexports(&tree, &block.source) {
+                let Some(target) = self.resolver.resolve(&reexport.specifier, Path::new(file)) else {
+                    continue;
+                };
+                let to_file = self.resolver.entity_file(&target);
+                if Path::new(&to_file).is_absolute() || to_file.contains("node_modules") {
+                    continue;
+                }
+                
+                let names: Vec<ImportedName> = match &reexport.names {
+                    Some(names) => names.iter().map(|(source, _)| source.clone()).collect(),
+                    None => vec![ImportedName::Namespace],
+                };
+                let mut imported: Vec<String> = Vec::new();
+                for name in &names {
+                    for id in self.find_export(&to_file, name, 0) {
+                        if !imported.contains(&id) {
+                            imported.push(id);
+                        }
+                    }
+                }
+                
+                result.push(FileImport {
+                    from_file: file.to_string(),
+                    line: reexport.line + block.line_offset,
+                    specifier: reexport.specifier,
+                    to_file,
+                    imported,
+                    from_entity: None,
+                    type_only: reexport.type_only,
+                    dynamic: false,
+                    reexport: true,
+                });
+            }
         }
         
-        // 提取 <template> 部分
-        if let Some(template_content) = self.extract_template_section(content) {
-            result.template_components = Some(self.extract_template_components(&template_content));
+        result
+    }
+    
+    /// 单个文件中目标无法静态确定的动态导入
+    ///
+    /// 相对路径前缀解析为项目内目录，别名等其他前缀只保留原文
+    pub fn dynamic_imports_of_file(&self, file: &str) -> Vec<DynamicImport> {
+        let mut result = Vec::new();
+        
+        for (block, tree) in self.parse_file(file) {
+            for pattern in syntax::dynamic_import_patterns(&tree, &block.source) {
+                let dir = if pattern.prefix.starts_with("./") || pattern.prefix.starts_with("../") {
+                    let dir_part = pattern.prefix.rsplit_once('/').map(|(dir, _)| dir).unwrap_or(".");
+                    let importer_dir = Path::new(file).parent().unwrap_or(Path::new(""));
+                    let dir = normalize_path(&self.root_dir.join(importer_dir).join(dir_part));
+                    Some(self.resolver.entity_file(&dir))
+                } else {
+                    None
+                };
+                
+                result.push(DynamicImport {
+                    from_file: file.to_string(),
+                    line: pattern.line + block.line_offset,
+                    prefix: pattern.prefix,
+                    dir,
+                });
+            }
+        }
+        
+        result
+    }
+    
+    /// 单个文件中的路由定义，页面组件解析到实体
+    pub fn routes_of_file(&self, file: &str) -> Vec<Route> {
+        let mut result = Vec::new();
+        
+        for (block, tree) in self.parse_file(file) {
+            let definitions = syntax::route_definitions(&tree, &block.source);
+            if definitions.is_empty() {
+                continue;
+            }
+            let mut imports = ResolvedImports::default();
+            self.extract_imports(&tree, &block.source, file, &mut imports);
+            
+            for definition in &definitions {
+                result.push(self.resolve_route(definition, "", file, block.line_offset, &imports));
+            }
+        }
+        
+        result
+    }
+    
+    fn resolve_route(
+        &self,
+        definition: &RouteDefinition,
+        parent_path: &str,
+        file: &str,
+        line_offset: usize,
+        imports: &ResolvedImports,
+    ) -> Route {
+        let full_path = join_route_path(parent_path, definition.path.as_deref());
+        let (component, component_ref, lazy) = match &definition.component {
+            Some(RouteComponent::Binding(name)) => {
+                let component = imports
+                    .bindings
+                    .get(name)
+                    .and_then(|ids| ids.first().cloned())
+                    .or_else(|| {
+                        self.entity_map
+                            .get(file)?
+                            .iter()
+                            .find(|e| e.raw_name == *name)
+                            .map(|e| e.id.clone())
+                    });
+                (component, Some(name.clone()), false)
+            }
+            Some(RouteComponent::Import(specifier)) => {
+                let component = self.resolver.resolve(specifier, Path::new(file)).and_then(|target| {
+                    let target_file = self.resolver.entity_file(&target);
+                    // React Router 的 `lazy` 模块以 `Component` 导出页面
+                    [ImportedName::Default, ImportedName::Named("Component".to_string())]
+                        .iter()
+                        .find_map(|name| self.find_export(&target_file, name, 0).into_iter().next())
+                });
+                (component, Some(specifier.clone()), true)
+            }
+            None => (None, None, false),
+        };
+        
+        Route {
+            path: definition.path.clone(),
+            name: definition.name.clone(),
+            meta: definition.meta.clone(),
+            redirect: definition.redirect.clone(),
+            component,
+            component_ref,
+            lazy,
+            file: file.to_string(),
+            line: definition.line + line_offset,
+            children: definition
+                .children
+                .iter()
+                .map(|child| self.resolve_route(child, &full_path, file, line_offset, imports))
+                .collect(),
+            full_path,
+        }
+    }
+    
+    /// 文件导出的全部实体 ID（含 `export ... from` 转发的实体）
+    pub fn exports_of(&self, file: &str) -> Vec<String> {
+        let ids = self.find_export(file, &ImportedName::Namespace, 0);
+        ids.into_iter()
+            .filter(|id| {
+                self.entities
+                    .iter()
+                    .find(|e| &e.id == id)
+                    .map(|e| e.is_exported || e.file.ends_with(".vue"))
+                    .unwrap_or(false)
+            })
+            .collect()
+    }
+    
+    /// 读取并解析文件的脚本部分：TS/JS 文件整体或 Vue 的每个 `<script>` 块
+    fn parse_file(&self, file: &str) -> Vec<(ScriptBlock, tree_sitter::Tree)> {
+        let Ok(content) = fs::read_to_string(self.root_dir.join(file)) else {
+            return Vec::new();
+        };
+        let blocks = if file.ends_with(".vue") {
+            Self::extract_script_blocks(&content)
+        } else {
+            vec![ScriptBlock {
+                source: content,
+                line_offset: 0,
+                lang: None,
+            }]
+        };
+        
+        blocks
+            .into_iter()
+            .filter_map(|block| {
+                let language = syntax::language_for(file, block.lang.as_deref());
+                let tree = syntax::parse(&block.source, &language)?;
+                Some((block, tree))
+            })
+            .collect()
+    }
+    
+    /// 包含指定行的最内层实体
+    fn innermost_entity(&self, file: &str, line: usize) -> Option<String> {
+        self.entities
+            .iter()
+            .filter(|e| e.file == file && e.loc.start_line <= line && line <= e.loc.end_line)
+            .min_by_key(|e| e.loc.end_line - e.loc.start_line)
+            .map(|e| e.id.clone())
+    }
+    
+    /// 引用目标实体时源码中会出现的名称：实体名、所在文件名（导入路径或默认导入的别名场景），
+    /// Vue 组件还包括文件名的 kebab-case 形式（模板标签）
+    fn reference_names(target: &CodeEntity) -> Vec<String> {
+        let name = short_name(Self::short_id_or_id(target));
+        let mut names = vec![name.rsplit('.').next().unwrap_or(name).to_string()];
+        
+        if let Some(stem) = Path::new(&target.file).file_stem().map(|s| s.to_string_lossy().to_string()) {
+            if target.file.ends_with(".vue") {
+                let mut kebab = String::new();
+                for (i, c) in stem.chars().enumerate() {
+                    if c.is_ascii_uppercase() && i > 0 {
+                        kebab.push('-');
+                    }
+                    kebab.push(c.to_ascii_lowercase());
+                }
+                names.push(kebab);
+            }
+            names.push(stem);
+        }
+        
+        names.retain(|n| !n.is_empty());
+        names
+    }
+    
+    /// 分析 Vue 文件
+    fn analyze_vue_file(&self, content: &str, entity: &CodeEntity) -> Result<StaticAnalysisResult> {
+        let blocks = Self::extract_script_blocks(content);
+        let (mut result, imports) = self.analyze_blocks(&blocks, entity);
+        
+        // 组件的行范围覆盖整个 SFC，模板中的 $emit 也会被统计
+        let body = Self::entity_code(content, entity);
+        result.emits = self.extract_vue_emits(&body);
+        
+        // 模板属于组件本身，组件内的函数等实体不重复记录
+        if entity.entity_type == "component" {
+            if let Some(usages) = template::parse_template_usages(content) {
+                let usages: Vec<ComponentUsage> = usages
+                    .into_iter()
+                    .map(|usage| ComponentUsage {
+                        component: self.resolve_template_component(&usage.tag, &imports),
+                        tag: usage.tag,
+                        line: usage.line,
+                        props: usage.props,
+                        events: usage.events,
+                        slots: usage.slots,
+                        directives: usage.directives,
+                    })
+                    .collect();
+                
+                let mut tags: Vec<String> = Vec::new();
+                for usage in &usages {
+                    if !tags.contains(&usage.tag) {
+                        tags.push(usage.tag.clone());
+                    }
+                }
+                result.template_components = Some(tags);
+                result.template_usages = Some(usages);
+            }
         }
         
         Ok(result)
@@ -117,95 +528,409 @@ impl StaticAnalyzer {
     
     /// 分析 TypeScript 文件
     fn analyze_ts_file(&self, content: &str, entity: &CodeEntity) -> Result<StaticAnalysisResult> {
-        let imports = self.extract_imports(content, &entity.file);
-        let calls = self.extract_calls(content, &imports);
-        let emits = if entity.file.ends_with(".tsx") {
-            self.extract_jsx_emits(content)
-        } else {
-            vec![]
+        let block = ScriptBlock {
+            source: content.to_string(),
+            line_offset: 0,
+            lang: None,
         };
-        let annotation = self.extract_annotation(content);
+        let (mut result, _) = self.analyze_blocks(std::slice::from_ref(&block), entity);
         
-        Ok(StaticAnalysisResult {
-            imports,
+        if entity.file.ends_with(".tsx") || entity.file.ends_with(".jsx") {
+            result.emits = self.extract_jsx_emits(&Self::entity_code(content, entity));
+        }
+        
+        Ok(result)
+    }
+    
+    /// 基于语法树分析脚本块
+    ///
+    /// 导入是文件级的；调用只统计实体自身行范围内的调用表达式
+    fn analyze_blocks(&self, blocks: &[ScriptBlock], entity: &CodeEntity) -> (StaticAnalysisResult, ResolvedImports) {
+        let mut imports = ResolvedImports::default();
+        let mut callees = Vec::new();
+        let mut store_accesses = Vec::new();
+        
+        for block in blocks {
+            let language = syntax::language_for(&entity.file, block.lang.as_deref());
+            let Some(tree) = syntax::parse(&block.source, &language) else {
+                eprintln!("⚠️  解析失败: {}", entity.file);
+                continue;
+            };
+            
+            self.extract_imports(&tree, &block.source, &entity.file, &mut imports);
+            callees.extend(syntax::calls_in_range(
+                &tree,
+                &block.source,
+                block.line_offset,
+                entity.loc.start_line,
+                entity.loc.end_line,
+            ));
+            store_accesses.extend(syntax::store_accesses(
+                &tree,
+                &block.source,
+                block.line_offset,
+                entity.loc.start_line,
+                entity.loc.end_line,
+            ));
+        }
+        
+        let calls = self.resolve_calls(&callees, &imports, entity);
+        let store_usages = self.resolve_store_usages(&store_accesses, &imports, entity);
+        let annotation = blocks
+            .iter()
+            .find_map(|block| self.extract_annotation(&block.source));
+        
+        let result = StaticAnalysisResult {
+            imports: imports.entities.clone(),
             calls,
-            emits,
+            emits: vec![],
             template_components: None,
+            template_usages: None,
+            store_usages: (!store_usages.is_empty()).then_some(store_usages),
             annotation,
-        })
+        };
+        (result, imports)
     }
     
-    /// 提取 Vue 文件的 script 部分
-    fn extract_script_section(&self, content: &str) -> Option<String> {
-        // 提取 <script setup>
-        let script_setup_re = Regex::new(r"<script\s+setup[^>]*>([\s\S]*?)</script>").unwrap();
-        if let Some(cap) = script_setup_re.captures(content) {
-            return Some(cap[1].to_string());
-        }
+    /// 把 store 访问解析到 store 实体，按 store 合并成员
+    ///
+    /// Pinia 经导入绑定（或同文件定义）找到 `useXxxStore` 变量下的 store 实体；
+    /// Vuex 按命名空间匹配模块，无命名空间时按成员名匹配。无法解析的访问（第三方 store）忽略
+    fn resolve_store_usages(&self, accesses: &[StoreAccess], imports: &ResolvedImports, entity: &CodeEntity) -> Vec<StoreUsage> {
+        let mut usages: Vec<StoreUsage> = Vec::new();
         
-        // 提取普通 <script>
-        let script_re = Regex::new(r"<script[^>]*>([\s\S]*?)</script>").unwrap();
-        if let Some(cap) = script_re.captures(content) {
-            return Some(cap[1].to_string());
+        for access in accesses {
+            let (store, member) = match access {
+                StoreAccess::Pinia { hook, member } => {
+                    let Some(store) = self.pinia_store_for_hook(hook, imports, entity) else {
+                        continue;
+                    };
+                    (store, member.clone())
+                }
+                StoreAccess::Vuex { path } => {
+                    let Some((store, member)) = self.vuex_store_for_path(path) else {
+                        continue;
+                    };
+                    (store, Some(member))
+                }
+            };
+            // store 定义自身的访问不算使用
+            if store.id == entity.id || store.parent.as_deref() == Some(entity.id.as_str()) {
+                continue;
+            }
+            
+            let index = match usages.iter().position(|u| u.store == store.id) {
+                Some(index) => index,
+                None => {
+                    usages.push(StoreUsage {
+                        store: store.id.clone(),
+                        store_id: store.store.as_ref().map(|m| m.id.clone()).unwrap_or_default(),
+                        members: vec![],
+                    });
+                    usages.len() - 1
+                }
+            };
+            if let Some(member) = member {
+                if !usages[index].members.contains(&member) {
+                    usages[index].members.push(member);
+                }
+            }
         }
         
-        None
+        usages
+    }
+    
+    /// `useXxxStore` 对应的 Pinia store 实体
+    fn pinia_store_for_hook(&self, hook: &str, imports: &ResolvedImports, entity: &CodeEntity) -> Option<&CodeEntity> {
+        let is_store_of = |store: &CodeEntity, ids: &[&str]| {
+            store.store.is_some()
+                && (ids.contains(&store.id.as_str()) || store.parent.as_deref().is_some_and(|p| ids.contains(&p)))
+        };
+        
+        let ids: Vec<&str> = match imports.bindings.get(hook) {
+            Some(ids) => ids.iter().map(String::as_str).collect(),
+            None => self
+                .entities
+                .iter()
+                .filter(|e| e.file == entity.file && e.parent.is_none() && e.raw_name == hook)
+                .map(|e| e.id.as_str())
+                .collect(),
+        };
+        self.entities.iter().find(|e| is_store_of(e, &ids))
     }
     
-    /// 提取 Vue 文件的 template 部分
-    fn extract_template_section(&self, content: &str) -> Option<String> {
-        let template_re = Regex::new(r"<template[^>]*>([\s\S]*?)</template>").unwrap();
-        template_re.captures(content).map(|cap| cap[1].to_string())
+    /// Vuex 访问路径（`cart/fetch`）对应的 store 实体与成员
+    fn vuex_store_for_path(&self, path: &str) -> Option<(&CodeEntity, String)> {
+        let vuex: Vec<&CodeEntity> = self
+            .entities
+            .iter()
+            .filter(|e| e.store.as_ref().is_some_and(|m| m.style == StoreStyle::Vuex))
+            .collect();
+        let find = |namespace: Option<&str>, member: &str| {
+            vuex.iter()
+                .filter(|e| {
+                    let model = e.store.as_ref().unwrap();
+                    namespace.is_none_or(|ns| model.id == ns) && model.has_member(member)
+                })
+                // 无命名空间时优先根 store
+                .min_by_key(|e| e.store.as_ref().unwrap().id != "root")
+                .map(|e| (*e, member.to_string()))
+        };
+        
+        if let Some((namespace, member)) = path.rsplit_once('/') {
+            let module = namespace.rsplit('/').next().unwrap_or(namespace);
+            if let Some(found) = find(Some(module), member) {
+                return Some(found);
+            }
+            // `$store.state.items.length` 这类路径的首段才是成员
+            let first = path.split('/').next().unwrap_or(path);
+            return find(None, first);
+        }
+        find(None, path)
     }
     
-    /// 提取导入语句
-    fn extract_imports(&self, content: &str, current_file: &str) -> Vec<String> {
-        let mut imports = HashSet::new();
+    /// 实体所在行范围的代码
+    fn entity_code(content: &str, entity: &CodeEntity) -> String {
+        let start = entity.loc.start_line.saturating_sub(1);
+        let len = entity
+            .loc
+            .end_line
+            .saturating_sub(entity.loc.start_line)
+            .saturating_add(1);
         
-        // 匹配 ES6 import 语句
-        let import_re = Regex::new(
-            r#"import\s+(?:(?:\{[^}]*\}|\*\s+as\s+\w+|\w+)\s+from\s+)?['"]([^'"]+)['"]"#
-        ).unwrap();
+        content.lines().skip(start).take(len).collect::<Vec<_>>().join("\n")
+    }
+    
+    /// 提取 Vue 文件的全部 `<script>` 块（`<script setup>` 排在前面）
+    fn extract_script_blocks(content: &str) -> Vec<ScriptBlock> {
+        let mut blocks: Vec<(bool, ScriptBlock)> = SCRIPT_BLOCK_REGEX
+            .captures_iter(content)
+            .filter_map(|cap| {
+                let attrs = cap.get(1).map(|m| m.as_str()).unwrap_or("");
+                let body = cap.get(2)?;
+                let is_setup = SCRIPT_SETUP_REGEX.is_match(attrs);
+                Some((
+                    is_setup,
+                    ScriptBlock {
+                        source: body.as_str().to_string(),
+                        line_offset: content[..body.start()].matches('\n').count(),
+                        lang: SCRIPT_LANG_REGEX.captures(attrs).map(|c| c[1].to_string()),
+                    },
+                ))
+            })
+            .collect();
+        blocks.sort_by_key(|(is_setup, _)| !is_setup);
         
-        for cap in import_re.captures_iter(content) {
-            let module_path = &cap[1];
+        blocks.into_iter().map(|(_, block)| block).collect()
+    }
+    
+    /// 提取导入声明，解析到导出的实体
+    ///
+    /// 模块说明符经 [`ModuleResolver`] 解析（相对路径、tsconfig paths、打包工具 alias、workspace 包），
+    /// 具名导入和默认导入只关联对应的导出实体，命名空间导入关联文件的全部顶层实体
+    fn extract_imports(
+        &self,
+        tree: &tree_sitter::Tree,
+        source: &str,
+        current_file: &str,
+        resolved: &mut ResolvedImports,
+    ) {
+        for statement in syntax::import_statements(tree, source) {
+            let Some(target) = self.resolver.resolve(&statement.specifier, Path::new(current_file)) else {
+                continue;
+            };
+            let target_file = self.resolver.entity_file(&target);
             
-            // 只处理相对导入（以 ./ 或 ../ 开头）
-            if module_path.starts_with('.') {
-                // 解析模块路径
-                if let Some(resolved_file) = self.resolve_module_path(module_path, current_file) {
-                    // 提取该文件的导出实体
-                    if let Some(file_entities) = self.entity_map.get(&resolved_file) {
-                        for entity_id in file_entities.values().map(|e| e.id.clone()) {
-                            imports.insert(entity_id);
+            for (imported, local) in &statement.names {
+                let entity_ids = self.find_export(&target_file, imported, 0);
+                
+                for id in &entity_ids {
+                    if !resolved.entities.contains(id) {
+                        resolved.entities.push(id.clone());
+                    }
+                }
+                if local.is_empty() {
+                    continue;
+                }
+                
+                if *imported == ImportedName::Namespace {
+                    let members = resolved.namespaces.entry(local.clone()).or_default();
+                    for id in entity_ids {
+                        if let Some(name) = self.short_id_of(&id).map(|s| short_name(s).to_string()) {
+                            members.entry(name).or_default().push(id);
                         }
                     }
+                } else if !entity_ids.is_empty() {
+                    resolved.bindings.entry(local.clone()).or_default().extend(entity_ids);
                 }
             }
         }
-        
-        imports.into_iter().collect()
     }
     
-    /// 提取函数调用
-    fn extract_calls(&self, content: &str, imports: &[String]) -> Vec<String> {
-        let mut calls = HashSet::new();
+    /// 在文件中查找导出的实体，沿 `export ... from` 转发链继续查找
+    ///
+    /// 命名空间导入返回文件的全部顶层实体（含转发导出的实体）
+    fn find_export(&self, file: &str, imported: &ImportedName, depth: usize) -> Vec<String> {
+        let entities: &[CodeEntity] = self.entity_map.get(file).map(|v| v.as_slice()).unwrap_or(&[]);
         
-        // 简单的函数调用检测（identifier() 模式）
-        let call_re = Regex::new(r"\b(\w+)\s*\(").unwrap();
+        // 1. 文件自身的实体
+        let own: Vec<String> = match imported {
+            ImportedName::Default => {
+                let default_exports: Vec<&CodeEntity> = entities
+                    .iter()
+                    .filter(|e| e.modifiers.iter().any(|m| m == "default"))
+                    .collect();
+                if !default_exports.is_empty() {
+                    default_exports.into_iter().map(|e| e.id.clone()).collect()
+                } else if file.ends_with(".vue") {
+                    // SFC 的默认导出就是组件本身
+                    entities
+                        .iter()
+                        .filter(|e| e.entity_type == "component")
+                        .map(|e| e.id.clone())
+                        .collect()
+                } else {
+                    vec![]
+                }
+            }
+            ImportedName::Named(name) => {
+                let matches: Vec<&CodeEntity> = entities
+                    .iter()
+                    .filter(|e| short_name(Self::short_id_or_id(e)) == name)
+                    .collect();
+                // 优先导出的实体（同名的未导出实体只是局部变量）
+                let exported: Vec<&CodeEntity> = matches.iter().copied().filter(|e| e.is_exported).collect();
+                if exported.is_empty() { matches } else { exported }
+                    .into_iter()
+                    .map(|e| e.id.clone())
+                    .collect()
+            }
+            ImportedName::Namespace => entities.iter().map(|e| e.id.clone()).collect(),
+        };
+        let is_namespace = *imported == ImportedName::Namespace;
+        if (!own.is_empty() && !is_namespace) || depth >= MAX_REEXPORT_DEPTH || file.ends_with(".vue") {
+            return own;
+        }
+        
+        // 2. 转发导出：`export { a as b } from './x'`、`export * from './x'`
+        let Ok(content) = fs::read_to_string(self.root_dir.join(file)) else {
+            return own;
+        };
+        let language = syntax::language_for(file, None);
+        let Some(tree) = syntax::parse(&content, &language) else {
+            return own;
+        };
         
-        for cap in call_re.captures_iter(content) {
-            let func_name = &cap[1];
+        let mut found = own;
+        for reexport in syntax::reexports(&tree, &content) {
+            let forwarded: Vec<ImportedName> = match (&reexport.names, imported) {
+                // `export *` 不转发默认导出
+                (None, ImportedName::Default) => vec![],
+                (None, _) => vec![imported.clone()],
+                (Some(names), ImportedName::Namespace) => names.iter().map(|(source, _)| source.clone()).collect(),
+                (Some(names), _) => names
+                    .iter()
+                    .filter(|(_, exported)| match imported {
+                        ImportedName::Named(name) => exported == name,
+                        _ => exported == "default",
+                    })
+                    .map(|(source, _)| source.clone())
+                    .collect(),
+            };
+            if forwarded.is_empty() {
+                continue;
+            }
             
-            // 检查是否是导入的函数
-            for import_id in imports {
-                if import_id.contains(func_name) {
-                    calls.insert(import_id.clone());
+            let Some(target) = self.resolver.resolve(&reexport.specifier, Path::new(file)) else {
+                continue;
+            };
+            let target_file = self.resolver.entity_file(&target);
+            for name in &forwarded {
+                for id in self.find_export(&target_file, name, depth + 1) {
+                    if !found.contains(&id) {
+                        found.push(id);
+                    }
                 }
             }
+            if !found.is_empty() && !is_namespace {
+                break;
+            }
         }
         
-        calls.into_iter().collect()
+        found
+    }
+    
+    /// 实体的短 ID（旧数据没有短 ID 时使用 ID 本身）
+    fn short_id_or_id(entity: &CodeEntity) -> &str {
+        if entity.short_id.is_empty() {
+            &entity.id
+        } else {
+            &entity.short_id
+        }
+    }
+    
+    /// 由实体 ID 查找短 ID
+    fn short_id_of(&self, id: &str) -> Option<&str> {
+        self.entities
+            .iter()
+            .find(|e| e.id == id)
+            .map(Self::short_id_or_id)
+    }
+    
+    /// 将调用表达式解析为实体
+    ///
+    /// - `foo()`：导入的绑定，其次是同文件的顶层实体
+    /// - `ns.foo()`：命名空间导入的成员
+    /// - `this.foo()`：同一个类中的成员
+    fn resolve_calls(&self, callees: &[Callee], imports: &ResolvedImports, entity: &CodeEntity) -> Vec<String> {
+        let mut calls = Vec::new();
+        let local_entities: &[CodeEntity] = self
+            .entity_map
+            .get(&entity.file)
+            .map(|v| v.as_slice())
+            .unwrap_or(&[]);
+        // 类自身或方法所属的类
+        let class_id = entity.parent.as_deref().unwrap_or(&entity.id);
+        
+        for callee in callees {
+            let targets: Vec<String> = match callee {
+                Callee::Identifier(name) => match imports.bindings.get(name) {
+                    Some(ids) => ids.clone(),
+                    None => local_entities
+                        .iter()
+                        .filter(|e| short_name(Self::short_id_or_id(e)) == name)
+                        .map(|e| e.id.clone())
+                        .collect(),
+                },
+                Callee::Member { object, property } if object == "this" => self
+                    .entities
+                    .iter()
+                    .filter(|e| e.parent.as_deref() == Some(class_id))
+                    .filter(|e| {
+                        short_name(Self::short_id_or_id(e))
+                            .rsplit('.')
+                            .next()
+                            .map(|member| member == property)
+                            .unwrap_or(false)
+                    })
+                    .map(|e| e.id.clone())
+                    .collect(),
+                Callee::Member { object, property } => imports
+                    .namespaces
+                    .get(object)
+                    .and_then(|members| members.get(property))
+                    .cloned()
+                    .unwrap_or_default(),
+            };
+            
+            for id in targets {
+                if id != entity.id && !calls.contains(&id) {
+                    calls.push(id);
+                }
+            }
+        }
+        
+        calls
     }
     
     /// 提取 Vue emit 事件
@@ -213,9 +938,7 @@ impl StaticAnalyzer {
         let mut emits = HashSet::new();
         
         // 匹配 emit('event-name') 或 $emit('event-name')
-        let emit_re = Regex::new(r#"(?:\$?emit)\s*\(\s*['"]([^'"]+)['"]"#).unwrap();
-        
-        for cap in emit_re.captures_iter(content) {
+        for cap in VUE_EMIT_REGEX.captures_iter(content) {
             emits.insert(cap[1].to_string());
         }
         
@@ -227,9 +950,7 @@ impl StaticAnalyzer {
         let mut emits = HashSet::new();
         
         // 匹配 props.onXxx() 或 props['onXxx']()
-        let emit_re = Regex::new(r#"props\.on([A-Z]\w+)\s*\("#).unwrap();
-        
-        for cap in emit_re.captures_iter(content) {
+        for cap in JSX_EMIT_REGEX.captures_iter(content) {
             let event_name = cap[1].to_lowercase();
             emits.insert(event_name);
         }
@@ -237,38 +958,93 @@ impl StaticAnalyzer {
         emits.into_iter().collect()
     }
     
-    /// 提取模板中的组件
-    fn extract_template_components(&self, template: &str) -> Vec<String> {
-        let mut components = HashSet::new();
+    /// 将模板中的组件标签解析为组件实体
+    ///
+    /// 依次查找：脚本中导入的绑定（`<script setup>` 导入或 `components` 选项）、
+    /// `app.component()` 全局注册的组件、按文件名唯一匹配的组件（自动导入）
+    fn resolve_template_component(&self, tag: &str, imports: &ResolvedImports) -> Option<String> {
+        let name = template::normalize_tag(tag);
+        let is_component = |id: &String| {
+            self.entities
+                .iter()
+                .any(|e| &e.id == id && e.entity_type == "component")
+        };
         
-        // 匹配 <ComponentName> 或 <component-name> 标签
-        let component_re = Regex::new(r"<([A-Z][a-zA-Z0-9]*|[a-z]+-[a-z-]+)[\s>]").unwrap();
+        let imported = imports
+            .bindings
+            .iter()
+            .filter(|(local, _)| template::normalize_tag(local) == name)
+            .flat_map(|(_, ids)| ids.iter())
+            .find(|id| is_component(id));
+        if let Some(id) = imported {
+            return Some(id.clone());
+        }
         
-        // HTML 原生标签列表
-        let native_tags = [
-            "template", "div", "span", "p", "a", "img", "ul", "ol", "li",
-            "button", "input", "form", "table", "tr", "td", "th", "h1", "h2",
-            "h3", "h4", "h5", "h6", "header", "footer", "nav", "section", "article",
-        ];
+        let global = self.global_components().get(&name).and_then(|ids| ids.iter().find(|id| is_component(id)));
+        if let Some(id) = global {
+            return Some(id.clone());
+        }
         
-        for cap in component_re.captures_iter(template) {
-            let tag_name = &cap[1];
+        let mut candidates = self.entities.iter().filter(|e| {
+            e.entity_type == "component" && e.parent.is_none() && template::normalize_tag(&Self::component_name(e)) == name
+        });
+        match (candidates.next(), candidates.next()) {
+            (Some(only), None) => Some(only.id.clone()),
+            _ => None,
+        }
+    }
+    
+    /// 组件名：单文件组件为文件名，其余为实体名
+    fn component_name(entity: &CodeEntity) -> String {
+        if entity.file.ends_with(".vue") {
+            Path::new(&entity.file)
+                .file_stem()
+                .map(|s| s.to_string_lossy().to_string())
+                .unwrap_or_default()
+        } else {
+            short_name(Self::short_id_or_id(entity)).to_string()
+        }
+    }
+    
+    /// 全局注册的组件：`app.component('OrderList', OrderList)`，归一化的注册名 -> 组件实体 ID
+    ///
+    /// 扫描实体所在文件以及项目的 `main.*` / `src/main.*`（启动代码通常不产出实体），结果缓存
+    fn global_components(&self) -> &HashMap<String, Vec<String>> {
+        self.global_components.get_or_init(|| {
+            let mut files: BTreeSet<String> = self.entities.iter().map(|e| e.file.clone()).collect();
+            for dir in ["", "src/"] {
+                for ext in ["ts", "js", "mts", "mjs"] {
+                    let file = format!("{}main.{}", dir, ext);
+                    if self.root_dir.join(&file).is_file() {
+                        files.insert(file);
+                    }
+                }
+            }
             
-            // 过滤原生 HTML 标签
-            if !native_tags.contains(&tag_name.to_lowercase().as_str()) {
-                components.insert(tag_name.to_string());
+            let mut registered: HashMap<String, Vec<String>> = HashMap::new();
+            for file in &files {
+                for (block, tree) in self.parse_file(file) {
+                    let registrations = syntax::component_registrations(&tree, &block.source);
+                    if registrations.is_empty() {
+                        continue;
+                    }
+                    let mut imports = ResolvedImports::default();
+                    self.extract_imports(&tree, &block.source, file, &mut imports);
+                    for (name, local) in registrations {
+                        if let Some(ids) = imports.bindings.get(&local) {
+                            registered.entry(template::normalize_tag(&name)).or_default().extend(ids.iter().cloned());
+                        }
+                    }
+                }
             }
-        }
-        
-        components.into_iter().collect()
+            registered
+        })
     }
     
     /// 提取注释
     fn extract_annotation(&self, content: &str) -> Option<String> {
         // 提取文件开头的注释块
-        let comment_re = Regex::new(r"^[\s\n]*/\*\*?([\s\S]*?)\*/").unwrap();
-        
-        if let Some(cap) = comment_re.captures(content) {
+        if let Some(cap) = BLOCK_COMMENT_REGEX.captures(content) {
             let comment = cap[1]
                 .lines()
                 .map(|line| line.trim().trim_start_matches('*').trim())
@@ -282,51 +1058,262 @@ impl StaticAnalyzer {
         }
         
         // 尝试提取单行注释
-        let single_line_re = Regex::new(r"^[\s\n]*//\s*(.+)$").unwrap();
-        if let Some(cap) = single_line_re.captures(content) {
+        if let Some(cap) = LINE_COMMENT_REGEX.captures(content) {
             return Some(cap[1].trim().to_string());
         }
         
         None
     }
-    
-    /// 解析模块路径
-    fn resolve_module_path(&self, module_specifier: &str, current_file: &str) -> Option<String> {
-        let current_path = Path::new(current_file);
-        let current_dir = current_path.parent()?;
-        
-        // 拼接相对路径
-        let mut resolved = current_dir.join(module_specifier);
-        
-        // 尝试添加扩展名
-        let extensions = [".ts", ".tsx", ".vue", ".js", ".jsx"];
-        
-        // 如果已经有扩展名
-        if resolved.extension().is_some() {
-            if self.root_dir.join(&resolved).exists() {
-                return Some(resolved.to_string_lossy().to_string());
-            }
-        }
-        
-        // 尝试添加扩展名
-        for ext in &extensions {
-            let with_ext = resolved.with_extension(&ext[1..]);
-            if self.root_dir.join(&with_ext).exists() {
-                return Some(with_ext.to_string_lossy().to_string());
-            }
-        }
-        
-        // 尝试作为目录，查找 index 文件
-        if resolved.is_dir() {
-            for ext in &extensions {
-                let index_file = resolved.join(format!("index{}", ext));
-                if self.root_dir.join(&index_file).exists() {
-                    return Some(index_file.to_string_lossy().to_string());
-                }
-            }
-        }
-        
-        None
+}
+
+/// 子路由的完整路径：绝对路径直接使用，相对路径拼接到父路由之后，index 路由与父路由相同
+fn join_route_path(parent: &str, path: Option<&str>) -> String {
+    let parent = if parent.is_empty() { "/" } else { parent };
+    match path {
+        None | Some("") => parent.to_string(),
+        Some(path) if path.starts_with('/') => path.to_string(),
+        Some(path) => format!("{}/{}", parent.trim_end_matches('/'), path),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tool_execution::codebase::{EdgeKind, FileWalker};
+
+    #[tokio::test]
+    async fn test_alias_import_resolves_to_exporting_entity() {
+        let root = Path::new("/tmp/test_static_analyzer_alias");
+        fs::remove_dir_all(root).ok();
+        fs::create_dir_all(root.join("src/utils")).unwrap();
+        fs::create_dir_all(root.join("src/views")).unwrap();
+
+        fs::write(
+            root.join("tsconfig.json"),
+            r#"{ "compilerOptions": { "baseUrl": ".", "paths": { "@/*": ["src/*"] } } }"#,
+        )
+        .unwrap();
+        fs::write(root.join("src/utils/index.ts"), "export * from './date';\n").unwrap();
+        fs::write(
+            root.join("src/utils/date.ts"),
+            "export function formatDate() {}\nexport function parseDate() {}\n",
+        )
+        .unwrap();
+        fs::write(
+            root.join("src/views/home.ts"),
+            "import { formatDate as fmt } from '@/utils';\nimport { ref } from 'vue';\n\nexport function render() {\n    return fmt(ref(1));\n}\n",
+        )
+        .unwrap();
+
+        let walker = FileWalker::with_default();
+        let (entities, _) = walker.extract_all_entities(root.to_str().unwrap()).unwrap();
+        let render = entities.iter().find(|e| e.raw_name == "render").unwrap().clone();
+        let format_date = entities.iter().find(|e| e.raw_name == "formatDate").unwrap().id.clone();
+
+        let analyzer = StaticAnalyzer::new(root, entities);
+        let result = analyzer.analyze_entity(&render).await.unwrap();
+
+        assert_eq!(result.imports, vec![format_date.clone()]);
+        assert_eq!(result.calls, vec![format_date]);
+
+        fs::remove_dir_all(root).ok();
+    }
+
+    #[tokio::test]
+    async fn test_template_usages_resolve_to_components() {
+        let root = Path::new("/tmp/test_static_analyzer_template");
+        fs::remove_dir_all(root).ok();
+        fs::create_dir_all(root.join("src/components")).unwrap();
+        fs::create_dir_all(root.join("src/views")).unwrap();
+
+        fs::write(root.join("src/components/OrderList.vue"), "<template><ul /></template>\n").unwrap();
+        fs::write(root.join("src/components/BaseCard.vue"), "<template><div /></template>\n").unwrap();
+        fs::write(
+            root.join("src/main.ts"),
+            "import { createApp } from 'vue';\nimport Card from './components/BaseCard.vue';\n\ncreateApp({}).component('AppCard', Card);\n",
+        )
+        .unwrap();
+        fs::write(
+            root.join("src/views/OrderPage.vue"),
+            r#"<template>
+  <app-card>
+    <order-list :items="orders" @select="onSelect" />
+    <el-button>刷新</el-button>
+  </app-card>
+</template>
+
+<script setup lang="ts">
+import OrderList from '../components/OrderList.vue';
+</script>
+"#,
+        )
+        .unwrap();
+
+        let walker = FileWalker::with_default();
+        let (entities, _) = walker.extract_all_entities(root.to_str().unwrap()).unwrap();
+        let component = |file: &str| {
+            entities
+                .iter()
+                .find(|e| e.file == file && e.entity_type == "component")
+                .unwrap()
+                .clone()
+        };
+        let page = component("src/views/OrderPage.vue");
+        let list = component("src/components/OrderList.vue").id;
+        let card = component("src/components/BaseCard.vue").id;
+
+        let analyzer = StaticAnalyzer::new(root, entities);
+        let result = analyzer.analyze_entity(&page).await.unwrap();
+        let usages = result.template_usages.unwrap();
+
+        assert_eq!(result.template_components.unwrap(), vec!["app-card", "order-list", "el-button"]);
+        assert_eq!(usages[0].component.as_deref(), Some(card.as_str()));
+        assert_eq!(usages[1].component.as_deref(), Some(list.as_str()));
+        assert_eq!(usages[1].props, vec!["items"]);
+        assert_eq!(usages[1].events, vec!["select"]);
+        assert_eq!(usages[2].component, None);
+
+        let graph = analyzer.build_dependency_graph().await.unwrap();
+        let rendered: Vec<&str> = graph
+            .dependencies(&page.id, &[EdgeKind::Renders])
+            .iter()
+            .map(|e| e.to.as_str())
+            .collect();
+        assert_eq!(rendered, vec![card.as_str(), list.as_str()]);
+
+        fs::remove_dir_all(root).ok();
     }
+
+    #[tokio::test]
+    async fn test_calls_are_limited_to_entity_and_specifiers() {
+        let root = Path::new("/tmp/test_static_analyzer_calls");
+        fs::remove_dir_all(root).ok();
+        fs::create_dir_all(root.join("src")).unwrap();
+
+        fs::write(
+            root.join("src/api.ts"),
+            "export function get() {}\nexport function getUserData() {}\nexport function remove() {}\n",
+        )
+        .unwrap();
+        fs::write(
+            root.join("src/service.ts"),
+            r#"import { get } from './api';
+import * as api from './api';
+
+export function helper() {}
+
+export function load() {
+    helper();
+    return get('/user');
+}
+
+export function clear() {
+    api.remove();
 }
+"#,
+        )
+        .unwrap();
+
+        let walker = FileWalker::with_default();
+        let (entities, _) = walker.extract_all_entities(root.to_str().unwrap()).unwrap();
+        let id = |name: &str| entities.iter().find(|e| e.raw_name == name).unwrap().id.clone();
+        let (get, get_user_data, remove, helper) = (id("get"), id("getUserData"), id("remove"), id("helper"));
+        let load = entities.iter().find(|e| e.raw_name == "load").unwrap().clone();
+        let clear = entities.iter().find(|e| e.raw_name == "clear").unwrap().clone();
+
+        let analyzer = StaticAnalyzer::new(root, entities);
+
+        let result = analyzer.analyze_entity(&load).await.unwrap();
+        assert_eq!(result.calls, vec![helper, get.clone()]);
+        assert!(result.imports.contains(&get));
+        assert!(result.imports.contains(&get_user_data));
+
+        let result = analyzer.analyze_entity(&clear).await.unwrap();
+        assert_eq!(result.calls, vec![remove.clone()]);
+        assert!(analyzer.code_snippet(&clear).unwrap().contains("api.remove();"));
 
+        // 反向依赖：只有 clear 调用了 remove，service.ts 的实体都经命名空间导入了它
+        let target = analyzer.entities.iter().find(|e| e.id == remove).unwrap().clone();
+        let dependents = analyzer.find_dependents(&target).await.unwrap();
+        let callers: Vec<&str> = dependents
+            .iter()
+            .filter(|e| e.kind == EdgeKind::Calls)
+            .map(|e| e.from.as_str())
+            .collect();
+        assert_eq!(callers, vec![clear.id.as_str()]);
+        assert!(dependents.iter().any(|e| e.kind == EdgeKind::Imports && e.from == load.id));
+
+        fs::remove_dir_all(root).ok();
+    }
+
+    #[tokio::test]
+    async fn test_store_usages_resolve_to_stores() {
+        let root = Path::new("/tmp/test_static_analyzer_store");
+        fs::remove_dir_all(root).ok();
+        fs::create_dir_all(root.join("src/stores")).unwrap();
+        fs::create_dir_all(root.join("src/store/modules")).unwrap();
+        fs::create_dir_all(root.join("src/views")).unwrap();
+
+        fs::write(
+            root.join("src/stores/cart.ts"),
+            r#"import { defineStore } from 'pinia';
+
+export const useCartStore = defineStore('cart', {
+  state: () => ({ items: [], loading: false }),
+  getters: { total: (state) => state.items.length },
+  actions: { async fetchList() {} },
+});
+"#,
+        )
+        .unwrap();
+        fs::write(
+            root.join("src/store/modules/user.ts"),
+            "export default {\n  namespaced: true,\n  state: () => ({ name: '' }),\n  mutations: { setName() {} },\n  actions: { login() {} },\n};\n",
+        )
+        .unwrap();
+        fs::write(
+            root.join("src/views/CartPage.vue"),
+            r#"<template><div>{{ cart.total }}</div></template>
+
+<script setup lang="ts">
+import { storeToRefs } from 'pinia';
+import { useStore } from 'vuex';
+import { useCartStore } from '../stores/cart';
+
+const cart = useCartStore();
+const { items } = storeToRefs(useCartStore());
+const store = useStore();
+
+cart.fetchList();
+store.dispatch('user/login');
+</script>
+"#,
+        )
+        .unwrap();
+
+        let walker = FileWalker::with_default();
+        let (entities, _) = walker.extract_all_entities(root.to_str().unwrap()).unwrap();
+        let find = |file: &str, entity_type: &str| {
+            entities
+                .iter()
+                .find(|e| e.file == file && e.entity_type == entity_type)
+                .unwrap()
+                .clone()
+        };
+        let page = find("src/views/CartPage.vue", "component");
+        let cart = find("src/stores/cart.ts", "pinia_store").id;
+        let user = find("src/store/modules/user.ts", "vuex_module").id;
+
+        let analyzer = StaticAnalyzer::new(root, entities);
+        let usages = analyzer.analyze_entity(&page).await.unwrap().store_usages.unwrap();
+
+        assert_eq!(usages.len(), 2);
+        assert_eq!(usages[0].store, cart);
+        assert_eq!(usages[0].store_id, "cart");
+        assert_eq!(usages[0].members, vec!["items", "fetchList"]);
+        assert_eq!(usages[1].store, user);
+        assert_eq!(usages[1].members, vec!["login"]);
+
+        fs::remove_dir_all(root).ok();
+    }
+}
@@ -0,0 +1,994 @@
+//! 静态分析使用的语法树查询
+//!
+//! 基于 tree-sitter TypeScript/TSX 语法提取导入声明、转发导出和调用表达式，
+//! 供 [`StaticAnalyzer`](super::StaticAnalyzer) 解析依赖关系
+
+use tree_sitter::{Language, Node, Parser, Tree};
+
+/// 导入声明中引入的单个名称
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(super) enum ImportedName {
+    /// `import Foo from` / `import { default as Foo } from` / `import('...')`
+    Default,
+    /// `import { foo } from` / `import { foo as bar } from` / `const { foo } = require()`
+    Named(String),
+    /// `import * as ns from` / `const ns = require()` / `import ns = require()`
+    Namespace,
+}
+
+/// 一条导入声明
+#[derive(Debug, Clone)]
+pub(super) struct ImportStatement {
+    /// 模块说明符
+    pub specifier: String,
+    /// (导入的名称, 本地绑定名)，动态导入没有本地绑定名
+    pub names: Vec<(ImportedName, String)>,
+    /// 所在行（1-based，相对脚本块）
+    pub line: usize,
+    /// `import type ... from`，只在类型层面依赖
+    pub type_only: bool,
+    /// 动态 `import('...')`
+    pub dynamic: bool,
+}
+
+/// 目标无法静态确定的动态导入
+#[derive(Debug, Clone)]
+pub(super) struct DynamicImportPattern {
+    /// 说明符中确定的前缀：`import(`./views/${name}.vue`)` -> `./views/`
+    pub prefix: String,
+    /// 所在行（1-based，相对脚本块）
+    pub line: usize,
+}
+
+/// 一条转发导出 `export ... from '...'`
+#[derive(Debug, Clone)]
+pub(super) struct ReExport {
+    /// 模块说明符
+    pub specifier: String,
+    /// (源模块中的名称, 对外导出的名称)；`export *` 为 `None`
+    pub names: Option<Vec<(ImportedName, String)>>,
+    /// 所在行（1-based，相对脚本块）
+    pub line: usize,
+    /// `export type { ... } from`，只在类型层面依赖
+    pub type_only: bool,
+}
+
+/// 调用表达式的被调用者
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(super) enum Callee {
+    /// `foo()` / `new Foo()`
+    Identifier(String),
+    /// `ns.foo()` / `this.foo()`
+    Member { object: String, property: String },
+}
+
+/// 对 store 成员的一次访问
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(super) enum StoreAccess {
+    /// Pinia：`useCartStore()` 返回值上的成员，只取得 store 时成员为空
+    Pinia { hook: String, member: Option<String> },
+    /// Vuex：`命名空间/成员` 形式的路径，如 `dispatch('cart/fetch')` -> `cart/fetch`，
+    /// `$store.state.cart.items` -> `cart/items`
+    Vuex { path: String },
+}
+
+/// 路由指向的页面组件
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(super) enum RouteComponent {
+    /// 本地绑定：`component: Home`、`element: <Home />`
+    Binding(String),
+    /// 懒加载的模块：`component: () => import('./views/Home.vue')`、`lazy: () => import('./routes/home')`
+    Import(String),
+}
+
+/// 一条路由定义（Vue Router / React Router 的路由对象或 `<Route>` 元素）
+#[derive(Debug, Clone, Default)]
+pub(super) struct RouteDefinition {
+    /// 路径，React Router 的 index 路由为空
+    pub path: Option<String>,
+    pub name: Option<String>,
+    /// `meta` 中的字面量，无法静态求值的部分保留源码
+    pub meta: Option<serde_json::Value>,
+    pub redirect: Option<String>,
+    pub component: Option<RouteComponent>,
+    /// 所在行（1-based，相对脚本块）
+    pub line: usize,
+    pub children: Vec<RouteDefinition>,
+}
+
+/// 按文件类型选择语法：JS/JSX/TSX 使用 TSX 语法，其余使用 TypeScript 语法
+pub(super) fn language_for(file: &str, lang: Option<&str>) -> Language {
+    let jsx_lang = matches!(lang, Some("tsx") | Some("jsx"));
+    let jsx_file = [".tsx", ".jsx", ".js", ".mjs", ".cjs"]
+        .iter()
+        .any(|ext| file.ends_with(ext));
+
+    if jsx_lang || (lang.is_none() && jsx_file) {
+        tree_sitter_typescript::language_tsx()
+    } else {
+        tree_sitter_typescript::language_typescript()
+    }
+}
+
+/// 解析源码
+pub(super) fn parse(source: &str, language: &Language) -> Option<Tree> {
+    let mut parser = Parser::new();
+    parser.set_language(language).ok()?;
+    parser.parse(source, None)
+}
+
+/// 提取导入声明：静态 import、`import x = require()`、动态 `import()` 与 CommonJS `require()`
+pub(super) fn import_statements(tree: &Tree, source: &str) -> Vec<ImportStatement> {
+    let mut statements = Vec::new();
+    visit(tree.root_node(), &mut |node| match node.kind() {
+        "import_statement" => {
+            if let Some(statement) = static_import(&node, source) {
+                statements.push(statement);
+            }
+        }
+        "call_expression" => {
+            if let Some(statement) = dynamic_import(&node, source) {
+                statements.push(statement);
+            }
+        }
+        _ => {}
+    });
+    statements
+}
+
+/// 提取目标无法静态确定的动态导入：模板字符串 `import(`./views/${name}.vue`)`
+/// 与 Vite 的 `import.meta.glob('./views/*.vue')`
+pub(super) fn dynamic_import_patterns(tree: &Tree, source: &str) -> Vec<DynamicImportPattern> {
+    let mut patterns = Vec::new();
+    visit(tree.root_node(), &mut |node| {
+        if node.kind() != "call_expression" {
+            return;
+        }
+        let (Some(function), Some(arguments)) = (
+            node.child_by_field_name("function"),
+            node.child_by_field_name("arguments"),
+        ) else {
+            return;
+        };
+        let Some(argument) = arguments.named_child(0) else {
+            return;
+        };
+        let line = node.start_position().row + 1;
+
+        if function.kind() == "import" && argument.kind() == "template_string" {
+            let value = string_value(&argument, source);
+            let prefix = value.split("${").next().unwrap_or("").to_string();
+            patterns.push(DynamicImportPattern { prefix, line });
+        } else if matches!(text(&function, source), "import.meta.glob" | "import.meta.globEager") {
+            let globs: Vec<Node> = match argument.kind() {
+                "string" => vec![argument],
+                "array" => {
+                    let mut cursor = argument.walk();
+                    argument
+                        .named_children(&mut cursor)
+                        .filter(|n| n.kind() == "string")
+                        .collect()
+                }
+                _ => Vec::new(),
+            };
+            for glob in globs {
+                let value = string_value(&glob, source);
+                // 排除模式 `!**/*.spec.ts` 不引入文件
+                if value.starts_with('!') {
+                    continue;
+                }
+                let prefix = value.split(['*', '?', '{', '[']).next().unwrap_or("").to_string();
+                patterns.push(DynamicImportPattern { prefix, line });
+            }
+        }
+    });
+    patterns
+}
+
+/// 提取全局组件注册 `app.component('OrderList', OrderList)`，返回 (注册名, 本地绑定名)
+pub(super) fn component_registrations(tree: &Tree, source: &str) -> Vec<(String, String)> {
+    let mut registrations = Vec::new();
+    visit(tree.root_node(), &mut |node| {
+        if node.kind() != "call_expression" {
+            return;
+        }
+        let is_register = node
+            .child_by_field_name("function")
+            .filter(|f| f.kind() == "member_expression")
+            .and_then(|f| f.child_by_field_name("property"))
+            .map(|p| text(&p, source) == "component")
+            .unwrap_or(false);
+        let Some(arguments) = node.child_by_field_name("arguments").filter(|_| is_register) else {
+            return;
+        };
+
+        let (Some(name), Some(component)) = (arguments.named_child(0), arguments.named_child(1)) else {
+            return;
+        };
+        if name.kind() == "string" && component.kind() == "identifier" {
+            registrations.push((string_value(&name, source), text(&component, source).to_string()));
+        }
+    });
+    registrations
+}
+
+/// 提取转发导出
+pub(super) fn reexports(tree: &Tree, source: &str) -> Vec<ReExport> {
+    let root = tree.root_node();
+    let mut cursor = root.walk();
+    let mut result = Vec::new();
+
+    for statement in root.named_children(&mut cursor) {
+        if statement.kind() != "export_statement" {
+            continue;
+        }
+        let Some(specifier) = statement.child_by_field_name("source").map(|s| string_value(&s, source)) else {
+            continue;
+        };
+
+        let mut inner = statement.walk();
+        let clause = statement
+            .named_children(&mut inner)
+            .find(|c| matches!(c.kind(), "export_clause" | "namespace_export"));
+        let type_only = statement.children(&mut inner).any(|c| c.kind() == "type");
+
+        let names = match clause {
+            // `export * as ns from` 只能以命名空间形式使用，视为导出源模块的全部实体
+            Some(clause) if clause.kind() == "namespace_export" => {
+                let exported = clause
+                    .named_child(0)
+                    .map(|n| text(&n, source).to_string())
+                    .unwrap_or_default();
+                Some(vec![(ImportedName::Namespace, exported)])
+            }
+            Some(clause) => {
+                let mut specifiers = clause.walk();
+                let names = clause
+                    .named_children(&mut specifiers)
+                    .filter(|s| s.kind() == "export_specifier")
+                    .filter_map(|s| {
+                        let name = text(&s.child_by_field_name("name")?, source).to_string();
+                        let exported = s
+                            .child_by_field_name("alias")
+                            .map(|a| text(&a, source).to_string())
+                            .unwrap_or_else(|| name.clone());
+                        Some((imported_name(&name), exported))
+                    })
+                    .collect();
+                Some(names)
+            }
+            None => None,
+        };
+
+        result.push(ReExport {
+            specifier,
+            names,
+            line: statement.start_position().row + 1,
+            type_only,
+        });
+    }
+
+    result
+}
+
+/// 收集 `[start_line, end_line]`（1-based，含 `line_offset`）范围内的调用
+pub(super) fn calls_in_range(
+    tree: &Tree,
+    source: &str,
+    line_offset: usize,
+    start_line: usize,
+    end_line: usize,
+) -> Vec<Callee> {
+    let mut calls = Vec::new();
+
+    visit(tree.root_node(), &mut |node| {
+        let line = node.start_position().row + 1 + line_offset;
+        if line < start_line || line > end_line {
+            return;
+        }
+
+        let target = match node.kind() {
+            "call_expression" => node.child_by_field_name("function"),
+            "new_expression" => node.child_by_field_name("constructor"),
+            _ => None,
+        };
+        let Some(target) = target else {
+            return;
+        };
+
+        let callee = match target.kind() {
+            "identifier" => Some(Callee::Identifier(text(&target, source).to_string())),
+            "member_expression" => {
+                let object = target.child_by_field_name("object");
+                let property = target.child_by_field_name("property");
+                match (object, property) {
+                    (Some(object), Some(property)) if matches!(object.kind(), "identifier" | "this") => {
+                        Some(Callee::Member {
+                            object: text(&object, source).to_string(),
+                            property: text(&property, source).to_string(),
+                        })
+                    }
+                    _ => None,
+                }
+            }
+            _ => None,
+        };
+
+        if let Some(callee) = callee {
+            if !calls.contains(&callee) {
+                calls.push(callee);
+            }
+        }
+    });
+
+    calls
+}
+
+/// 提取行范围内对 store 的访问
+///
+/// - Pinia：`useXxxStore()` 的返回值（变量、解构、`storeToRefs`）上的成员，以及 `mapState(useXxxStore, [...])` 等辅助函数
+/// - Vuex：`dispatch` / `commit` 的类型字符串、`mapGetters('cart', [...])` 等辅助函数、
+///   `$store.state.xxx` / `$store.getters['xxx']` 与 `useStore()` 返回值上的同类访问
+pub(super) fn store_accesses(
+    tree: &Tree,
+    source: &str,
+    line_offset: usize,
+    start_line: usize,
+    end_line: usize,
+) -> Vec<StoreAccess> {
+    let mut accesses = Vec::new();
+    let mut push = |access: StoreAccess| {
+        if !accesses.contains(&access) {
+            accesses.push(access);
+        }
+    };
+    let in_range = |node: &Node| {
+        let line = node.start_position().row + 1 + line_offset;
+        start_line <= line && line <= end_line
+    };
+
+    // 1. store 的取得方式与辅助函数；记录保存 store 的变量
+    let mut pinia_bindings: Vec<(String, String)> = Vec::new();
+    let mut vuex_bindings: Vec<String> = vec!["$store".to_string()];
+    visit(tree.root_node(), &mut |node| {
+        if node.kind() != "call_expression" || !in_range(&node) {
+            return;
+        }
+        let Some(function) = node.child_by_field_name("function") else {
+            return;
+        };
+        let arguments = node.child_by_field_name("arguments");
+        let argument = |i: usize| arguments.and_then(|a| a.named_child(i));
+        let name = match function.kind() {
+            "identifier" => text(&function, source),
+            "member_expression" => function.child_by_field_name("property").map(|p| text(&p, source)).unwrap_or(""),
+            _ => "",
+        };
+
+        if function.kind() == "identifier" && is_store_hook(name) {
+            push(StoreAccess::Pinia { hook: name.to_string(), member: None });
+
+            let mut members = Vec::new();
+            match node.parent() {
+                // `useCartStore().fetch()`
+                Some(parent) if parent.kind() == "member_expression" => {
+                    if let Some(property) = parent.child_by_field_name("property") {
+                        members.push(text(&property, source).to_string());
+                    }
+                }
+                // `const cart = useCartStore()` / `const { fetch } = useCartStore()`
+                Some(parent) if parent.kind() == "variable_declarator" => match parent.child_by_field_name("name") {
+                    Some(binding) if binding.kind() == "identifier" => {
+                        pinia_bindings.push((text(&binding, source).to_string(), name.to_string()));
+                    }
+                    Some(pattern) if pattern.kind() == "object_pattern" => {
+                        members = destructured_keys(&pattern, source);
+                    }
+                    _ => {}
+                },
+                // `const { items } = storeToRefs(useCartStore())`
+                Some(parent) if parent.kind() == "arguments" => {
+                    let pattern = parent
+                        .parent()
+                        .filter(|call| call.kind() == "call_expression")
+                        .and_then(|call| call.parent())
+                        .filter(|declarator| declarator.kind() == "variable_declarator")
+                        .and_then(|declarator| declarator.child_by_field_name("name"))
+                        .filter(|pattern| pattern.kind() == "object_pattern");
+                    if let Some(pattern) = pattern {
+                        members = destructured_keys(&pattern, source);
+                    }
+                }
+                _ => {}
+            }
+            for member in members.into_iter().filter(|m| !m.starts_with('$')) {
+                push(StoreAccess::Pinia { hook: name.to_string(), member: Some(member) });
+            }
+        } else if function.kind() == "identifier" && name == "useStore" {
+            // Vuex 4 组合式 API：`const store = useStore()`
+            let binding = node
+                .parent()
+                .filter(|p| p.kind() == "variable_declarator")
+                .and_then(|p| p.child_by_field_name("name"))
+                .filter(|n| n.kind() == "identifier");
+            if let Some(binding) = binding {
+                vuex_bindings.push(text(&binding, source).to_string());
+            }
+        } else if matches!(name, "mapState" | "mapGetters" | "mapActions" | "mapMutations" | "mapWritableState") {
+            let Some(first) = argument(0) else {
+                return;
+            };
+            match first.kind() {
+                // Pinia：`mapState(useCartStore, ['items'])`
+                "identifier" if is_store_hook(text(&first, source)) => {
+                    let hook = text(&first, source).to_string();
+                    for member in helper_members(argument(1), source) {
+                        push(StoreAccess::Pinia { hook: hook.clone(), member: Some(member) });
+                    }
+                }
+                // Vuex：`mapGetters('cart', ['total'])`
+                "string" => {
+                    let namespace = string_value(&first, source);
+                    for member in helper_members(argument(1), source) {
+                        push(StoreAccess::Vuex { path: format!("{}/{}", namespace.trim_end_matches('/'), member) });
+                    }
+                }
+                // Vuex：`mapActions(['cart/fetch'])`
+                "array" | "object" => {
+                    for path in helper_members(Some(first), source) {
+                        push(StoreAccess::Vuex { path });
+                    }
+                }
+                _ => {}
+            }
+        } else if matches!(name, "dispatch" | "commit") {
+            if let Some(path) = argument(0).filter(|a| a.kind() == "string") {
+                push(StoreAccess::Vuex { path: string_value(&path, source) });
+            }
+        }
+    });
+
+    // 2. store 变量上的成员访问
+    visit(tree.root_node(), &mut |node| {
+        if !matches!(node.kind(), "member_expression" | "subscript_expression") || !in_range(&node) {
+            return;
+        }
+        let Some(chain) = access_chain(&node, source) else {
+            return;
+        };
+
+        // Pinia：`cart.fetch`
+        if let [object, member] = chain.as_slice() {
+            if let Some((_, hook)) = pinia_bindings.iter().find(|(binding, _)| binding == object) {
+                if !member.starts_with('$') {
+                    push(StoreAccess::Pinia { hook: hook.clone(), member: Some(member.clone()) });
+                }
+                return;
+            }
+        }
+
+        // Vuex：`this.$store.state.cart.items`、`store.getters['cart/total']`
+        let Some(start) = chain.iter().position(|segment| vuex_bindings.contains(segment)) else {
+            return;
+        };
+        let rest = &chain[start + 1..];
+        match rest {
+            [kind, path @ ..] if kind == "state" && !path.is_empty() => {
+                let path = path.iter().take(2).cloned().collect::<Vec<_>>().join("/");
+                push(StoreAccess::Vuex { path });
+            }
+            [kind, path] if kind == "getters" => push(StoreAccess::Vuex { path: path.clone() }),
+            _ => {}
+        }
+    });
+
+    accesses
+}
+
+/// Pinia store 的组合式函数名：`useCartStore`
+fn is_store_hook(name: &str) -> bool {
+    name.len() > "useStore".len() && name.starts_with("use") && name.ends_with("Store")
+}
+
+/// 解构模式中的属性名：`{ items, total: sum }` -> [items, total]
+fn destructured_keys(pattern: &Node, source: &str) -> Vec<String> {
+    destructured_names(pattern, source)
+        .into_iter()
+        .filter_map(|(name, _)| match name {
+            ImportedName::Named(name) => Some(name),
+            _ => None,
+        })
+        .collect()
+}
+
+/// `mapState` 等辅助函数参数中的成员：`['a', 'b']` 或 `{ alias: 'a' }`
+fn helper_members(node: Option<Node>, source: &str) -> Vec<String> {
+    let Some(node) = node else {
+        return Vec::new();
+    };
+    let mut members = Vec::new();
+    let mut cursor = node.walk();
+    for child in node.named_children(&mut cursor) {
+        match child.kind() {
+            "string" => members.push(string_value(&child, source)),
+            "pair" => {
+                if let Some(value) = child.child_by_field_name("value").filter(|v| v.kind() == "string") {
+                    members.push(string_value(&value, source));
+                }
+            }
+            _ => {}
+        }
+    }
+    members
+}
+
+/// 成员访问链：`this.$store.getters['cart/total']` -> [this, $store, getters, cart/total]
+fn access_chain(node: &Node, source: &str) -> Option<Vec<String>> {
+    match node.kind() {
+        "identifier" | "this" => Some(vec![text(node, source).to_string()]),
+        "member_expression" => {
+            let mut chain = access_chain(&node.child_by_field_name("object")?, source)?;
+            chain.push(text(&node.child_by_field_name("property")?, source).to_string());
+            Some(chain)
+        }
+        "subscript_expression" => {
+            let index = node.child_by_field_name("index")?;
+            if index.kind() != "string" {
+                return None;
+            }
+            let mut chain = access_chain(&node.child_by_field_name("object")?, source)?;
+            chain.push(string_value(&index, source));
+            Some(chain)
+        }
+        _ => None,
+    }
+}
+
+/// 提取路由定义
+///
+/// - 路由对象数组：`createRouter({ routes: [...] })`、`const routes = [...]`、`createBrowserRouter([...])`，
+///   至少一个元素带字符串 `path` 的对象数组即视为路由表，`children` 递归处理
+/// - JSX：`<Route path="/" element={<Home />}>` 及嵌套的 `<Route>`
+///
+/// `const Home = () => import('./Home.vue')`、`const Home = lazy(() => import('./Home'))` 这类懒加载绑定
+/// 会被展开为模块说明符
+pub(super) fn route_definitions(tree: &Tree, source: &str) -> Vec<RouteDefinition> {
+    let mut lazy_bindings: Vec<(String, String)> = Vec::new();
+    visit(tree.root_node(), &mut |node| {
+        if node.kind() != "variable_declarator" {
+            return;
+        }
+        let (Some(name), Some(value)) = (node.child_by_field_name("name"), node.child_by_field_name("value")) else {
+            return;
+        };
+        if let (true, Some(specifier)) = (name.kind() == "identifier", lazy_import(&value, source)) {
+            lazy_bindings.push((text(&name, source).to_string(), specifier));
+        }
+    });
+    let context = RouteContext { source, lazy_bindings };
+
+    let mut routes = Vec::new();
+    visit(tree.root_node(), &mut |node| match node.kind() {
+        "array" if is_route_array(&node, source) && !is_children_value(&node, source) => {
+            routes.extend(context.route_array(&node));
+        }
+        "jsx_element" | "jsx_self_closing_element" if is_route_element(&node, source) => {
+            let nested = std::iter::successors(node.parent(), |n| n.parent())
+                .any(|ancestor| is_route_element(&ancestor, source));
+            if !nested {
+                routes.extend(context.route_element(&node));
+            }
+        }
+        _ => {}
+    });
+    routes
+}
+
+struct RouteContext<'s> {
+    source: &'s str,
+    /// 懒加载组件的本地绑定名 -> 模块说明符
+    lazy_bindings: Vec<(String, String)>,
+}
+
+impl RouteContext<'_> {
+    fn route_array(&self, array: &Node) -> Vec<RouteDefinition> {
+        let mut cursor = array.walk();
+        array
+            .named_children(&mut cursor)
+            .filter(|n| n.kind() == "object")
+            .filter_map(|object| self.route_object(&object))
+            .collect()
+    }
+
+    /// 路由对象：带 `path` 或 `index: true`
+    fn route_object(&self, object: &Node) -> Option<RouteDefinition> {
+        let source = self.source;
+        let mut route = RouteDefinition {
+            line: object.start_position().row + 1,
+            ..Default::default()
+        };
+        let mut index = false;
+
+        let mut cursor = object.walk();
+        for pair in object.named_children(&mut cursor).filter(|n| n.kind() == "pair") {
+            let (Some(key), Some(value)) = (pair.child_by_field_name("key"), pair.child_by_field_name("value")) else {
+                continue;
+            };
+            let string = || (value.kind() == "string").then(|| string_value(&value, source));
+            match string_value(&key, source).as_str() {
+                "path" => route.path = string(),
+                "name" => route.name = string(),
+                "redirect" => route.redirect = string(),
+                "index" => index = text(&value, source) == "true",
+                "meta" => route.meta = Some(literal_json(&value, source)),
+                "component" | "Component" | "element" => route.component = self.component(&value),
+                "lazy" => route.component = lazy_import(&value, source).map(RouteComponent::Import),
+                // 命名视图只取默认视图
+                "components" if value.kind() == "object" => {
+                    let mut views = value.walk();
+                    let default = value
+                        .named_children(&mut views)
+                        .filter(|n| n.kind() == "pair")
+                        .find(|n| n.child_by_field_name("key").is_some_and(|k| text(&k, source) == "default"))
+                        .and_then(|n| n.child_by_field_name("value"));
+                    route.component = default.and_then(|v| self.component(&v));
+                }
+                "children" if value.kind() == "array" => route.children = self.route_array(&value),
+                _ => {}
+            }
+        }
+
+        (route.path.is_some() || index).then_some(route)
+    }
+
+    /// `<Route>` 元素
+    fn route_element(&self, element: &Node) -> Option<RouteDefinition> {
+        let source = self.source;
+        let opening = match element.kind() {
+            "jsx_element" => element.child_by_field_name("open_tag")?,
+            _ => *element,
+        };
+        let mut route = RouteDefinition {
+            line: element.start_position().row + 1,
+            ..Default::default()
+        };
+        let mut index = false;
+
+        let mut cursor = opening.walk();
+        for attribute in opening.named_children(&mut cursor).filter(|n| n.kind() == "jsx_attribute") {
+            let Some(name) = attribute.named_child(0) else {
+                continue;
+            };
+            // `path="/"` 或 `path={'/'}`
+            let value = attribute.named_child(1).map(|v| match v.kind() {
+                "jsx_expression" => v.named_child(0).unwrap_or(v),
+                _ => v,
+            });
+            match text(&name, source) {
+                "path" => route.path = value.filter(|v| v.kind() == "string").map(|v| string_value(&v, source)),
+                "index" => index = value.is_none_or(|v| text(&v, source) == "true"),
+                "element" | "component" | "Component" => route.component = value.and_then(|v| self.component(&v)),
+                "lazy" => route.component = value.and_then(|v| lazy_import(&v, source)).map(RouteComponent::Import),
+                _ => {}
+            }
+        }
+
+        if element.kind() == "jsx_element" {
+            let mut children = element.walk();
+            route.children = element
+                .named_children(&mut children)
+                .filter(|child| is_route_element(child, source))
+                .filter_map(|child| self.route_element(&child))
+                .collect();
+        }
+
+        (route.path.is_some() || index).then_some(route)
+    }
+
+    /// 路由组件：标识符、JSX 元素或懒加载函数
+    fn component(&self, value: &Node) -> Option<RouteComponent> {
+        let source = self.source;
+        let binding = match value.kind() {
+            "identifier" => Some(text(value, source).to_string()),
+            "jsx_self_closing_element" => value.child_by_field_name("name").map(|n| text(&n, source).to_string()),
+            "jsx_element" => value
+                .child_by_field_name("open_tag")
+                .and_then(|t| t.child_by_field_name("name"))
+                .map(|n| text(&n, source).to_string()),
+            _ => None,
+        };
+
+        match binding {
+            // `<div />` 等原生元素不是组件
+            Some(name) if value.kind() != "identifier" && name.starts_with(|c: char| c.is_ascii_lowercase()) => None,
+            Some(name) => Some(
+                match self.lazy_bindings.iter().find(|(local, _)| *local == name) {
+                    Some((_, specifier)) => RouteComponent::Import(specifier.clone()),
+                    None => RouteComponent::Binding(name),
+                },
+            ),
+            None => lazy_import(value, source).map(RouteComponent::Import),
+        }
+    }
+}
+
+/// 懒加载函数中的模块说明符：`() => import('./x')`、`defineAsyncComponent(() => import('./x'))`、`lazy(...)`
+fn lazy_import(node: &Node, source: &str) -> Option<String> {
+    if !matches!(node.kind(), "arrow_function" | "function_expression" | "function" | "call_expression") {
+        return None;
+    }
+    let mut specifier = None;
+    visit(*node, &mut |n| {
+        if specifier.is_some() || n.kind() != "call_expression" {
+            return;
+        }
+        let is_import = n.child_by_field_name("function").is_some_and(|f| f.kind() == "import");
+        let argument = n.child_by_field_name("arguments").and_then(|a| a.named_child(0));
+        if let (true, Some(argument)) = (is_import, argument.filter(|a| a.kind() == "string")) {
+            specifier = Some(string_value(&argument, source));
+        }
+    });
+    specifier
+}
+
+/// 至少一个元素是带字符串 `path` 的对象
+fn is_route_array(array: &Node, source: &str) -> bool {
+    let mut cursor = array.walk();
+    let objects: Vec<Node> = array.named_children(&mut cursor).filter(|n| n.kind() == "object").collect();
+
+    objects.iter().any(|object| {
+        let mut pairs = object.walk();
+        let pairs: Vec<Node> = object.named_children(&mut pairs).filter(|n| n.kind() == "pair").collect();
+        pairs.iter().any(|pair| {
+            let key = pair.child_by_field_name("key").map(|k| string_value(&k, source));
+            let value = pair.child_by_field_name("value");
+            key.as_deref() == Some("path") && value.is_some_and(|v| v.kind() == "string")
+        })
+    })
+}
+
+/// 数组是否为路由对象的 `children`（由父路由递归处理）
+fn is_children_value(array: &Node, source: &str) -> bool {
+    array
+        .parent()
+        .filter(|p| p.kind() == "pair")
+        .and_then(|p| p.child_by_field_name("key"))
+        .is_some_and(|k| string_value(&k, source) == "children")
+}
+
+/// `<Route>` 元素
+fn is_route_element(node: &Node, source: &str) -> bool {
+    let name = match node.kind() {
+        "jsx_element" => node.child_by_field_name("open_tag").and_then(|t| t.child_by_field_name("name")),
+        "jsx_self_closing_element" => node.child_by_field_name("name"),
+        _ => None,
+    };
+    name.is_some_and(|n| text(&n, source) == "Route")
+}
+
+/// 字面量转为 JSON，无法静态求值的表达式保留源码
+fn literal_json(node: &Node, source: &str) -> serde_json::Value {
+    use serde_json::Value;
+
+    match node.kind() {
+        "string" => Value::String(string_value(node, source)),
+        "template_string" if !text(node, source).contains("${") => Value::String(string_value(node, source)),
+        "number" => {
+            let number = text(node, source);
+            match number.parse::<i64>() {
+                Ok(n) => Value::from(n),
+                Err(_) => number
+                    .parse::<f64>()
+                    .ok()
+                    .and_then(serde_json::Number::from_f64)
+                    .map(Value::Number)
+                    .unwrap_or_else(|| Value::String(number.to_string())),
+            }
+        }
+        "true" => Value::Bool(true),
+        "false" => Value::Bool(false),
+        "null" | "undefined" => Value::Null,
+        "array" => {
+            let mut cursor = node.walk();
+            Value::Array(node.named_children(&mut cursor).map(|n| literal_json(&n, source)).collect())
+        }
+        "object" => {
+            let mut map = serde_json::Map::new();
+            let mut cursor = node.walk();
+            for child in node.named_children(&mut cursor) {
+                match child.kind() {
+                    "pair" => {
+                        if let (Some(key), Some(value)) = (child.child_by_field_name("key"), child.child_by_field_name("value")) {
+                            map.insert(string_value(&key, source), literal_json(&value, source));
+                        }
+                    }
+                    "shorthand_property_identifier" => {
+                        map.insert(text(&child, source).to_string(), Value::String(text(&child, source).to_string()));
+                    }
+                    _ => {}
+                }
+            }
+            Value::Object(map)
+        }
+        _ => Value::String(text(node, source).to_string()),
+    }
+}
+
+/// 静态 import 声明
+fn static_import(node: &Node, source: &str) -> Option<ImportStatement> {
+    let mut names = Vec::new();
+    let mut cursor = node.walk();
+
+    for child in node.named_children(&mut cursor) {
+        match child.kind() {
+            "import_clause" => {
+                let mut clause_cursor = child.walk();
+                for part in child.named_children(&mut clause_cursor) {
+                    match part.kind() {
+                        "identifier" => names.push((ImportedName::Default, text(&part, source).to_string())),
+                        "namespace_import" => {
+                            if let Some(local) = last_identifier(&part, source) {
+                                names.push((ImportedName::Namespace, local));
+                            }
+                        }
+                        "named_imports" => {
+                            let mut specifiers = part.walk();
+                            for specifier in part.named_children(&mut specifiers) {
+                                if specifier.kind() != "import_specifier" {
+                                    continue;
+                                }
+                                let Some(name) = specifier.child_by_field_name("name") else {
+                                    continue;
+                                };
+                                let name = string_value(&name, source);
+                                let local = specifier
+                                    .child_by_field_name("alias")
+                                    .map(|a| text(&a, source).to_string())
+                                    .unwrap_or_else(|| name.clone());
+                                names.push((imported_name(&name), local));
+                            }
+                        }
+                        _ => {}
+                    }
+                }
+            }
+            // `import api = require('./api')`
+            "import_require_clause" => {
+                if let Some(local) = child.named_child(0).filter(|n| n.kind() == "identifier") {
+                    names.push((ImportedName::Namespace, text(&local, source).to_string()));
+                }
+                let specifier = child.child_by_field_name("source").map(|s| string_value(&s, source))?;
+                return Some(ImportStatement {
+                    specifier,
+                    names,
+                    line: node.start_position().row + 1,
+                    type_only: false,
+                    dynamic: false,
+                });
+            }
+            _ => {}
+        }
+    }
+
+    let mut cursor = node.walk();
+    let type_only = node.children(&mut cursor).any(|c| c.kind() == "type");
+    let specifier = node.child_by_field_name("source").map(|s| string_value(&s, source))?;
+    Some(ImportStatement {
+        specifier,
+        names,
+        line: node.start_position().row + 1,
+        type_only,
+        dynamic: false,
+    })
+}
+
+/// 动态 `import('...')` 与 `require('...')`
+fn dynamic_import(node: &Node, source: &str) -> Option<ImportStatement> {
+    let function = node.child_by_field_name("function")?;
+    let is_import = function.kind() == "import";
+    let is_require = function.kind() == "identifier" && text(&function, source) == "require";
+    if !is_import && !is_require {
+        return None;
+    }
+
+    let argument = node.child_by_field_name("arguments")?.named_child(0)?;
+    if argument.kind() != "string" {
+        return None;
+    }
+    let specifier = string_value(&argument, source);
+
+    if is_import {
+        return Some(ImportStatement {
+            specifier,
+            names: vec![(ImportedName::Default, String::new())],
+            line: node.start_position().row + 1,
+            type_only: false,
+            dynamic: true,
+        });
+    }
+
+    // `const api = require()` / `const { a, b: c } = require()`
+    let names = match node.parent().filter(|p| p.kind() == "variable_declarator") {
+        Some(declarator) => match declarator.child_by_field_name("name") {
+            Some(name) if name.kind() == "identifier" => {
+                vec![(ImportedName::Namespace, text(&name, source).to_string())]
+            }
+            Some(pattern) if pattern.kind() == "object_pattern" => destructured_names(&pattern, source),
+            _ => Vec::new(),
+        },
+        None => Vec::new(),
+    };
+
+    Some(ImportStatement {
+        specifier,
+        names,
+        line: node.start_position().row + 1,
+        type_only: false,
+        dynamic: false,
+    })
+}
+
+/// 解构出的名称：`{ a, b: c }` -> [(a, a), (b, c)]
+fn destructured_names(pattern: &Node, source: &str) -> Vec<(ImportedName, String)> {
+    let mut names = Vec::new();
+    let mut cursor = pattern.walk();
+
+    for property in pattern.named_children(&mut cursor) {
+        match property.kind() {
+            "shorthand_property_identifier_pattern" => {
+                let name = text(&property, source).to_string();
+                names.push((ImportedName::Named(name.clone()), name));
+            }
+            "pair_pattern" => {
+                let key = property.child_by_field_name("key").map(|k| text(&k, source).to_string());
+                let value = property
+                    .child_by_field_name("value")
+                    .filter(|v| v.kind() == "identifier")
+                    .map(|v| text(&v, source).to_string());
+                if let (Some(key), Some(value)) = (key, value) {
+                    names.push((imported_name(&key), value));
+                }
+            }
+            _ => {}
+        }
+    }
+
+    names
+}
+
+fn imported_name(name: &str) -> ImportedName {
+    if name == "default" {
+        ImportedName::Default
+    } else {
+        ImportedName::Named(name.to_string())
+    }
+}
+
+fn last_identifier(node: &Node, source: &str) -> Option<String> {
+    let mut cursor = node.walk();
+    let identifier = node
+        .named_children(&mut cursor)
+        .filter(|c| c.kind() == "identifier")
+        .last();
+    identifier.map(|i| text(&i, source).to_string())
+}
+
+/// 深度优先遍历所有节点
+fn visit<'a>(node: Node<'a>, callback: &mut impl FnMut(Node<'a>)) {
+    callback(node);
+    let mut cursor = node.walk();
+    for child in node.children(&mut cursor) {
+        visit(child, callback);
+    }
+}
+
+/// 字符串字面量的内容（去掉引号）
+fn string_value(node: &Node, source: &str) -> String {
+    text(node, source)
+        .trim_matches(|c| c == '\'' || c == '"' || c == '`')
+        .to_string()
+}
+
+fn text<'s>(node: &Node, source: &'s str) -> &'s str {
+    node.utf8_text(source.as_bytes()).unwrap_or("")
+}
@@ -0,0 +1,414 @@
+//! 标签表与标签归一化
+//!
+//! 项目标签表来自项目配置 `codebase.config.json` 的 `taxonomy` 字段；未配置时，首次富化后请 LLM
+//! 把生成的标签归并为同义词组（如 `order` 与 `订单`），保留被多个实体使用的标签组，写入输出文件旁的
+//! `tag_taxonomy.json`，后续运行的标签只能从中选择（该文件可以人工审阅和编辑）。
+//!
+//! 富化结束后对全项目的标签做归一化：忽略大小写、空白与连字符，去掉「管理」「模块」等泛化后缀，
+//! 英文复数按单数处理。有标签表时，命中名称或别名的标签替换为规范名称，其余标签被丢弃并记入标签索引；
+//! 没有标签表时，写法不同的同一标签合并为最常用的写法。
+
+use super::interfaces::{EnrichedEntity, LabelSource};
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// 自动生成的标签表文件名，与富化输出放在同一目录
+pub const TAXONOMY_FILE: &str = "tag_taxonomy.json";
+
+/// 标签索引文件名，与富化输出放在同一目录
+pub const TAG_INDEX_FILE: &str = "tag_index.json";
+
+/// 自动生成标签表时，标签至少被多少个实体使用
+pub const BOOTSTRAP_MIN_ENTITIES: usize = 2;
+
+/// 归一化时去掉的泛化后缀
+const GENERIC_SUFFIXES: &[&str] = &["管理", "模块", "功能", "相关", "management", "manager", "module"];
+
+/// 标签表中的一个标签
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TagDefinition {
+    /// 规范名称
+    pub name: String,
+
+    /// 同义词、其他语言的写法，归一化为规范名称
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub aliases: Vec<String>,
+
+    /// 标签含义（仅供阅读）
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+}
+
+/// 项目标签表
+///
+/// 示例：
+/// ```json
+/// {
+///   "tags": [
+///     { "name": "订单", "aliases": ["order", "订单管理"] },
+///     { "name": "支付", "aliases": ["payment", "pay"] }
+///   ]
+/// }
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TagTaxonomy {
+    pub tags: Vec<TagDefinition>,
+}
+
+impl TagTaxonomy {
+    /// 输出文件对应的自动生成标签表路径
+    pub fn path_for(output_path: &Path) -> PathBuf {
+        output_path.with_file_name(TAXONOMY_FILE)
+    }
+
+    /// 加载标签表，文件不存在时为空
+    pub fn load(path: &Path) -> Result<Option<Self>> {
+        if !path.exists() {
+            return Ok(None);
+        }
+        let content = fs::read_to_string(path).context(format!("无法读取标签表: {}", path.display()))?;
+        let taxonomy = serde_json::from_str(&content).context(format!("解析标签表失败: {}", path.display()))?;
+        Ok(Some(taxonomy))
+    }
+
+    pub fn save(&self, path: &Path) -> Result<()> {
+        let json = serde_json::to_string_pretty(self).context("序列化标签表失败")?;
+        fs::write(path, json).context(format!("写入标签表失败: {}", path.display()))
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.tags.is_empty()
+    }
+
+    /// 写入提示词的标签列表
+    pub fn prompt_text(&self) -> String {
+        self.tags.iter().map(|t| t.name.as_str()).collect::<Vec<_>>().join("、")
+    }
+
+    /// 解析 LLM 返回的标签表，允许输出被代码块或说明文字包裹
+    pub fn parse(text: &str) -> Result<Self> {
+        let json_text = match (text.find('{'), text.rfind('}')) {
+            (Some(start), Some(end)) if start < end => &text[start..=end],
+            _ => anyhow::bail!("输出中没有 JSON 对象"),
+        };
+        serde_json::from_str(json_text).context("标签表格式无效")
+    }
+
+    /// 从首次富化的结果生成标签表
+    ///
+    /// 只统计 LLM 生成（含修复后）的标签。`groups` 为 LLM 归并的同义词组，组内标签合并计数，
+    /// 以组名为规范名称；未归组的标签各自成组。保留被至少 `min_entities` 个实体使用的标签组，
+    /// 按使用次数降序排列；`variants` 为归一化时合并的原始写法，一并作为别名
+    pub fn bootstrap(
+        entities: &[EnrichedEntity],
+        variants: &BTreeMap<String, BTreeSet<String>>,
+        groups: Option<&TagTaxonomy>,
+        min_entities: usize,
+    ) -> Self {
+        let counts: BTreeMap<String, usize> = tag_counts(entities).into_iter().collect();
+
+        // 组名 -> 组内实际使用的标签；组只能归并已使用的标签，一个标签只归入第一个组
+        let mut grouped: Vec<(String, Vec<&str>)> = Vec::new();
+        let mut assigned: HashSet<&str> = HashSet::new();
+        for group in groups.iter().flat_map(|g| &g.tags) {
+            let keys: HashSet<String> = std::iter::once(&group.name).chain(&group.aliases).map(|t| tag_key(t)).collect();
+            let members: Vec<&str> = counts
+                .keys()
+                .map(|tag| tag.as_str())
+                .filter(|tag| !assigned.contains(tag) && keys.contains(&tag_key(tag)))
+                .collect();
+            let name = group.name.trim();
+            if members.is_empty() || name.is_empty() {
+                continue;
+            }
+            assigned.extend(&members);
+            grouped.push((name.to_string(), members));
+        }
+        for tag in counts.keys().filter(|tag| !assigned.contains(tag.as_str())) {
+            grouped.push((tag.clone(), vec![tag.as_str()]));
+        }
+
+        let mut totals: Vec<(usize, TagDefinition)> = grouped
+            .into_iter()
+            .map(|(name, members)| {
+                let total = members.iter().map(|tag| counts[*tag]).sum();
+                let aliases: BTreeSet<String> = members
+                    .iter()
+                    .flat_map(|tag| std::iter::once(tag.to_string()).chain(variants.get(*tag).into_iter().flatten().cloned()))
+                    .filter(|alias| *alias != name)
+                    .collect();
+                let definition = TagDefinition {
+                    name,
+                    aliases: aliases.into_iter().collect(),
+                    description: None,
+                };
+                (total, definition)
+            })
+            .filter(|(total, _)| *total >= min_entities)
+            .collect();
+        totals.sort_by(|a, b| b.0.cmp(&a.0).then(a.1.name.cmp(&b.1.name)));
+
+        Self {
+            tags: totals.into_iter().map(|(_, definition)| definition).collect(),
+        }
+    }
+}
+
+/// LLM 生成（含修复后）的标签及使用该标签的实体数，按标签排序
+pub fn tag_counts(entities: &[EnrichedEntity]) -> Vec<(String, usize)> {
+    let mut counts: BTreeMap<String, usize> = BTreeMap::new();
+    for entity in entities {
+        if matches!(entity.label_source, Some(LabelSource::Llm | LabelSource::Repair)) {
+            for tag in &entity.tags {
+                *counts.entry(tag.clone()).or_default() += 1;
+            }
+        }
+    }
+    counts.into_iter().collect()
+}
+
+/// 同义标签归并结果的 JSON Schema，与标签表的格式相同
+pub fn taxonomy_schema() -> Value {
+    json!({
+        "type": "object",
+        "properties": {
+            "tags": {
+                "type": "array",
+                "items": {
+                    "type": "object",
+                    "properties": {
+                        "name": { "type": "string", "minLength": 1, "description": "规范名称" },
+                        "aliases": {
+                            "type": "array",
+                            "items": { "type": "string" },
+                            "description": "归并到该名称的其他标签"
+                        }
+                    },
+                    "required": ["name", "aliases"],
+                    "additionalProperties": false
+                }
+            }
+        },
+        "required": ["tags"],
+        "additionalProperties": false
+    })
+}
+
+/// 请 LLM 归并同义标签的提示词
+pub fn synonym_prompt(counts: &[(String, usize)]) -> String {
+    let tags: String = counts.iter().map(|(tag, count)| format!("- {} ({})\n", tag, count)).collect();
+    format!(
+        r#"以下是代码库中各实体的标签及使用次数：
+{}
+请把含义相同的标签归为一组，包括中英文、单复数、缩写等不同写法（如 order 与 订单）。
+每组选一个最常用、最规范的写法作为 name，其余写法放入 aliases；没有同义词的标签单独成组，aliases 为空。
+不要新增上面没有的标签。只返回 JSON：{{"tags": [{{"name": "订单", "aliases": ["order"]}}]}}"#,
+        tags
+    )
+}
+
+/// 标签的归一化键：用于判断两个写法是否为同一标签
+pub fn tag_key(tag: &str) -> String {
+    let mut key: String = tag
+        .trim()
+        .to_lowercase()
+        .chars()
+        .filter(|c| !c.is_whitespace() && !matches!(c, '-' | '_' | '/' | '·'))
+        .collect();
+    for suffix in GENERIC_SUFFIXES {
+        if key.len() > suffix.len() && key.ends_with(suffix) {
+            key.truncate(key.len() - suffix.len());
+            break;
+        }
+    }
+    if key.is_ascii() && key.len() > 3 && key.ends_with('s') && !key.ends_with("ss") {
+        key.pop();
+    }
+    key
+}
+
+/// 标签归一化的结果
+#[derive(Debug, Clone, Default)]
+pub struct NormalizedTags {
+    /// 每个规范标签合并的原始写法
+    pub variants: BTreeMap<String, BTreeSet<String>>,
+
+    /// 不在标签表中而被丢弃的标签
+    pub unlisted: BTreeSet<String>,
+}
+
+/// 全项目的标签归一化
+///
+/// 原地替换实体的标签并去重；处理失败的实体不参与。有标签表时只保留能映射到标签表的标签
+pub fn normalize_tags(entities: &mut [EnrichedEntity], taxonomy: Option<&TagTaxonomy>) -> NormalizedTags {
+    // 标签表中的名称与别名
+    let mut canonical: HashMap<String, String> = HashMap::new();
+    for definition in taxonomy.iter().flat_map(|t| &t.tags) {
+        for variant in std::iter::once(&definition.name).chain(&definition.aliases) {
+            canonical.entry(tag_key(variant)).or_insert_with(|| definition.name.clone());
+        }
+    }
+
+    // 没有标签表时：同一键取最常用的写法，次数相同时取较短、字典序靠前的
+    let mut spellings: HashMap<String, HashMap<String, usize>> = HashMap::new();
+    let restricted = taxonomy.is_some_and(|t| !t.is_empty());
+    for tag in entities.iter().filter(|e| e.label_source.is_some() && !restricted).flat_map(|e| &e.tags) {
+        let key = tag_key(tag);
+        if !key.is_empty() && !canonical.contains_key(&key) {
+            *spellings.entry(key).or_default().entry(tag.trim().to_string()).or_default() += 1;
+        }
+    }
+    for (key, counts) in spellings {
+        let preferred = counts
+            .into_iter()
+            .max_by(|a, b| a.1.cmp(&b.1).then(b.0.chars().count().cmp(&a.0.chars().count())).then(b.0.cmp(&a.0)))
+            .map(|(spelling, _)| spelling)
+            .unwrap_or_default();
+        canonical.insert(key, preferred);
+    }
+
+    let mut result = NormalizedTags::default();
+    for entity in entities.iter_mut().filter(|e| e.label_source.is_some()) {
+        let mut tags: Vec<String> = Vec::new();
+        for tag in &entity.tags {
+            let Some(name) = canonical.get(&tag_key(tag)) else {
+                if !tag.trim().is_empty() {
+                    result.unlisted.insert(tag.trim().to_string());
+                }
+                continue;
+            };
+            result.variants.entry(name.clone()).or_default().insert(tag.trim().to_string());
+            if !tags.contains(name) {
+                tags.push(name.clone());
+            }
+        }
+        entity.tags = tags;
+    }
+    result
+}
+
+/// 标签索引：标签 -> 使用该标签的实体 ID
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct TagIndex {
+    pub tags: BTreeMap<String, Vec<String>>,
+
+    /// 不在标签表中而被丢弃的标签，可据此扩充标签表
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub unlisted: Vec<String>,
+}
+
+impl TagIndex {
+    /// 输出文件对应的标签索引路径
+    pub fn path_for(output_path: &Path) -> PathBuf {
+        output_path.with_file_name(TAG_INDEX_FILE)
+    }
+
+    /// 从归一化后的实体构建索引，处理失败的实体不计入
+    pub fn build(entities: &[EnrichedEntity], normalized: &NormalizedTags) -> Self {
+        let mut index = Self::default();
+        for entity in entities.iter().filter(|e| e.label_source.is_some()) {
+            for tag in &entity.tags {
+                index.tags.entry(tag.clone()).or_default().push(entity.base.id.clone());
+            }
+        }
+        for ids in index.tags.values_mut() {
+            ids.sort();
+            ids.dedup();
+        }
+        index.unlisted = normalized.unlisted.iter().cloned().collect();
+        index
+    }
+
+    pub fn save(&self, path: &Path) -> Result<()> {
+        let json = serde_json::to_string_pretty(self).context("序列化标签索引失败")?;
+        fs::write(path, json).context(format!("写入标签索引失败: {}", path.display()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tool_execution::codebase::CodeEntity;
+
+    fn enriched(id: &str, tags: &[&str], label_source: Option<LabelSource>) -> EnrichedEntity {
+        EnrichedEntity {
+            base: CodeEntity {
+                id: id.to_string(),
+                ..Default::default()
+            },
+            imports: vec![],
+            calls: vec![],
+            emits: vec![],
+            template_components: None,
+            template_usages: None,
+            store_usages: None,
+            annotation: None,
+            summary: String::new(),
+            tags: tags.iter().map(|t| t.to_string()).collect(),
+            label_source,
+        }
+    }
+
+    #[test]
+    fn test_normalize_bootstrap_and_index() {
+        let entities = || {
+            vec![
+                enriched("a", &["订单", "order", "列表"], Some(LabelSource::Llm)),
+                enriched("b", &["订单管理", "Orders", "支付"], Some(LabelSource::Repair)),
+                enriched("c", &["order ", "支付模块"], Some(LabelSource::Llm)),
+                enriched("d", &["function"], Some(LabelSource::Fallback)),
+                enriched("e", &["处理失败"], None),
+            ]
+        };
+
+        // 没有标签表：合并大小写、复数与泛化后缀的写法
+        let mut first = entities();
+        let normalized = normalize_tags(&mut first, None);
+        assert_eq!(first[0].tags, vec!["订单", "order", "列表"]);
+        assert_eq!(first[1].tags, vec!["订单", "order", "支付"]);
+        assert_eq!(first[2].tags, vec!["order", "支付"]);
+        assert_eq!(first[4].tags, vec!["处理失败"]);
+        assert!(normalized.unlisted.is_empty());
+
+        // 只按使用次数生成时，order 与订单是两个标签
+        let taxonomy = TagTaxonomy::bootstrap(&first, &normalized.variants, None, BOOTSTRAP_MIN_ENTITIES);
+        let names: Vec<&str> = taxonomy.tags.iter().map(|t| t.name.as_str()).collect();
+        assert_eq!(names, vec!["order", "支付", "订单"]);
+        assert_eq!(taxonomy.tags[0].aliases, vec!["Orders"]);
+
+        // LLM 归并的同义词组合并计数；组外的新标签被忽略，无法解析的输出报错
+        let groups = TagTaxonomy::parse(
+            "```json\n{\"tags\": [{\"name\": \"订单\", \"aliases\": [\"order\"]}, {\"name\": \"物流\", \"aliases\": []}]}\n```",
+        )
+        .unwrap();
+        assert!(TagTaxonomy::parse(r#"{"summary": "", "tags": ["模拟"]}"#).is_err());
+        assert!(synonym_prompt(&tag_counts(&first)).contains("- order (3)"));
+        let taxonomy = TagTaxonomy::bootstrap(&first, &normalized.variants, Some(&groups), BOOTSTRAP_MIN_ENTITIES);
+        let names: Vec<&str> = taxonomy.tags.iter().map(|t| t.name.as_str()).collect();
+        assert_eq!(names, vec!["订单", "支付"]);
+        assert_eq!(taxonomy.tags[0].aliases, vec!["Orders", "order", "订单管理"]);
+        assert_eq!(taxonomy.tags[1].aliases, vec!["支付模块"]);
+
+        // 有标签表时只保留标签表中的标签，其余记入索引的 unlisted
+        let mut second = entities();
+        let normalized = normalize_tags(&mut second, Some(&taxonomy));
+        assert_eq!(second[0].tags, vec!["订单"]);
+        assert_eq!(second[1].tags, vec!["订单", "支付"]);
+        assert_eq!(second[2].tags, vec!["订单", "支付"]);
+        assert!(second[3].tags.is_empty());
+
+        let index = TagIndex::build(&second, &normalized);
+        assert_eq!(index.tags["订单"], vec!["a", "b", "c"]);
+        assert_eq!(index.tags["支付"], vec!["b", "c"]);
+        assert!(!index.tags.contains_key("处理失败"));
+        assert_eq!(index.unlisted, vec!["function", "列表"]);
+        assert_eq!(
+            TagIndex::path_for(Path::new("/p/src/data/entities.enriched.json")),
+            Path::new("/p/src/data/tag_index.json")
+        );
+    }
+}
@@ -0,0 +1,456 @@
+//! Vue 单文件组件的模板解析
+//!
+//! 按 HTML 词法扫描顶层 `<template>`，识别其中的子组件，记录每次使用时绑定的 props、
+//! 监听的事件、使用的插槽和指令，供 [`StaticAnalyzer`](super::StaticAnalyzer) 解析为父子组件关系。
+//!
+//! 原生 HTML / SVG 元素以及 Vue、vue-router 内置组件（`<transition>`、`<router-view>` 等）不算作子组件
+
+/// 原生 HTML 元素
+const HTML_TAGS: &[&str] = &[
+    "a", "abbr", "address", "area", "article", "aside", "audio", "b", "base", "bdi", "bdo",
+    "blockquote", "body", "br", "button", "canvas", "caption", "cite", "code", "col", "colgroup",
+    "data", "datalist", "dd", "del", "details", "dfn", "dialog", "div", "dl", "dt", "em", "embed",
+    "fieldset", "figcaption", "figure", "footer", "form", "h1", "h2", "h3", "h4", "h5", "h6", "head",
+    "header", "hgroup", "hr", "html", "i", "iframe", "img", "input", "ins", "kbd", "label", "legend",
+    "li", "link", "main", "map", "mark", "menu", "meta", "meter", "nav", "noscript", "object", "ol",
+    "optgroup", "option", "output", "p", "param", "picture", "pre", "progress", "q", "rp", "rt",
+    "ruby", "s", "samp", "script", "search", "section", "select", "small", "source", "span",
+    "strong", "style", "sub", "summary", "sup", "table", "tbody", "td", "textarea", "tfoot", "th",
+    "thead", "time", "title", "tr", "track", "u", "ul", "var", "video", "wbr",
+];
+
+/// 原生 SVG / MathML 元素（按小写比较）
+const SVG_TAGS: &[&str] = &[
+    "svg", "animate", "animatemotion", "animatetransform", "circle", "clippath", "defs", "desc",
+    "ellipse", "feblend", "fecolormatrix", "fecomponenttransfer", "fecomposite", "feconvolvematrix",
+    "fediffuselighting", "fedisplacementmap", "fedistantlight", "fedropshadow", "feflood",
+    "fefunca", "fefuncb", "fefuncg", "fefuncr", "fegaussianblur", "feimage", "femerge",
+    "femergenode", "femorphology", "feoffset", "fepointlight", "fespecularlighting", "fespotlight",
+    "fetile", "feturbulence", "filter", "foreignobject", "g", "image", "line", "lineargradient",
+    "marker", "mask", "metadata", "mpath", "path", "pattern", "polygon", "polyline",
+    "radialgradient", "rect", "set", "stop", "switch", "symbol", "text", "textpath", "tspan",
+    "use", "view", "font-face", "font-face-src", "font-face-uri", "font-face-format",
+    "font-face-name", "missing-glyph", "color-profile", "math", "mi", "mn", "mo", "ms", "mtext",
+];
+
+/// Vue 与 vue-router 的内置组件（归一化后的名称）
+const BUILTIN_COMPONENTS: &[&str] = &[
+    "template", "slot", "component", "transition", "transitiongroup", "keepalive", "teleport",
+    "suspense", "routerview", "routerlink",
+];
+
+/// 没有结束标签的元素
+const VOID_TAGS: &[&str] = &[
+    "area", "base", "br", "col", "embed", "hr", "img", "input", "link", "meta", "param", "source",
+    "track", "wbr",
+];
+
+/// 模板中一次子组件的使用（未解析到实体）
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub(super) struct TemplateUsage {
+    /// 标签名，保持源码写法
+    pub tag: String,
+    /// 所在行（1-based，相对整个文件）
+    pub line: usize,
+    /// 传入的 props（camelCase）：`:foo`、`v-bind:foo`、静态属性、`v-model` 对应的 prop
+    pub props: Vec<String>,
+    /// 监听的事件：`@change`、`v-on:change`、`v-model` 对应的 `update:xxx`
+    pub events: Vec<String>,
+    /// 使用的插槽：`<template #header>`、`v-slot`，直接写在组件内的内容记为 `default`
+    pub slots: Vec<String>,
+    /// 其他指令：`v-if`、`v-for`、`v-model`、自定义指令等
+    pub directives: Vec<String>,
+}
+
+/// 一个开始标签
+struct Tag {
+    name: String,
+    attrs: Vec<(String, Option<String>)>,
+    self_closing: bool,
+    line: usize,
+}
+
+enum Token {
+    Open(Tag),
+    Close(String),
+    /// 非空白文本
+    Text,
+}
+
+/// 解析 SFC 顶层 `<template>` 中的子组件使用，按出现顺序返回
+///
+/// 文件没有模板时返回 `None`
+pub(super) fn parse_template_usages(content: &str) -> Option<Vec<TemplateUsage>> {
+    let tokens = tokenize(content);
+
+    // 定位顶层 <template>
+    let mut depth = 0usize;
+    let mut start = None;
+    for (i, token) in tokens.iter().enumerate() {
+        match token {
+            Token::Open(tag) if depth == 0 && tag.name == "template" && !tag.self_closing => {
+                start = Some(i + 1);
+                break;
+            }
+            Token::Open(tag) if !tag.self_closing && !is_void(&tag.name) => depth += 1,
+            Token::Close(_) => depth = depth.saturating_sub(1),
+            _ => {}
+        }
+    }
+    let start = start?;
+
+    // 元素栈：(标签名, 所属组件使用的下标, 是否为插槽 <template>)
+    let mut stack: Vec<(String, Option<usize>, bool)> = Vec::new();
+    let mut usages: Vec<TemplateUsage> = Vec::new();
+
+    for token in &tokens[start..] {
+        let parent_component = stack.last().and_then(|(_, usage, is_slot)| (!is_slot).then_some(*usage).flatten());
+
+        match token {
+            Token::Open(tag) => {
+                let slot = slot_name(&tag.attrs);
+
+                if tag.name == "template" && slot.is_some() {
+                    // <template #header> 属于最近的组件
+                    let owner = stack.iter().rev().find_map(|(_, usage, _)| *usage);
+                    if let (Some(owner), Some(slot)) = (owner, slot) {
+                        push_unique(&mut usages[owner].slots, slot);
+                    }
+                    stack.push((tag.name.clone(), None, true));
+                    continue;
+                }
+
+                if let Some(owner) = parent_component {
+                    push_unique(&mut usages[owner].slots, "default".to_string());
+                }
+
+                let usage_index = if is_component_tag(&tag.name) {
+                    usages.push(usage_of(tag));
+                    Some(usages.len() - 1)
+                } else {
+                    None
+                };
+
+                if !tag.self_closing && !is_void(&tag.name) {
+                    stack.push((tag.name.clone(), usage_index, false));
+                }
+            }
+            Token::Close(name) => {
+                if name == "template" && !stack.iter().any(|(n, _, _)| n == "template") {
+                    break;
+                }
+                if let Some(pos) = stack.iter().rposition(|(n, _, _)| n == name) {
+                    stack.truncate(pos);
+                }
+            }
+            Token::Text => {
+                if let Some(owner) = parent_component {
+                    push_unique(&mut usages[owner].slots, "default".to_string());
+                }
+            }
+        }
+    }
+
+    Some(usages)
+}
+
+/// 是否为子组件标签
+pub(super) fn is_component_tag(name: &str) -> bool {
+    let lower = name.to_lowercase();
+    if BUILTIN_COMPONENTS.contains(&normalize_tag(name).as_str()) {
+        return false;
+    }
+    if name.starts_with(|c: char| c.is_ascii_uppercase()) {
+        return true;
+    }
+
+    !HTML_TAGS.contains(&lower.as_str()) && !SVG_TAGS.contains(&lower.as_str())
+}
+
+/// 组件名归一化：`OrderList` / `order-list` -> `orderlist`
+pub(super) fn normalize_tag(name: &str) -> String {
+    name.chars()
+        .filter(|c| *c != '-' && *c != '_')
+        .flat_map(|c| c.to_lowercase())
+        .collect()
+}
+
+/// 由开始标签的属性生成组件使用记录
+fn usage_of(tag: &Tag) -> TemplateUsage {
+    let mut usage = TemplateUsage {
+        tag: tag.name.clone(),
+        line: tag.line,
+        ..Default::default()
+    };
+
+    for (name, _) in &tag.attrs {
+        let name = strip_modifiers(name);
+
+        if let Some(prop) = name
+            .strip_prefix(':')
+            .or_else(|| name.strip_prefix("v-bind:"))
+            .or_else(|| name.strip_prefix('.'))
+        {
+            push_unique(&mut usage.props, camel_case(prop));
+        } else if let Some(event) = name.strip_prefix('@').or_else(|| name.strip_prefix("v-on:")) {
+            push_unique(&mut usage.events, event.to_string());
+        } else if name == "v-model" || name.starts_with("v-model:") {
+            let prop = name.strip_prefix("v-model:").map(camel_case).unwrap_or_else(|| "modelValue".to_string());
+            push_unique(&mut usage.events, format!("update:{}", prop));
+            push_unique(&mut usage.props, prop);
+            push_unique(&mut usage.directives, name.to_string());
+        } else if name == "v-slot" || name.starts_with("v-slot:") || name.starts_with('#') {
+            if let Some(slot) = slot_name(&[(name.to_string(), None)]) {
+                push_unique(&mut usage.slots, slot);
+            }
+        } else if name.starts_with("v-") {
+            // `v-bind="obj"` 等无参数的绑定也记为指令
+            push_unique(&mut usage.directives, name.to_string());
+        } else if !matches!(name, "key" | "ref" | "class" | "style" | "is") {
+            push_unique(&mut usage.props, camel_case(name));
+        }
+    }
+
+    usage
+}
+
+/// `v-slot` / `#name` 指定的插槽名，没有插槽指令时返回 `None`
+fn slot_name(attrs: &[(String, Option<String>)]) -> Option<String> {
+    attrs.iter().find_map(|(name, _)| {
+        let slot = if name == "v-slot" || name == "#" {
+            ""
+        } else {
+            name.strip_prefix("v-slot:").or_else(|| name.strip_prefix('#'))?
+        };
+        Some(if slot.is_empty() { "default".to_string() } else { slot.to_string() })
+    })
+}
+
+/// 去掉指令修饰符：`@click.stop` -> `@click`；动态参数 `:[key]` 中的 `.` 不算修饰符，
+/// `.prop` 简写（`.foo="x"`）开头的 `.` 保留
+fn strip_modifiers(name: &str) -> &str {
+    let head = usize::from(name.starts_with('.'));
+    let search_from = head + name[head..].rfind(']').unwrap_or(0);
+    match name[search_from..].find('.') {
+        Some(pos) => &name[..search_from + pos],
+        None => name,
+    }
+}
+
+/// `order-id` -> `orderId`
+fn camel_case(name: &str) -> String {
+    let mut result = String::with_capacity(name.len());
+    let mut upper = false;
+    for c in name.chars() {
+        if c == '-' {
+            upper = true;
+        } else if upper {
+            result.extend(c.to_uppercase());
+            upper = false;
+        } else {
+            result.push(c);
+        }
+    }
+    result
+}
+
+fn push_unique(list: &mut Vec<String>, value: String) {
+    if !list.contains(&value) {
+        list.push(value);
+    }
+}
+
+fn is_void(name: &str) -> bool {
+    VOID_TAGS.contains(&name.to_lowercase().as_str())
+}
+
+/// 按 HTML 词法切分：跳过注释、`{{ }}` 插值以及 `<script>` / `<style>` 的内容
+fn tokenize(content: &str) -> Vec<Token> {
+    let bytes = content.as_bytes();
+    let mut tokens = Vec::new();
+    let mut i = 0;
+    let mut line = 1;
+    let mut text_start = 0;
+
+    let advance_to = |i: &mut usize, line: &mut usize, end: usize| {
+        *line += content[*i..end].matches('\n').count();
+        *i = end;
+    };
+    let flush_text = |tokens: &mut Vec<Token>, start: usize, end: usize| {
+        if content[start..end].trim().is_empty() {
+            return;
+        }
+        tokens.push(Token::Text);
+    };
+
+    while i < bytes.len() {
+        if content[i..].starts_with("<!--") {
+            flush_text(&mut tokens, text_start, i);
+            let end = content[i..].find("-->").map(|p| i + p + 3).unwrap_or(bytes.len());
+            advance_to(&mut i, &mut line, end);
+            text_start = i;
+        } else if content[i..].starts_with("{{") {
+            let end = content[i..].find("}}").map(|p| i + p + 2).unwrap_or(bytes.len());
+            advance_to(&mut i, &mut line, end);
+        } else if content[i..].starts_with("</") {
+            flush_text(&mut tokens, text_start, i);
+            let end = content[i..].find('>').map(|p| i + p + 1).unwrap_or(bytes.len());
+            let name = content[i + 2..end.saturating_sub(1).max(i + 2)].trim().to_string();
+            tokens.push(Token::Close(name));
+            advance_to(&mut i, &mut line, end);
+            text_start = i;
+        } else if bytes[i] == b'<' && bytes.get(i + 1).is_some_and(|b| b.is_ascii_alphabetic()) {
+            flush_text(&mut tokens, text_start, i);
+            let (tag, end) = parse_tag(content, i, line);
+            let raw = matches!(tag.name.as_str(), "script" | "style") && !tag.self_closing;
+            let name = tag.name.clone();
+            tokens.push(Token::Open(tag));
+            advance_to(&mut i, &mut line, end);
+
+            // 原始文本元素：直接跳到结束标签
+            if raw {
+                let close = format!("</{}", name);
+                let end = content[i..].find(&close).map(|p| i + p).unwrap_or(bytes.len());
+                advance_to(&mut i, &mut line, end);
+            }
+            text_start = i;
+        } else {
+            let end = i + content[i..].chars().next().map(|c| c.len_utf8()).unwrap_or(1);
+            advance_to(&mut i, &mut line, end);
+        }
+    }
+    flush_text(&mut tokens, text_start, bytes.len());
+
+    tokens
+}
+
+/// 解析从 `start`（`<`）开始的开始标签，返回标签和标签之后的位置
+fn parse_tag(content: &str, start: usize, line: usize) -> (Tag, usize) {
+    let bytes = content.as_bytes();
+    let mut i = start + 1;
+
+    let name_end = content[i..]
+        .find(|c: char| c.is_whitespace() || c == '>' || c == '/')
+        .map(|p| i + p)
+        .unwrap_or(bytes.len());
+    let name = content[i..name_end].to_string();
+    i = name_end;
+
+    let mut attrs = Vec::new();
+    let mut self_closing = false;
+    while i < bytes.len() {
+        match bytes[i] {
+            b'>' => {
+                i += 1;
+                break;
+            }
+            b'/' if bytes.get(i + 1) == Some(&b'>') => {
+                self_closing = true;
+                i += 2;
+                break;
+            }
+            b if b.is_ascii_whitespace() || b == b'/' => i += 1,
+            _ => {
+                // 属性名到空白、`=`、`>` 或 `/>` 为止
+                let mut attr_end = i;
+                while attr_end < bytes.len() {
+                    let b = bytes[attr_end];
+                    if b.is_ascii_whitespace() || b == b'=' || b == b'>' || (b == b'/' && bytes.get(attr_end + 1) == Some(&b'>')) {
+                        break;
+                    }
+                    attr_end += 1;
+                }
+                let attr_name = content[i..attr_end].to_string();
+                i = attr_end;
+
+                let mut value = None;
+                let rest = content[i..].trim_start();
+                if rest.starts_with('=') {
+                    i = bytes.len() - rest.len() + 1;
+                    i += content[i..].len() - content[i..].trim_start().len();
+                    match bytes.get(i) {
+                        Some(&quote) if quote == b'"' || quote == b'\'' => {
+                            let end = content[i + 1..]
+                                .find(quote as char)
+                                .map(|p| i + 1 + p)
+                                .unwrap_or(bytes.len());
+                            value = Some(content[i + 1..end].to_string());
+                            i = (end + 1).min(bytes.len());
+                        }
+                        _ => {
+                            let end = content[i..]
+                                .find(|c: char| c.is_whitespace() || c == '>')
+                                .map(|p| i + p)
+                                .unwrap_or(bytes.len());
+                            value = Some(content[i..end].to_string());
+                            i = end;
+                        }
+                    }
+                }
+                attrs.push((attr_name, value));
+            }
+        }
+    }
+
+    (
+        Tag {
+            name,
+            attrs,
+            self_closing,
+            line,
+        },
+        i,
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_template_usages() {
+        let content = r#"<template>
+  <div class="page">
+    <label>名称</label>
+    <select v-model="status"><option>1</option></select>
+    <router-view />
+    <!-- <fake-comment /> -->
+    <order-list
+      :order-id="id"
+      page-size="20"
+      v-model:filter.trim="filter"
+      @change.stop="onChange"
+      v-if="ready"
+      v-focus
+    >
+      <template #header="{ total }">共 {{ total > 1 ? total : 0 }} 条</template>
+      <StatusTag :status="status" />
+    </order-list>
+    <EmptyState v-else>暂无数据</EmptyState>
+  </div>
+</template>
+
+<script setup lang="ts">
+const tpl = '<fake-script-tag>';
+</script>
+"#;
+
+        let usages = parse_template_usages(content).unwrap();
+        let tags: Vec<&str> = usages.iter().map(|u| u.tag.as_str()).collect();
+        assert_eq!(tags, vec!["order-list", "StatusTag", "EmptyState"]);
+
+        let list = &usages[0];
+        assert_eq!(list.line, 7);
+        assert_eq!(list.props, vec!["orderId", "pageSize", "filter"]);
+        assert_eq!(list.events, vec!["update:filter", "change"]);
+        assert_eq!(list.slots, vec!["header", "default"]);
+        assert_eq!(list.directives, vec!["v-model:filter", "v-if", "v-focus"]);
+
+        assert_eq!(usages[1].props, vec!["status"]);
+        assert!(usages[1].slots.is_empty());
+        assert_eq!(usages[2].slots, vec!["default"]);
+        assert_eq!(usages[2].directives, vec!["v-else"]);
+
+        assert!(parse_template_usages("<script>export default {}</script>").is_none());
+    }
+}
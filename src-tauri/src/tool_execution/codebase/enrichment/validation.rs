@@ -0,0 +1,181 @@
+//! LLM 标注输出的校验与修复
+//!
+//! 模型输出先按 [`label_schema`] 校验：摘要非空且不超过 160 个字符，标签 3-5 个。
+//! 未通过时由编排器带上问题列表请求一次修复；修复后仍不合格时在本地强制约束
+//! （截断摘要、截取标签、用回退标签补足）
+
+use super::interfaces::LLMResponse;
+use serde_json::{json, Value};
+
+/// 摘要最大字符数
+pub const MAX_SUMMARY_CHARS: usize = 160;
+
+/// 标签数量下限
+pub const MIN_TAGS: usize = 3;
+
+/// 标签数量上限
+pub const MAX_TAGS: usize = 5;
+
+/// 标注输出的 JSON Schema，用于工具调用 / JSON 模式
+pub fn label_schema() -> Value {
+    json!({
+        "type": "object",
+        "properties": {
+            "summary": {
+                "type": "string",
+                "minLength": 1,
+                "maxLength": MAX_SUMMARY_CHARS,
+                "description": "不超过160个字符的中文功能摘要"
+            },
+            "tags": {
+                "type": "array",
+                "items": { "type": "string", "minLength": 1 },
+                "minItems": MIN_TAGS,
+                "maxItems": MAX_TAGS,
+                "description": "3-5个业务相关的标签"
+            }
+        },
+        "required": ["summary", "tags"],
+        "additionalProperties": false
+    })
+}
+
+/// 未通过校验的输出
+#[derive(Debug, Clone)]
+pub struct LabelIssues {
+    /// 不符合要求的地方，用于修复提示词
+    pub problems: Vec<String>,
+
+    /// 能解析出摘要和标签时的候选结果（可能违反长度或数量约束）
+    pub candidate: Option<LLMResponse>,
+}
+
+/// 解析并校验模型输出
+///
+/// 允许输出被代码块或说明文字包裹；摘要与标签去除首尾空白，空标签与重复标签被忽略
+pub fn validate_labels(text: &str) -> Result<LLMResponse, LabelIssues> {
+    let invalid = |problem: String| LabelIssues {
+        problems: vec![problem],
+        candidate: None,
+    };
+
+    let json_text = match (text.find('{'), text.rfind('}')) {
+        (Some(start), Some(end)) if start < end => &text[start..=end],
+        _ => return Err(invalid("输出中没有 JSON 对象".to_string())),
+    };
+    let value: Value =
+        serde_json::from_str(json_text).map_err(|e| invalid(format!("输出不是合法的 JSON: {}", e)))?;
+
+    let Some(summary) = value["summary"].as_str().map(|s| s.trim().to_string()) else {
+        return Err(invalid("缺少字符串字段 summary".to_string()));
+    };
+    let Some(raw_tags) = value["tags"].as_array() else {
+        return Err(invalid("缺少字符串数组字段 tags".to_string()));
+    };
+    let mut tags: Vec<String> = Vec::new();
+    for tag in raw_tags.iter().filter_map(|t| t.as_str()).map(str::trim) {
+        if !tag.is_empty() && !tags.iter().any(|t| t == tag) {
+            tags.push(tag.to_string());
+        }
+    }
+
+    let mut problems = Vec::new();
+    let summary_chars = summary.chars().count();
+    if summary_chars == 0 {
+        problems.push("summary 为空".to_string());
+    } else if summary_chars > MAX_SUMMARY_CHARS {
+        problems.push(format!(
+            "summary 有 {} 个字符，超过 {} 个字符的上限",
+            summary_chars, MAX_SUMMARY_CHARS
+        ));
+    }
+    if tags.len() < MIN_TAGS || tags.len() > MAX_TAGS {
+        problems.push(format!(
+            "tags 有 {} 个不重复的非空标签，要求 {}-{} 个",
+            tags.len(),
+            MIN_TAGS,
+            MAX_TAGS
+        ));
+    }
+
+    let response = LLMResponse { summary, tags };
+    if problems.is_empty() {
+        Ok(response)
+    } else {
+        Err(LabelIssues {
+            problems,
+            candidate: Some(response),
+        })
+    }
+}
+
+/// 修复提示词：附在原提示词之后，列出上一次输出的问题
+pub fn repair_prompt(output: &str, problems: &[String]) -> String {
+    let problems: String = problems.iter().map(|p| format!("- {}\n", p)).collect();
+    format!(
+        r#"你上一次的输出不符合要求：
+{}
+上一次的输出：
+{}
+
+请修正以上问题，只返回 JSON：{{"summary": "...", "tags": ["...", "..."]}}。
+摘要不超过 {} 个字符，标签 {}-{} 个且互不重复。"#,
+        problems,
+        output.trim(),
+        MAX_SUMMARY_CHARS,
+        MIN_TAGS,
+        MAX_TAGS
+    )
+}
+
+/// 在本地强制约束：截断摘要、最多保留 5 个标签，不足 3 个时用回退标签补足。
+/// 摘要为空时无法修复
+pub fn enforce_constraints(candidate: LLMResponse, fallback_tags: &[String]) -> Option<LLMResponse> {
+    if candidate.summary.is_empty() {
+        return None;
+    }
+
+    let summary = candidate.summary.chars().take(MAX_SUMMARY_CHARS).collect();
+    let mut tags = candidate.tags;
+    tags.truncate(MAX_TAGS);
+    for tag in fallback_tags {
+        if tags.len() >= MIN_TAGS {
+            break;
+        }
+        if !tags.contains(tag) {
+            tags.push(tag.clone());
+        }
+    }
+
+    Some(LLMResponse { summary, tags })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_validate_and_enforce_labels() {
+        let valid = validate_labels(
+            "```json\n{\"summary\": \" 格式化日期 \", \"tags\": [\"日期\", \"格式化\", \"日期\", \"工具\", \"\"]}\n```",
+        )
+        .unwrap();
+        assert_eq!(valid.summary, "格式化日期");
+        assert_eq!(valid.tags, vec!["日期", "格式化", "工具"]);
+
+        let issues = validate_labels("无法分析").unwrap_err();
+        assert!(issues.candidate.is_none());
+        assert!(validate_labels(r#"{"summary": "x", "tags": "日期"}"#).unwrap_err().candidate.is_none());
+
+        let long = "很".repeat(200);
+        let issues = validate_labels(&format!(r#"{{"summary": "{}", "tags": ["日期"]}}"#, long)).unwrap_err();
+        assert_eq!(issues.problems.len(), 2);
+        assert!(repair_prompt("{}", &issues.problems).contains("超过 160 个字符"));
+
+        let fallback = vec!["function".to_string(), "日期".to_string(), "工具".to_string()];
+        let enforced = enforce_constraints(issues.candidate.unwrap(), &fallback).unwrap();
+        assert_eq!(enforced.summary.chars().count(), MAX_SUMMARY_CHARS);
+        assert_eq!(enforced.tags, vec!["日期", "function", "工具"]);
+        assert!(validate_labels(&serde_json::to_string(&enforced).unwrap()).is_ok());
+    }
+}
@@ -0,0 +1,365 @@
+//! 实体 ID
+//!
+//! 提取器生成的是展示用的短 ID（`Function:formatDate`、`Method:OrderService.refund`），
+//! 不同包、不同文件中的同名实体会冲突。FileWalker 在提取后为每个实体分配规范 ID：
+//!
+//! ```text
+//! <package>:<path>#<short_id>
+//! @acme/order:src/utils/date.ts#Function:formatDate
+//! ```
+//!
+//! - `package`: 文件所属包名（最近的 package.json 的 `name`，没有时为项目目录名）
+//! - `path`: 文件相对包根目录的路径，统一使用 `/`，与项目所在位置无关
+//! - `short_id`: 提取器生成的短 ID；同一文件内重名时按源码顺序追加 `~2`、`~3`
+//!
+//! 规范 ID 只依赖包名、包内路径和实体名，重复扫描保持不变。
+//! 短 ID 保存在 `CodeEntity::short_id` 中用于展示，由短 ID 查找实体使用 [`resolve_short_id`]
+
+use super::extractors::CodeEntity;
+use super::workspace::WorkspaceInfo;
+use std::collections::HashMap;
+use std::fmt;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// 解析后的规范实体 ID
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EntityId {
+    /// 所属包名
+    pub package: String,
+
+    /// 相对包根目录的文件路径
+    pub path: String,
+
+    /// 短 ID（`Kind:Name`）
+    pub short_id: String,
+}
+
+impl EntityId {
+    pub fn new(package: &str, path: &str, short_id: &str) -> Self {
+        Self {
+            package: package.to_string(),
+            path: path.replace('\\', "/"),
+            short_id: short_id.to_string(),
+        }
+    }
+
+    /// 解析规范 ID，短 ID 返回 `None`
+    pub fn parse(id: &str) -> Option<Self> {
+        let (package, rest) = id.split_once(':')?;
+        let (path, short_id) = rest.split_once('#')?;
+        if package.is_empty() || path.is_empty() || !short_id.contains(':') {
+            return None;
+        }
+
+        Some(Self::new(package, path, short_id))
+    }
+
+    /// 实体种类前缀，如 `Function`
+    pub fn kind(&self) -> &str {
+        short_kind(&self.short_id)
+    }
+
+    /// 实体名称（不含重名序号），如 `OrderService.refund`
+    pub fn name(&self) -> &str {
+        short_name(&self.short_id)
+    }
+}
+
+impl fmt::Display for EntityId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}:{}#{}", self.package, self.path, self.short_id)
+    }
+}
+
+/// 短 ID 的种类前缀：`Function:foo` -> `Function`
+pub fn short_kind(short_id: &str) -> &str {
+    short_id.split_once(':').map(|(kind, _)| kind).unwrap_or("")
+}
+
+/// 短 ID 的名称：`Function:foo~2` -> `foo`
+pub fn short_name(short_id: &str) -> &str {
+    let name = short_id.split_once(':').map(|(_, name)| name).unwrap_or(short_id);
+    strip_ordinal(name)
+}
+
+/// 去掉重名序号后缀：`foo~2` -> `foo`
+fn strip_ordinal(name: &str) -> &str {
+    match name.rsplit_once('~') {
+        Some((base, ordinal)) if !ordinal.is_empty() && ordinal.chars().all(|c| c.is_ascii_digit()) => base,
+        _ => name,
+    }
+}
+
+/// 为单个文件的实体分配规范 ID
+///
+/// `entities` 须为同一文件按源码顺序产出的实体；`parent` 引用同步改写为规范 ID
+pub fn assign_canonical_ids(entities: &mut [CodeEntity], package: &str, path: &str) {
+    let mut counts: HashMap<String, usize> = HashMap::new();
+    let mut mapping: HashMap<String, String> = HashMap::new();
+
+    for entity in entities.iter_mut() {
+        let count = counts.entry(entity.id.clone()).or_insert(0);
+        *count += 1;
+
+        let short_id = if *count > 1 {
+            format!("{}~{}", entity.id, count)
+        } else {
+            entity.id.clone()
+        };
+        let canonical = EntityId::new(package, path, &short_id).to_string();
+
+        mapping.entry(entity.id.clone()).or_insert_with(|| canonical.clone());
+        entity.short_id = short_id;
+        entity.id = canonical;
+    }
+
+    for entity in entities.iter_mut() {
+        if let Some(parent) = entity.parent.as_ref().and_then(|p| mapping.get(p)) {
+            entity.parent = Some(parent.clone());
+        }
+    }
+}
+
+/// 由 ID 查找实体
+///
+/// 支持三种写法：
+/// - 规范 ID：`@acme/order:src/utils/date.ts#Function:formatDate`
+/// - 路径 + 短 ID：`src/utils/date.ts#Function:formatDate`（按后缀匹配）
+/// - 短 ID：`Function:formatDate`（同时匹配 `Function:formatDate~2` 等重名实体）
+///
+/// 短 ID 可能对应多个实体，调用方需要处理多候选的情况
+pub fn resolve_short_id<'a>(entities: &'a [CodeEntity], query: &str) -> Vec<&'a CodeEntity> {
+    if let Some(exact) = entities.iter().find(|e| e.id == query) {
+        return vec![exact];
+    }
+
+    entities
+        .iter()
+        .filter(|e| id_matches(&e.id, &e.short_id, query))
+        .collect()
+}
+
+/// 由 ID 查找实体，找不到时按实体名模糊匹配
+///
+/// 先按 [`resolve_short_id`] 查找；没有结果时取查询中的名称部分
+/// （`src/a.ts#Function:foo` / `Function:foo` / `foo` -> `foo`），
+/// 依次尝试 `raw_name` 相等、忽略大小写相等、忽略大小写包含，返回第一组非空结果
+pub fn resolve_entity_fuzzy<'a>(entities: &'a [CodeEntity], query: &str) -> Vec<&'a CodeEntity> {
+    let found = resolve_short_id(entities, query);
+    if !found.is_empty() {
+        return found;
+    }
+
+    let short_id = query.rsplit_once('#').map(|(_, s)| s).unwrap_or(query);
+    let name = short_name(short_id);
+    if name.is_empty() {
+        return Vec::new();
+    }
+    let lower = name.to_lowercase();
+
+    let matchers: [&dyn Fn(&CodeEntity) -> bool; 3] = [
+        &|e| e.raw_name == name,
+        &|e| e.raw_name.to_lowercase() == lower,
+        &|e| e.raw_name.to_lowercase().contains(&lower),
+    ];
+    for matcher in matchers {
+        let found: Vec<&CodeEntity> = entities.iter().filter(|e| matcher(e)).collect();
+        if !found.is_empty() {
+            return found;
+        }
+    }
+
+    Vec::new()
+}
+
+/// 实体是否匹配路径 + 短 ID 或短 ID 形式的查询（规范 ID 的精确匹配由调用方处理）
+pub fn id_matches(id: &str, short_id: &str, query: &str) -> bool {
+    if query.contains('#') {
+        let suffix = if query.starts_with('/') {
+            query.to_string()
+        } else {
+            format!("/{}", query)
+        };
+        return id.ends_with(&suffix) || id.split_once(':').map(|(_, rest)| rest == query).unwrap_or(false);
+    }
+
+    let short_id = if short_id.is_empty() { id } else { short_id };
+    short_id == query || strip_ordinal(short_id) == query
+}
+
+/// 文件所属包的解析器
+///
+/// 从文件所在目录向上查找带 `name` 的 package.json；
+/// 项目根目录内的文件最多查找到根目录，workspace 中项目外的文件最多查找到 workspace 根目录，
+/// 找不到时归属于以项目目录名命名的根包
+pub struct PackageResolver {
+    root: PathBuf,
+    root_name: String,
+    /// 向上查找的边界：workspace 根目录（包含项目时），否则为项目根目录
+    boundary: PathBuf,
+    /// 目录 -> package.json 中的包名
+    cache: HashMap<PathBuf, Option<String>>,
+}
+
+impl PackageResolver {
+    pub fn new(root: &Path) -> Self {
+        let root_name = root
+            .file_name()
+            .and_then(|n| n.to_str())
+            .unwrap_or("project")
+            .to_string();
+
+        Self {
+            root: root.to_path_buf(),
+            root_name,
+            boundary: root.to_path_buf(),
+            cache: HashMap::new(),
+        }
+    }
+
+    /// 使用已解析的 workspace 信息创建解析器，项目外的 workspace 包也能解析到包名
+    pub fn with_workspace(root: &Path, workspace: Option<&WorkspaceInfo>) -> Self {
+        let mut resolver = Self::new(root);
+        if let Some(workspace) = workspace.filter(|w| root.starts_with(&w.root)) {
+            resolver.boundary = workspace.root.clone();
+        }
+        resolver
+    }
+
+    /// 返回 (包名, 文件相对包根目录的路径)
+    pub fn resolve(&mut self, file: &Path) -> (String, String) {
+        // 边界之外的文件不向上查找
+        let mut current = file.parent().filter(|dir| dir.starts_with(&self.boundary));
+
+        while let Some(dir) = current {
+            if let Some(name) = self.package_name(dir) {
+                return (name, Self::relative(file, dir));
+            }
+            if dir == self.root || dir == self.boundary {
+                break;
+            }
+            current = dir.parent();
+        }
+
+        let relative = if file.starts_with(&self.root) {
+            Self::relative(file, &self.root)
+        } else {
+            file.to_string_lossy().replace('\\', "/")
+        };
+        (self.root_name.clone(), relative)
+    }
+
+    /// 读取目录下 package.json 的包名（带缓存）
+    fn package_name(&mut self, dir: &Path) -> Option<String> {
+        self.cache
+            .entry(dir.to_path_buf())
+            .or_insert_with(|| {
+                let content = fs::read_to_string(dir.join("package.json")).ok()?;
+                let package_json: serde_json::Value = serde_json::from_str(&content).ok()?;
+                package_json
+                    .get("name")
+                    .and_then(|v| v.as_str())
+                    .map(|s| s.to_string())
+            })
+            .clone()
+    }
+
+    fn relative(file: &Path, dir: &Path) -> String {
+        file.strip_prefix(dir)
+            .unwrap_or(file)
+            .to_string_lossy()
+            .replace('\\', "/")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tool_execution::codebase::extractors::LocationInfo;
+
+    fn entity(id: &str, parent: Option<&str>) -> CodeEntity {
+        CodeEntity {
+            id: id.to_string(),
+            entity_type: "method".to_string(),
+            file: "packages/order/src/service.ts".to_string(),
+            loc: LocationInfo::new(1),
+            raw_name: id.to_string(),
+            parent: parent.map(|p| p.to_string()),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_assign_and_resolve_canonical_ids() {
+        let mut entities = vec![
+            entity("Class:OrderService", None),
+            entity("Method:OrderService.refund", Some("Class:OrderService")),
+            entity("Variable:config", None),
+            entity("Variable:config", None),
+        ];
+        assign_canonical_ids(&mut entities, "@acme/order", "src/service.ts");
+
+        assert_eq!(entities[0].id, "@acme/order:src/service.ts#Class:OrderService");
+        assert_eq!(entities[1].short_id, "Method:OrderService.refund");
+        assert_eq!(entities[1].parent.as_deref(), Some(entities[0].id.as_str()));
+        assert_eq!(entities[3].id, "@acme/order:src/service.ts#Variable:config~2");
+
+        let parsed = EntityId::parse(&entities[3].id).unwrap();
+        assert_eq!(parsed.package, "@acme/order");
+        assert_eq!(parsed.kind(), "Variable");
+        assert_eq!(parsed.name(), "config");
+        assert_eq!(parsed.to_string(), entities[3].id);
+        assert!(EntityId::parse("Function:formatDate").is_none());
+
+        assert_eq!(resolve_short_id(&entities, "Variable:config").len(), 2);
+        assert_eq!(resolve_short_id(&entities, "src/service.ts#Class:OrderService").len(), 1);
+        assert_eq!(resolve_short_id(&entities, &entities[1].id.clone()).len(), 1);
+
+        // 短 ID 种类写错时按实体名模糊匹配
+        assert!(resolve_short_id(&entities, "Function:refund").is_empty());
+        let fuzzy = resolve_entity_fuzzy(&entities, "Function:Refund");
+        assert_eq!(fuzzy.len(), 1);
+        assert_eq!(fuzzy[0].short_id, "Method:OrderService.refund");
+    }
+
+    #[test]
+    fn test_package_resolver() {
+        let root = Path::new("/tmp/test_entity_id_packages");
+        fs::remove_dir_all(root).ok();
+        fs::create_dir_all(root.join("packages/order/src")).unwrap();
+        fs::create_dir_all(root.join("scripts")).unwrap();
+        fs::write(root.join("packages/order/package.json"), r#"{ "name": "@acme/order" }"#).unwrap();
+
+        let mut resolver = PackageResolver::new(root);
+        assert_eq!(
+            resolver.resolve(&root.join("packages/order/src/index.ts")),
+            ("@acme/order".to_string(), "src/index.ts".to_string())
+        );
+        assert_eq!(
+            resolver.resolve(&root.join("scripts/build.ts")),
+            ("test_entity_id_packages".to_string(), "scripts/build.ts".to_string())
+        );
+
+        // 项目外的文件不会向上查找到无关的 package.json
+        let app = root.join("apps/web");
+        fs::create_dir_all(app.join("src")).unwrap();
+        fs::write(root.join("package.json"), r#"{ "name": "monorepo" }"#).unwrap();
+        let outside = root.join("tools/gen.ts");
+        let mut resolver = PackageResolver::new(&app);
+        assert_eq!(resolver.resolve(&app.join("src/main.ts")).0, "web");
+        assert_eq!(resolver.resolve(&outside).0, "web");
+
+        // 指定 workspace 时，项目外的 workspace 包查找到 workspace 根目录为止
+        fs::write(root.join("pnpm-workspace.yaml"), "packages:\n  - packages/*\n  - apps/*\n").unwrap();
+        let workspace = WorkspaceInfo::discover(&app, &[]).unwrap();
+        let mut resolver = PackageResolver::with_workspace(&app, workspace.as_ref());
+        assert_eq!(
+            resolver.resolve(&root.join("packages/order/src/index.ts")),
+            ("@acme/order".to_string(), "src/index.ts".to_string())
+        );
+        assert_eq!(resolver.resolve(&outside).0, "monorepo");
+
+        fs::remove_dir_all(root).ok();
+    }
+}
@@ -39,6 +39,11 @@ mod tests {
             input_path: base_entities_path.to_string_lossy().to_string(),
             output_path: "src/data/entities.enriched.json".to_string(),
             pre_initialize: false,
+            checkpoint_path: None,
+            resume: false,
+            use_cache: true,
+            cache_path: None,
+            llm: None,
         };
 
         let mut orchestrator =
@@ -0,0 +1,250 @@
+//! 实体详情提取
+//!
+//! 从声明节点提取签名、修饰符、装饰器和前置 JSDoc，
+//! TS/JS/Vue 提取器在记录实体后统一调用 [`annotate`]
+
+use tree_sitter::Node;
+use super::{CodeEntity, ParamInfo, Signature};
+
+/// 函数类节点
+const FUNCTION_KINDS: [&str; 5] = [
+    "arrow_function",
+    "function_expression",
+    "function",
+    "generator_function",
+    "generator_function_declaration",
+];
+
+/// 作为修饰符记录的关键字节点
+const MODIFIER_TOKENS: [&str; 9] = [
+    "async", "static", "abstract", "readonly", "declare", "override", "get", "set", "const",
+];
+
+/// 声明外层的包装节点，JSDoc 与装饰器挂在最外层
+const WRAPPER_KINDS: [&str; 4] = [
+    "lexical_declaration",
+    "variable_declaration",
+    "export_statement",
+    "ambient_declaration",
+];
+
+/// 填充实体的导出状态、签名、修饰符、装饰器与文档注释
+///
+/// `node` 为实体的声明节点（函数/类/成员/变量声明器等）
+pub fn annotate(entity: &mut CodeEntity, node: &Node, content: &str, is_exported: bool) {
+    entity.is_exported = is_exported;
+    entity.signature = signature(node, content);
+    entity.modifiers = modifiers(node, content);
+    entity.decorators = decorators(node, content);
+    entity.doc_comment = doc_comment(node, content);
+}
+
+/// 函数签名：参数、返回类型、类型参数
+///
+/// 变量声明器取其初始化函数的签名；非函数声明返回 `None`
+pub fn signature(node: &Node, content: &str) -> Option<Signature> {
+    let function = function_node(node)?;
+
+    let params = match function.child_by_field_name("parameters") {
+        Some(parameters) => {
+            let mut cursor = parameters.walk();
+            parameters
+                .named_children(&mut cursor)
+                .filter(|p| p.kind() != "comment")
+                .map(|p| param_info(&p, content))
+                .collect()
+        }
+        // 单参数箭头函数：`x => x + 1`
+        None => function
+            .child_by_field_name("parameter")
+            .map(|p| vec![param_info(&p, content)])
+            .unwrap_or_default(),
+    };
+
+    Some(Signature {
+        params,
+        return_type: function
+            .child_by_field_name("return_type")
+            .map(|t| type_text(&t, content)),
+        type_params: function
+            .child_by_field_name("type_parameters")
+            .map(|t| text(&t, content).to_string()),
+    })
+}
+
+/// 修饰符：async / generator / static / abstract / readonly / 访问控制 / default 等
+pub fn modifiers(node: &Node, content: &str) -> Vec<String> {
+    let mut result = Vec::new();
+
+    let mut targets = vec![*node];
+    if let Some(function) = function_node(node).filter(|f| f.id() != node.id()) {
+        targets.push(function);
+    }
+
+    for target in &targets {
+        let mut cursor = target.walk();
+        for child in target.children(&mut cursor) {
+            let modifier = match child.kind() {
+                "accessibility_modifier" => text(&child, content).to_string(),
+                "*" => "generator".to_string(),
+                kind if MODIFIER_TOKENS.contains(&kind) => kind.to_string(),
+                _ => continue,
+            };
+            if !result.contains(&modifier) {
+                result.push(modifier);
+            }
+        }
+        if target.kind().starts_with("generator_function") && !result.iter().any(|m| m == "generator") {
+            result.push("generator".to_string());
+        }
+    }
+
+    // export default / declare 挂在外层节点上
+    for wrapper in wrappers(node) {
+        let mut cursor = wrapper.walk();
+        for child in wrapper.children(&mut cursor) {
+            let modifier = match child.kind() {
+                "default" | "declare" => child.kind(),
+                _ => continue,
+            };
+            if !result.iter().any(|m| m == modifier) {
+                result.push(modifier.to_string());
+            }
+        }
+    }
+
+    result
+}
+
+/// 装饰器源码，如 `@Injectable()`
+pub fn decorators(node: &Node, content: &str) -> Vec<String> {
+    let mut result = Vec::new();
+
+    for target in std::iter::once(*node).chain(wrappers(node)) {
+        let mut cursor = target.walk();
+        for child in target.children(&mut cursor) {
+            if child.kind() == "decorator" {
+                result.push(text(&child, content).to_string());
+            }
+        }
+    }
+
+    // 类成员的装饰器是 class_body 中位于成员之前的兄弟节点（export 的类也会出现在 export_statement 中）
+    let mut preceding = Vec::new();
+    let mut sibling = node.prev_named_sibling();
+    while let Some(current) = sibling.filter(|s| s.kind() == "decorator") {
+        preceding.push(text(&current, content).to_string());
+        sibling = current.prev_named_sibling();
+    }
+    preceding.reverse();
+    for decorator in preceding {
+        if !result.contains(&decorator) {
+            result.push(decorator);
+        }
+    }
+
+    result
+}
+
+/// 紧邻声明之前的 JSDoc（`/** ... */`），去掉注释符号后按行拼接
+pub fn doc_comment(node: &Node, content: &str) -> Option<String> {
+    let outer = wrappers(node).last().copied().unwrap_or(*node);
+    let comment = outer.prev_named_sibling().filter(|c| c.kind() == "comment")?;
+
+    // 注释与声明之间不能隔着其他代码行
+    if outer.start_position().row > comment.end_position().row + 1 {
+        return None;
+    }
+
+    let raw = text(&comment, content);
+    if !raw.starts_with("/**") {
+        return None;
+    }
+
+    let cleaned = raw
+        .trim_start_matches("/**")
+        .trim_end_matches("*/")
+        .lines()
+        .map(|line| line.trim().trim_start_matches('*').trim())
+        .filter(|line| !line.is_empty())
+        .collect::<Vec<_>>()
+        .join("\n");
+
+    if cleaned.is_empty() {
+        None
+    } else {
+        Some(cleaned)
+    }
+}
+
+/// 声明本身或变量声明器初始化的函数节点
+fn function_node<'a>(node: &Node<'a>) -> Option<Node<'a>> {
+    if node.child_by_field_name("parameters").is_some() || FUNCTION_KINDS.contains(&node.kind()) {
+        return Some(*node);
+    }
+
+    // `const foo = async () => {}` / `handle = () => {}`
+    node.child_by_field_name("value")
+        .filter(|v| FUNCTION_KINDS.contains(&v.kind()))
+}
+
+/// 由内向外的包装节点（变量声明、export、declare）
+fn wrappers<'a>(node: &Node<'a>) -> Vec<Node<'a>> {
+    let mut result = Vec::new();
+    let mut current = *node;
+
+    while let Some(parent) = current.parent() {
+        if !WRAPPER_KINDS.contains(&parent.kind()) {
+            break;
+        }
+        result.push(parent);
+        current = parent;
+    }
+
+    result
+}
+
+/// 单个参数
+fn param_info(param: &Node, content: &str) -> ParamInfo {
+    match param.kind() {
+        // TS: `id: string` / `reason?: string` / `private api: Api` / `size = 10`
+        "required_parameter" | "optional_parameter" => ParamInfo {
+            name: param
+                .child_by_field_name("pattern")
+                .map(|p| text(&p, content).to_string())
+                .unwrap_or_else(|| text(param, content).to_string()),
+            param_type: param
+                .child_by_field_name("type")
+                .map(|t| type_text(&t, content)),
+            optional: param.kind() == "optional_parameter",
+            default: param
+                .child_by_field_name("value")
+                .map(|v| text(&v, content).to_string()),
+        },
+        // JS: `size = 10`
+        "assignment_pattern" => ParamInfo {
+            name: param
+                .child_by_field_name("left")
+                .map(|p| text(&p, content).to_string())
+                .unwrap_or_default(),
+            optional: true,
+            default: param
+                .child_by_field_name("right")
+                .map(|v| text(&v, content).to_string()),
+            ..Default::default()
+        },
+        _ => ParamInfo {
+            name: text(param, content).to_string(),
+            ..Default::default()
+        },
+    }
+}
+
+/// 类型注解文本（去掉前导冒号）
+fn type_text(node: &Node, content: &str) -> String {
+    text(node, content).trim_start_matches(':').trim().to_string()
+}
+
+fn text<'s>(node: &Node, content: &'s str) -> &'s str {
+    node.utf8_text(content.as_bytes()).unwrap_or("")
+}
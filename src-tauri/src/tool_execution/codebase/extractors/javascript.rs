@@ -0,0 +1,365 @@
+//! JavaScript/JSX 代码提取器
+//!
+//! 使用 tree-sitter-javascript 解析 `.js/.jsx/.mjs/.cjs` 文件。
+//! ES Module 导出复用 TypeScriptExtractor 的节点处理逻辑，
+//! 另外识别 CommonJS 的 `module.exports = ...` 与 `exports.foo = ...` 导出
+
+use std::collections::HashMap;
+use std::fs;
+use std::path::Path;
+use tree_sitter::Node;
+use super::details;
+use super::type_utils::TypeUtils;
+use super::typescript::TypeScriptExtractor;
+use super::{CodeEntity, LocationInfo};
+
+/// JavaScript/JSX 提取器
+pub struct JavaScriptExtractor {
+    inner: TypeScriptExtractor,
+}
+
+impl JavaScriptExtractor {
+    /// 创建新的 JavaScript 提取器（JSX 语法内置于 JavaScript 语法中）
+    pub fn new() -> Result<Self, Box<dyn std::error::Error>> {
+        Ok(Self {
+            inner: TypeScriptExtractor::with_language(tree_sitter_javascript::language())?,
+        })
+    }
+
+    /// 提取文件中的实体
+    pub fn extract(
+        &mut self,
+        file_path: &str,
+        root_dir: &str,
+    ) -> Result<Vec<CodeEntity>, Box<dyn std::error::Error>> {
+        let content = fs::read_to_string(file_path)?;
+        // .js 文件中的 React 组件同样使用 JSX
+        let is_jsx_context = file_path.ends_with(".jsx")
+            || content.contains("from 'react'")
+            || content.contains("from \"react\"")
+            || content.contains("require('react')")
+            || content.contains("require(\"react\")");
+        let relative_path = Path::new(file_path)
+            .strip_prefix(root_dir)
+            .unwrap_or(Path::new(file_path))
+            .to_string_lossy()
+            .to_string();
+
+        let tree = self.inner.parse(&content)?;
+        let root_node = tree.root_node();
+
+        // 1. ES Module 导出
+        let mut entities = Vec::new();
+        self.inner
+            .extract_from_root(&root_node, &content, &relative_path, is_jsx_context, &mut entities)?;
+
+        // 2. CommonJS 导出
+        let declarations = Self::collect_declarations(&root_node, &content);
+        let mut cursor = root_node.walk();
+        for child in root_node.children(&mut cursor) {
+            if child.kind() != "expression_statement" {
+                continue;
+            }
+            if let Some(assignment) = child.named_child(0).filter(|n| n.kind() == "assignment_expression") {
+                self.handle_commonjs_assignment(
+                    &assignment,
+                    &content,
+                    &relative_path,
+                    is_jsx_context,
+                    &declarations,
+                    &mut entities,
+                )?;
+            }
+        }
+
+        Ok(entities)
+    }
+
+    /// 收集顶层声明（名称 -> 声明节点），用于解析 `module.exports = { foo }` 之类的引用
+    fn collect_declarations<'a>(root_node: &Node<'a>, content: &str) -> HashMap<String, Node<'a>> {
+        let mut declarations = HashMap::new();
+        let mut cursor = root_node.walk();
+
+        for child in root_node.children(&mut cursor) {
+            match child.kind() {
+                "function_declaration" | "generator_function_declaration" | "class_declaration" => {
+                    if let Some(name) = child
+                        .child_by_field_name("name")
+                        .and_then(|n| n.utf8_text(content.as_bytes()).ok())
+                    {
+                        declarations.insert(name.to_string(), child);
+                    }
+                }
+                "lexical_declaration" | "variable_declaration" => {
+                    let mut inner_cursor = child.walk();
+                    for declarator in child.children(&mut inner_cursor) {
+                        if declarator.kind() != "variable_declarator" {
+                            continue;
+                        }
+                        if let Some(name) = declarator
+                            .child_by_field_name("name")
+                            .filter(|n| n.kind() == "identifier")
+                            .and_then(|n| n.utf8_text(content.as_bytes()).ok())
+                        {
+                            declarations.insert(name.to_string(), declarator);
+                        }
+                    }
+                }
+                _ => {}
+            }
+        }
+
+        declarations
+    }
+
+    /// 处理 CommonJS 赋值导出
+    ///
+    /// - `module.exports = { foo, bar: baz, qux() {} }`
+    /// - `module.exports = foo` / `module.exports = function Foo() {}` / `module.exports = class Foo {}`
+    /// - `exports.foo = ...` / `module.exports.foo = ...`
+    fn handle_commonjs_assignment(
+        &self,
+        node: &Node,
+        content: &str,
+        file_path: &str,
+        is_jsx_context: bool,
+        declarations: &HashMap<String, Node>,
+        entities: &mut Vec<CodeEntity>,
+    ) -> Result<(), Box<dyn std::error::Error>> {
+        let (left, right) = match (node.child_by_field_name("left"), node.child_by_field_name("right")) {
+            (Some(left), Some(right)) => (left, right),
+            _ => return Ok(()),
+        };
+        let target = left.utf8_text(content.as_bytes())?;
+
+        if target == "module.exports" {
+            match right.kind() {
+                "object" => {
+                    let mut cursor = right.walk();
+                    for property in right.named_children(&mut cursor) {
+                        self.handle_exported_property(&property, content, file_path, is_jsx_context, declarations, entities)?;
+                    }
+                }
+                _ => {
+                    let file_name = Path::new(file_path)
+                        .file_stem()
+                        .and_then(|s| s.to_str())
+                        .unwrap_or("unknown");
+                    self.handle_exported_value(file_name, &right, content, file_path, is_jsx_context, declarations, entities)?;
+                }
+            }
+        } else if let Some(name) = target
+            .strip_prefix("module.exports.")
+            .or_else(|| target.strip_prefix("exports."))
+        {
+            if !name.is_empty() && !name.contains('.') {
+                self.handle_exported_value(name, &right, content, file_path, is_jsx_context, declarations, entities)?;
+            }
+        }
+
+        Ok(())
+    }
+
+    /// 处理 `module.exports = { ... }` 中的单个属性
+    fn handle_exported_property(
+        &self,
+        property: &Node,
+        content: &str,
+        file_path: &str,
+        is_jsx_context: bool,
+        declarations: &HashMap<String, Node>,
+        entities: &mut Vec<CodeEntity>,
+    ) -> Result<(), Box<dyn std::error::Error>> {
+        match property.kind() {
+            // { foo }
+            "shorthand_property_identifier" => {
+                let name = property.utf8_text(content.as_bytes())?;
+                if let Some(declaration) = declarations.get(name) {
+                    self.handle_declaration(declaration, content, file_path, is_jsx_context, entities)?;
+                }
+            }
+            // { foo: bar } / { foo: () => {} }
+            "pair" => {
+                if let (Some(key), Some(value)) = (property.child_by_field_name("key"), property.child_by_field_name("value")) {
+                    let name = key.utf8_text(content.as_bytes())?.trim_matches(|c| c == '\'' || c == '"');
+                    self.handle_exported_value(name, &value, content, file_path, is_jsx_context, declarations, entities)?;
+                }
+            }
+            // { foo() {} }
+            "method_definition" => {
+                self.inner
+                    .handle_function_declaration(property, content, file_path, is_jsx_context, true, entities)?;
+            }
+            _ => {}
+        }
+
+        Ok(())
+    }
+
+    /// 处理以 `name` 导出的值：标识符引用解析到声明，其余按表达式判定
+    #[allow(clippy::too_many_arguments)]
+    fn handle_exported_value(
+        &self,
+        name: &str,
+        value: &Node,
+        content: &str,
+        file_path: &str,
+        is_jsx_context: bool,
+        declarations: &HashMap<String, Node>,
+        entities: &mut Vec<CodeEntity>,
+    ) -> Result<(), Box<dyn std::error::Error>> {
+        match value.kind() {
+            "identifier" => {
+                let reference = value.utf8_text(content.as_bytes())?;
+                match declarations.get(reference) {
+                    Some(declaration) if reference == name => {
+                        self.handle_declaration(declaration, content, file_path, is_jsx_context, entities)?;
+                    }
+                    Some(declaration) => {
+                        let code = declaration.utf8_text(content.as_bytes())?;
+                        self.inner
+                            .push_variable_entity(name, code, declaration, content, file_path, is_jsx_context, entities);
+                    }
+                    None => {}
+                }
+            }
+            "class" => {
+                let code = value.utf8_text(content.as_bytes())?;
+                let class_name = value
+                    .child_by_field_name("name")
+                    .and_then(|n| n.utf8_text(content.as_bytes()).ok())
+                    .unwrap_or(name);
+                let is_component = TypeUtils::is_component_class(class_name, code, is_jsx_context);
+                let type_info = TypeUtils::get_class_type_info(is_component);
+                let class_id = format!("{}:{}", type_info.id_prefix, name);
+                let mut entity = CodeEntity {
+                    id: class_id.clone(),
+                    entity_type: type_info.entity_type,
+                    file: file_path.to_string(),
+                    loc: LocationInfo::with_range(
+                        value.start_position().row + 1,
+                        value.end_position().row + 1,
+                    ),
+                    raw_name: class_name.to_string(),
+                    ..Default::default()
+                };
+                details::annotate(&mut entity, value, content, true);
+                entities.push(entity);
+                self.inner
+                    .handle_class_members(value, content, file_path, name, &class_id, entities)?;
+            }
+            _ => {
+                let code = value.utf8_text(content.as_bytes())?;
+                self.inner
+                    .push_variable_entity(name, code, value, content, file_path, is_jsx_context, entities);
+            }
+        }
+
+        Ok(())
+    }
+
+    /// 按声明类型记录实体（CommonJS 导出的声明视为已导出）
+    fn handle_declaration(
+        &self,
+        declaration: &Node,
+        content: &str,
+        file_path: &str,
+        is_jsx_context: bool,
+        entities: &mut Vec<CodeEntity>,
+    ) -> Result<(), Box<dyn std::error::Error>> {
+        match declaration.kind() {
+            "function_declaration" | "generator_function_declaration" => self
+                .inner
+                .handle_function_declaration(declaration, content, file_path, is_jsx_context, true, entities),
+            "class_declaration" => self
+                .inner
+                .handle_class_declaration(declaration, content, file_path, is_jsx_context, true, entities),
+            "variable_declarator" => self
+                .inner
+                .handle_variable_declarator(declaration, content, file_path, is_jsx_context, entities),
+            _ => Ok(()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_javascript_commonjs_exports() {
+        let mut extractor = JavaScriptExtractor::new().unwrap();
+
+        let test_code = r#"
+function formatPrice(value) {
+    return value.toFixed(2);
+}
+
+const API_BASE = '/api';
+
+class OrderClient {
+    fetch() {}
+}
+
+exports.parseDate = function (text) {
+    return new Date(text);
+};
+
+module.exports = {
+    formatPrice,
+    OrderClient,
+    base: API_BASE,
+    ping() {}
+};
+        "#;
+
+        let temp_file = "/tmp/test_javascript_cjs.js";
+        fs::write(temp_file, test_code).unwrap();
+
+        let entities = extractor.extract(temp_file, "/tmp").unwrap();
+        let ids: Vec<&str> = entities.iter().map(|e| e.id.as_str()).collect();
+
+        assert!(ids.contains(&"Function:formatPrice"));
+        assert!(ids.contains(&"Class:OrderClient"));
+        assert!(ids.contains(&"Method:OrderClient.fetch"));
+        assert!(ids.contains(&"Function:parseDate"));
+        assert!(ids.contains(&"Variable:base"));
+        assert!(ids.contains(&"Function:ping"));
+
+        let format_price = entities.iter().find(|e| e.raw_name == "formatPrice").unwrap();
+        assert_eq!(format_price.loc.start_line, 2);
+        assert_eq!(format_price.loc.end_line, 4);
+
+        fs::remove_file(temp_file).ok();
+    }
+
+    #[test]
+    fn test_jsx_esm_component() {
+        let mut extractor = JavaScriptExtractor::new().unwrap();
+
+        let test_code = r#"
+import React from 'react';
+
+export const UserCard = ({ user }) => {
+    return <div>{user.name}</div>;
+};
+
+export default function App() {
+    return <UserCard user={{ name: 'a' }} />;
+}
+        "#;
+
+        let temp_file = "/tmp/test_javascript_esm.jsx";
+        fs::write(temp_file, test_code).unwrap();
+
+        let entities = extractor.extract(temp_file, "/tmp").unwrap();
+        let components: Vec<_> = entities
+            .iter()
+            .filter(|e| e.entity_type == "component")
+            .collect();
+
+        assert_eq!(components.len(), 2);
+
+        fs::remove_file(temp_file).ok();
+    }
+}
@@ -2,6 +2,9 @@
 //!
 //! 提供各种语言和框架的代码实体提取器
 
+pub mod details;
+pub mod javascript;
+pub mod store;
 pub mod type_utils;
 pub mod typescript;
 pub mod vue;
@@ -10,17 +13,231 @@ pub mod vue;
 use serde::{Deserialize, Serialize};
 
 /// 代码实体
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct CodeEntity {
+    /// 实体 ID：提取器产出短 ID（如 `Function:formatDate`），
+    /// FileWalker 改写为规范 ID（见 `entity_id` 模块）
     pub id: String,
+
+    /// 展示用短 ID，分配规范 ID 时填充
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub short_id: String,
+
     pub entity_type: String,
     pub file: String,
     pub loc: LocationInfo,
     pub raw_name: String,
+
+    /// 所属实体 ID（如 SFC 中 setup 函数所属的组件）
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub parent: Option<String>,
+
+    /// 组件对外接口（props/emits/expose 及 Options API 成员）
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub component: Option<ComponentApi>,
+
+    /// store 模型（Pinia / Vuex 的 state、getters、actions）
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub store: Option<StoreModel>,
+
+    /// 是否从模块导出（类成员、setup 内部函数为 false）
+    #[serde(default)]
+    pub is_exported: bool,
+
+    /// 函数/方法签名
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub signature: Option<Signature>,
+
+    /// 修饰符，如 async、generator、static、private、default
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub modifiers: Vec<String>,
+
+    /// 装饰器源码，如 `@Injectable()`
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub decorators: Vec<String>,
+
+    /// 声明前的 JSDoc 注释（已去除注释符号）
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub doc_comment: Option<String>,
+}
+
+/// 函数签名
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct Signature {
+    /// 参数列表
+    #[serde(default)]
+    pub params: Vec<ParamInfo>,
+
+    /// 返回类型注解
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub return_type: Option<String>,
+
+    /// 泛型参数，如 `<T extends object>`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub type_params: Option<String>,
+}
+
+impl Signature {
+    /// 格式化为 `name<T>(a: string, b?: number): R`
+    pub fn display(&self, name: &str) -> String {
+        let params = self
+            .params
+            .iter()
+            .map(|p| {
+                let mut text = p.name.clone();
+                if p.optional && p.default.is_none() {
+                    text.push('?');
+                }
+                if let Some(param_type) = &p.param_type {
+                    text.push_str(&format!(": {}", param_type));
+                }
+                if let Some(default) = &p.default {
+                    text.push_str(&format!(" = {}", default));
+                }
+                text
+            })
+            .collect::<Vec<_>>()
+            .join(", ");
+
+        let mut text = format!(
+            "{}{}({})",
+            name,
+            self.type_params.as_deref().unwrap_or(""),
+            params
+        );
+        if let Some(return_type) = &self.return_type {
+            text.push_str(&format!(": {}", return_type));
+        }
+        text
+    }
+}
+
+/// 函数参数
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct ParamInfo {
+    pub name: String,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub param_type: Option<String>,
+
+    #[serde(default)]
+    pub optional: bool,
+
+    /// 默认值源码
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub default: Option<String>,
+}
+
+/// 组件接口信息
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct ComponentApi {
+    /// 组件 props
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub props: Vec<PropInfo>,
+
+    /// 组件触发的事件
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub emits: Vec<String>,
+
+    /// 通过 defineExpose / expose 暴露的成员
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub exposed: Vec<String>,
+
+    /// Options API: data() 返回的字段
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub data: Vec<String>,
+
+    /// Options API: computed
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub computed: Vec<String>,
+
+    /// Options API: methods
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub methods: Vec<String>,
+
+    /// Options API: watch
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub watch: Vec<String>,
+}
+
+impl ComponentApi {
+    /// 是否没有任何接口信息
+    pub fn is_empty(&self) -> bool {
+        *self == Self::default()
+    }
+}
+
+/// store 的定义方式
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum StoreStyle {
+    /// Pinia options 写法
+    #[default]
+    Options,
+    /// Pinia setup 写法
+    Setup,
+    /// Vuex store 或模块
+    Vuex,
+}
+
+/// Pinia / Vuex store 模型
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct StoreModel {
+    /// Pinia store id（`defineStore` 的第一个参数）或 Vuex 模块名，Vuex 根 store 为 `root`
+    pub id: String,
+
+    pub style: StoreStyle,
+
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub state: Vec<String>,
+
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub getters: Vec<String>,
+
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub actions: Vec<String>,
+
+    /// Vuex mutations
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub mutations: Vec<String>,
+
+    /// Vuex 子模块名
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub modules: Vec<String>,
+
+    /// Vuex 模块是否启用命名空间
+    #[serde(default)]
+    pub namespaced: bool,
+}
+
+impl StoreModel {
+    /// 是否包含指定成员
+    pub fn has_member(&self, name: &str) -> bool {
+        [&self.state, &self.getters, &self.actions, &self.mutations]
+            .iter()
+            .any(|members| members.iter().any(|m| m == name))
+    }
+}
+
+/// 组件 prop
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct PropInfo {
+    pub name: String,
+
+    /// 类型（TS 类型或运行时构造器，如 `string` / `String`）
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub prop_type: Option<String>,
+
+    #[serde(default)]
+    pub required: bool,
+
+    /// 默认值源码
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub default: Option<String>,
 }
 
 /// 位置信息
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct LocationInfo {
     pub start_line: usize,
     pub end_line: usize,
@@ -43,6 +260,7 @@ impl LocationInfo {
 }
 
 // 重新导出
+pub use javascript::JavaScriptExtractor;
 pub use type_utils::{TypeInfo, TypeUtils};
 pub use typescript::TypeScriptExtractor;
 pub use vue::VueExtractor;
@@ -0,0 +1,361 @@
+//! 状态管理 store 的模型提取
+//!
+//! 识别 Pinia 与 Vuex 的 store 定义，提取 state / getters / actions（Vuex 还有 mutations、modules）：
+//! - Pinia options 写法：`defineStore('cart', { state, getters, actions })`、`defineStore({ id: 'cart', ... })`
+//! - Pinia setup 写法：`defineStore('cart', () => { ...; return { items, total, fetch } })`，
+//!   按声明方式归类：`ref` / `reactive` 为 state，`computed` 为 getter，函数为 action
+//! - Vuex：`createStore({...})`、`new Vuex.Store({...})` 以及带 `mutations` / `namespaced` 的模块对象
+
+use super::{StoreModel, StoreStyle};
+use std::collections::HashMap;
+use tree_sitter::Node;
+
+/// 识别 Pinia store 定义：`defineStore(...)`
+pub fn pinia_store(node: &Node, source: &str) -> Option<StoreModel> {
+    let node = unwrap_expression(*node);
+    if node.kind() != "call_expression" || callee_name(&node, source)? != "defineStore" {
+        return None;
+    }
+    let arguments = node.child_by_field_name("arguments")?;
+    let first = arguments.named_child(0)?;
+
+    // `defineStore({ id: 'cart', state, ... })`
+    if first.kind() == "object" {
+        let id = object_pairs(&first, source)
+            .into_iter()
+            .find(|(key, _)| key == "id")
+            .and_then(|(_, value)| string_value(&value, source))?;
+        let mut model = options_model(&first, source, StoreStyle::Options);
+        model.id = id;
+        return Some(model);
+    }
+
+    let id = string_value(&first, source)?;
+    let mut model = match arguments.named_child(1) {
+        Some(options) if options.kind() == "object" => options_model(&options, source, StoreStyle::Options),
+        Some(setup) if matches!(setup.kind(), "arrow_function" | "function_expression" | "function") => {
+            setup_model(&setup, source)
+        }
+        _ => StoreModel {
+            style: StoreStyle::Options,
+            ..Default::default()
+        },
+    };
+    model.id = id;
+    Some(model)
+}
+
+/// 识别 Vuex 根 store：`createStore({...})`、`new Vuex.Store({...})`
+pub fn vuex_store(node: &Node, source: &str) -> Option<StoreModel> {
+    let node = unwrap_expression(*node);
+    let is_store = match node.kind() {
+        "call_expression" => callee_name(&node, source)? == "createStore",
+        "new_expression" => node
+            .child_by_field_name("constructor")
+            .map(|c| matches!(text(&c, source), "Vuex.Store" | "Store"))
+            .unwrap_or(false),
+        _ => false,
+    };
+    if !is_store {
+        return None;
+    }
+
+    let options = node.child_by_field_name("arguments")?.named_child(0)?;
+    (options.kind() == "object").then(|| options_model(&options, source, StoreStyle::Vuex))
+}
+
+/// 识别 Vuex 模块对象：含 `mutations` 或 `namespaced`，以及 state / getters / actions 之一
+pub fn vuex_module(node: &Node, source: &str) -> Option<StoreModel> {
+    let node = unwrap_expression(*node);
+    if node.kind() != "object" {
+        return None;
+    }
+    let keys: Vec<String> = object_pairs(&node, source).into_iter().map(|(key, _)| key).collect();
+    let has = |name: &str| keys.iter().any(|k| k == name);
+    let is_module = (has("mutations") || has("namespaced")) && (has("state") || has("getters") || has("actions"));
+
+    is_module.then(|| options_model(&node, source, StoreStyle::Vuex))
+}
+
+/// options 对象（Pinia options 写法、Vuex store / 模块）中的成员
+fn options_model(options: &Node, source: &str, style: StoreStyle) -> StoreModel {
+    let mut model = StoreModel {
+        style,
+        ..Default::default()
+    };
+
+    for (key, value) in object_pairs(options, source) {
+        match key.as_str() {
+            "state" => {
+                let state = if value.kind() == "object" {
+                    Some(value)
+                } else {
+                    returned_object(&value)
+                };
+                model.state = state.map(|s| object_keys(&s, source)).unwrap_or_default();
+            }
+            "getters" => model.getters = object_keys(&value, source),
+            "actions" => model.actions = object_keys(&value, source),
+            "mutations" => model.mutations = object_keys(&value, source),
+            "modules" => model.modules = object_keys(&value, source),
+            "namespaced" => model.namespaced = text(&value, source) == "true",
+            _ => {}
+        }
+    }
+
+    model
+}
+
+/// setup 写法：按函数体中的声明归类返回的成员
+fn setup_model(setup: &Node, source: &str) -> StoreModel {
+    let mut model = StoreModel {
+        style: StoreStyle::Setup,
+        ..Default::default()
+    };
+    let Some(body) = setup.child_by_field_name("body") else {
+        return model;
+    };
+
+    // 名称 -> 归类
+    let mut declared: Vec<(String, Member)> = Vec::new();
+    let mut cursor = body.walk();
+    for statement in body.named_children(&mut cursor) {
+        match statement.kind() {
+            "function_declaration" => {
+                if let Some(name) = statement.child_by_field_name("name") {
+                    declared.push((text(&name, source).to_string(), Member::Action));
+                }
+            }
+            "lexical_declaration" | "variable_declaration" => {
+                let mut declarators = statement.walk();
+                for declarator in statement.named_children(&mut declarators) {
+                    let (Some(name), Some(value)) = (
+                        declarator.child_by_field_name("name"),
+                        declarator.child_by_field_name("value"),
+                    ) else {
+                        continue;
+                    };
+                    if name.kind() == "identifier" {
+                        declared.push((text(&name, source).to_string(), classify(&value, source)));
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+
+    let kinds: HashMap<&str, Member> = declared.iter().map(|(name, member)| (name.as_str(), *member)).collect();
+    let exposed: Vec<(String, Member)> = match returned_object(setup) {
+        Some(object) => object_pairs(&object, source)
+            .into_iter()
+            .map(|(key, value)| {
+                let member = match value.kind() {
+                    "identifier" | "shorthand_property_identifier" => kinds.get(text(&value, source)).copied().unwrap_or(Member::State),
+                    _ => classify(&value, source),
+                };
+                (key, member)
+            })
+            .collect(),
+        None => declared,
+    };
+
+    for (name, member) in exposed {
+        match member {
+            Member::State => model.state.push(name),
+            Member::Getter => model.getters.push(name),
+            Member::Action => model.actions.push(name),
+        }
+    }
+    model
+}
+
+#[derive(Debug, Clone, Copy)]
+enum Member {
+    State,
+    Getter,
+    Action,
+}
+
+/// setup store 中一个声明的归类：函数为 action，`computed()` 为 getter，
+/// 其余（`ref`、`reactive`、`useStorage` 等）为 state
+fn classify(value: &Node, source: &str) -> Member {
+    let value = unwrap_expression(*value);
+    match value.kind() {
+        "arrow_function" | "function_expression" | "function" => Member::Action,
+        "call_expression" if callee_name(&value, source).as_deref() == Some("computed") => Member::Getter,
+        _ => Member::State,
+    }
+}
+
+/// 函数返回的对象字面量：`() => ({...})`、`() => { return {...} }`、`state() { return {...} }`
+fn returned_object<'a>(function: &Node<'a>) -> Option<Node<'a>> {
+    let body = function.child_by_field_name("body")?;
+    let body = unwrap_expression(body);
+    if body.kind() == "object" {
+        return Some(body);
+    }
+    if body.kind() != "statement_block" {
+        return None;
+    }
+
+    let mut cursor = body.walk();
+    let found = body
+        .named_children(&mut cursor)
+        .filter(|s| s.kind() == "return_statement")
+        .filter_map(|s| s.named_child(0))
+        .map(unwrap_expression)
+        .find(|e| e.kind() == "object");
+    found
+}
+
+/// 去掉括号、`as` / `satisfies` 断言
+fn unwrap_expression(node: Node) -> Node {
+    match node.kind() {
+        "parenthesized_expression" | "as_expression" | "satisfies_expression" | "non_null_expression" => {
+            node.named_child(0).map(unwrap_expression).unwrap_or(node)
+        }
+        _ => node,
+    }
+}
+
+/// 被调用函数名：`foo()` -> `foo`，`Vuex.createStore()` -> `createStore`
+fn callee_name(call: &Node, source: &str) -> Option<String> {
+    let function = call.child_by_field_name("function")?;
+    match function.kind() {
+        "identifier" => Some(text(&function, source).to_string()),
+        "member_expression" => function
+            .child_by_field_name("property")
+            .map(|p| text(&p, source).to_string()),
+        _ => None,
+    }
+}
+
+/// 对象字面量的成员名（键值对、方法、简写属性），展开语法被忽略
+fn object_keys(node: &Node, source: &str) -> Vec<String> {
+    object_pairs(node, source).into_iter().map(|(key, _)| key).collect()
+}
+
+/// 对象字面量的 (成员名, 值节点)；方法与简写属性的值节点为其自身
+fn object_pairs<'a>(node: &Node<'a>, source: &str) -> Vec<(String, Node<'a>)> {
+    let node = unwrap_expression(*node);
+    if node.kind() != "object" {
+        return Vec::new();
+    }
+
+    let mut pairs = Vec::new();
+    let mut cursor = node.walk();
+    for member in node.named_children(&mut cursor) {
+        let entry = match member.kind() {
+            "pair" => member
+                .child_by_field_name("key")
+                .zip(member.child_by_field_name("value"))
+                .map(|(key, value)| (key_name(&key, source), value)),
+            "method_definition" => member
+                .child_by_field_name("name")
+                .map(|name| (key_name(&name, source), member)),
+            "shorthand_property_identifier" => Some((text(&member, source).to_string(), member)),
+            _ => None,
+        };
+        if let Some(entry) = entry {
+            pairs.push(entry);
+        }
+    }
+    pairs
+}
+
+/// 属性名：标识符、字符串或计算属性 `[SET_ITEMS]`
+fn key_name(key: &Node, source: &str) -> String {
+    match key.kind() {
+        "string" => string_value(key, source).unwrap_or_default(),
+        "computed_property_name" => text(key, source).trim_matches(|c| c == '[' || c == ']').to_string(),
+        _ => text(key, source).to_string(),
+    }
+}
+
+fn string_value(node: &Node, source: &str) -> Option<String> {
+    matches!(node.kind(), "string" | "template_string").then(|| {
+        text(node, source)
+            .trim_matches(|c| c == '\'' || c == '"' || c == '`')
+            .to_string()
+    })
+}
+
+fn text<'s>(node: &Node, source: &'s str) -> &'s str {
+    node.utf8_text(source.as_bytes()).unwrap_or("")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn first_call<'a>(node: Node<'a>, kinds: &[&str]) -> Option<Node<'a>> {
+        if kinds.contains(&node.kind()) {
+            return Some(node);
+        }
+        let mut cursor = node.walk();
+        let children: Vec<Node<'a>> = node.named_children(&mut cursor).collect();
+        children.into_iter().find_map(|child| first_call(child, kinds))
+    }
+
+    fn parse(source: &str) -> tree_sitter::Tree {
+        let mut parser = tree_sitter::Parser::new();
+        parser
+            .set_language(&tree_sitter_typescript::language_typescript())
+            .unwrap();
+        parser.parse(source, None).unwrap()
+    }
+
+    #[test]
+    fn test_pinia_and_vuex_models() {
+        let options = r#"export const useCartStore = defineStore('cart', {
+  state: () => ({ items: [], loading: false }),
+  getters: { total: (s) => s.items.length, isEmpty() { return true } },
+  actions: { async fetchList() {}, clear() {} },
+});"#;
+        let tree = parse(options);
+        let call = first_call(tree.root_node(), &["call_expression"]).unwrap();
+        let model = pinia_store(&call, options).unwrap();
+        assert_eq!(model.id, "cart");
+        assert_eq!(model.style, StoreStyle::Options);
+        assert_eq!(model.state, vec!["items", "loading"]);
+        assert_eq!(model.getters, vec!["total", "isEmpty"]);
+        assert_eq!(model.actions, vec!["fetchList", "clear"]);
+
+        let setup = r#"export const useUserStore = defineStore('user', () => {
+  const profile = ref(null);
+  const token = useStorage('token', '');
+  const isLogin = computed(() => !!token.value);
+  function login() {}
+  const logout = async () => {};
+  const internal = 1;
+  return { profile, token, isLogin, login, logout };
+});"#;
+        let tree = parse(setup);
+        let call = first_call(tree.root_node(), &["call_expression"]).unwrap();
+        let model = pinia_store(&call, setup).unwrap();
+        assert_eq!(model.style, StoreStyle::Setup);
+        assert_eq!(model.state, vec!["profile", "token"]);
+        assert_eq!(model.getters, vec!["isLogin"]);
+        assert_eq!(model.actions, vec!["login", "logout"]);
+
+        let vuex = r#"export default {
+  namespaced: true,
+  state: () => ({ list: [] }),
+  mutations: { [SET_LIST](state, list) {}, reset(state) {} },
+  actions: { fetch({ commit }) {} },
+};"#;
+        let tree = parse(vuex);
+        let object = first_call(tree.root_node(), &["object"]).unwrap();
+        let model = vuex_module(&object, vuex).unwrap();
+        assert!(model.namespaced);
+        assert_eq!(model.mutations, vec!["SET_LIST", "reset"]);
+        assert_eq!(model.actions, vec!["fetch"]);
+
+        let root = "export default createStore({ state: { ready: false }, modules: { cart, user } });";
+        let tree = parse(root);
+        let call = first_call(tree.root_node(), &["call_expression"]).unwrap();
+        let model = vuex_store(&call, root).unwrap();
+        assert_eq!(model.state, vec!["ready"]);
+        assert_eq!(model.modules, vec!["cart", "user"]);
+    }
+}
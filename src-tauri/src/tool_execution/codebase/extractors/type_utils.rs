@@ -65,6 +65,50 @@ impl TypeInfo {
     pub fn variable() -> Self {
         Self::new("variable", "Variable")
     }
+
+    pub fn interface() -> Self {
+        Self::new("interface", "Interface")
+    }
+
+    pub fn type_alias() -> Self {
+        Self::new("type", "Type")
+    }
+
+    pub fn enumeration() -> Self {
+        Self::new("enum", "Enum")
+    }
+
+    pub fn namespace() -> Self {
+        Self::new("namespace", "Namespace")
+    }
+
+    pub fn method() -> Self {
+        Self::new("method", "Method")
+    }
+
+    pub fn accessor() -> Self {
+        Self::new("accessor", "Accessor")
+    }
+
+    pub fn property() -> Self {
+        Self::new("property", "Property")
+    }
+
+    pub fn composable() -> Self {
+        Self::new("composable", "Composable")
+    }
+
+    pub fn pinia_store() -> Self {
+        Self::new("pinia_store", "Store")
+    }
+
+    pub fn vuex_store() -> Self {
+        Self::new("vuex_store", "VuexStore")
+    }
+
+    pub fn vuex_module() -> Self {
+        Self::new("vuex_module", "VuexModule")
+    }
 }
 
 pub struct TypeUtils;
@@ -1,12 +1,15 @@
 //! TypeScript/TSX 代码提取器
 //! 
-//! 使用 tree-sitter 解析 TypeScript 和 TSX 文件，提取函数、类、变量等实体
+//! 使用 tree-sitter 解析 TypeScript 和 TSX 文件，提取函数、类、变量、
+//! 接口、类型别名、枚举和命名空间等实体
 
 use std::fs;
 use std::path::Path;
-use tree_sitter::{Parser, Language, Node};
+use tree_sitter::{Parser, Language, Node, Tree};
+use super::details;
+use super::store;
 use super::type_utils::{TypeUtils, TypeInfo};
-use super::{CodeEntity, LocationInfo};
+use super::{CodeEntity, LocationInfo, StoreModel};
 
 /// TypeScript/TSX 提取器
 pub struct TypeScriptExtractor {
@@ -16,12 +19,18 @@ pub struct TypeScriptExtractor {
 impl TypeScriptExtractor {
     /// 创建新的 TypeScript 提取器
     pub fn new(is_tsx: bool) -> Result<Self, Box<dyn std::error::Error>> {
-        let mut parser = Parser::new();
         let language = if is_tsx {
             tree_sitter_typescript::language_tsx()
         } else {
             tree_sitter_typescript::language_typescript()
         };
+        
+        Self::with_language(language)
+    }
+    
+    /// 使用指定的 tree-sitter 语法创建提取器（JavaScript 与 TS 共用同一套节点处理逻辑）
+    pub(super) fn with_language(language: Language) -> Result<Self, Box<dyn std::error::Error>> {
+        let mut parser = Parser::new();
         parser.set_language(&language)?;
         
         Ok(Self { parser })
@@ -35,26 +44,41 @@ impl TypeScriptExtractor {
     ) -> Result<Vec<CodeEntity>, Box<dyn std::error::Error>> {
         let content = fs::read_to_string(file_path)?;
         let is_jsx_context = file_path.ends_with(".tsx") || file_path.ends_with(".jsx");
-        
-        let tree = self.parser.parse(&content, None)
-            .ok_or("Failed to parse file")?;
-        
-        let mut entities = Vec::new();
         let relative_path = Path::new(file_path)
             .strip_prefix(root_dir)
             .unwrap_or(Path::new(file_path))
             .to_string_lossy()
             .to_string();
         
-        // 遍历根节点的子节点
-        let root_node = tree.root_node();
+        let tree = self.parse(&content)?;
+        
+        let mut entities = Vec::new();
+        self.extract_from_root(&tree.root_node(), &content, &relative_path, is_jsx_context, &mut entities)?;
+        
+        Ok(entities)
+    }
+    
+    /// 解析源码
+    pub(super) fn parse(&mut self, content: &str) -> Result<Tree, Box<dyn std::error::Error>> {
+        Ok(self.parser.parse(content, None).ok_or("Failed to parse file")?)
+    }
+    
+    /// 遍历根节点的子节点，提取顶层声明
+    pub(super) fn extract_from_root(
+        &self,
+        root_node: &Node,
+        content: &str,
+        file_path: &str,
+        is_jsx_context: bool,
+        entities: &mut Vec<CodeEntity>,
+    ) -> Result<(), Box<dyn std::error::Error>> {
         let mut cursor = root_node.walk();
         
         for child in root_node.children(&mut cursor) {
-            self.extract_from_node(&child, &content, &relative_path, is_jsx_context, &mut entities)?;
+            self.extract_from_node(&child, content, file_path, is_jsx_context, entities)?;
         }
         
-        Ok(entities)
+        Ok(())
     }
     
     /// 从 AST 节点提取实体
@@ -78,13 +102,23 @@ impl TypeScriptExtractor {
                 self.handle_function_declaration(node, content, file_path, is_jsx_context, false, entities)?;
             }
             // 类声明
-            "class_declaration" => {
+            "class_declaration" | "abstract_class_declaration" => {
                 self.handle_class_declaration(node, content, file_path, is_jsx_context, false, entities)?;
             }
-            // 词法声明（const/let/var）
-            "lexical_declaration" => {
+            // 词法声明（const/let）与 var 声明
+            "lexical_declaration" | "variable_declaration" => {
                 self.handle_lexical_declaration(node, content, file_path, is_jsx_context, false, entities)?;
             }
+            // declare module 'x' / declare namespace X / declare global
+            "ambient_declaration" => {
+                self.handle_ambient_declaration(node, content, file_path, is_jsx_context, entities)?;
+            }
+            // 顶层 `namespace X {}` 被解析为表达式语句
+            "expression_statement" => {
+                if let Some(module) = node.named_child(0).filter(|n| n.kind() == "internal_module") {
+                    self.handle_namespace_declaration(&module, content, file_path, is_jsx_context, false, entities)?;
+                }
+            }
             _ => {}
         }
         
@@ -104,15 +138,44 @@ impl TypeScriptExtractor {
         
         for child in node.children(&mut cursor) {
             match child.kind() {
-                "function_declaration" => {
+                "function_declaration" | "function_signature" => {
                     self.handle_function_declaration(&child, content, file_path, is_jsx_context, true, entities)?;
                 }
-                "class_declaration" => {
+                "class_declaration" | "abstract_class_declaration" => {
                     self.handle_class_declaration(&child, content, file_path, is_jsx_context, true, entities)?;
                 }
-                "lexical_declaration" => {
+                "lexical_declaration" | "variable_declaration" => {
                     self.handle_lexical_declaration(&child, content, file_path, is_jsx_context, true, entities)?;
                 }
+                "interface_declaration" => {
+                    self.handle_type_declaration(&child, content, file_path, TypeInfo::interface(), entities)?;
+                }
+                "type_alias_declaration" => {
+                    self.handle_type_declaration(&child, content, file_path, TypeInfo::type_alias(), entities)?;
+                }
+                "enum_declaration" => {
+                    self.handle_type_declaration(&child, content, file_path, TypeInfo::enumeration(), entities)?;
+                }
+                "internal_module" | "module" => {
+                    self.handle_namespace_declaration(&child, content, file_path, is_jsx_context, true, entities)?;
+                }
+                "ambient_declaration" => {
+                    self.handle_ambient_declaration(&child, content, file_path, is_jsx_context, entities)?;
+                }
+                // `export default defineStore(...)` / `export default { namespaced: true, ... }`
+                _ if node.child_by_field_name("value").map(|v| v.id()) == Some(child.id()) => {
+                    let path = Path::new(file_path);
+                    let stem = path.file_stem().map(|s| s.to_string_lossy().to_string()).unwrap_or_default();
+                    let name = if stem == "index" {
+                        path.parent()
+                            .and_then(|p| p.file_name())
+                            .map(|s| s.to_string_lossy().to_string())
+                            .unwrap_or(stem)
+                    } else {
+                        stem
+                    };
+                    Self::push_store_entity(&child, node, content, file_path, &name, None, true, entities);
+                }
                 _ => {}
             }
         }
@@ -121,7 +184,7 @@ impl TypeScriptExtractor {
     }
     
     /// 处理函数声明
-    fn handle_function_declaration(
+    pub(super) fn handle_function_declaration(
         &self,
         node: &Node,
         content: &str,
@@ -164,9 +227,18 @@ impl TypeScriptExtractor {
         } else {
             func_name
         };
+        let id = format!("{}:{}", type_info.id_prefix, final_name);
         
-        entities.push(CodeEntity {
-            id: format!("{}:{}", type_info.id_prefix, final_name),
+        // 重载签名与实现合并为一个实体
+        if let Some(previous) = entities.last_mut() {
+            if previous.id == id && previous.file == file_path {
+                previous.loc.end_line = node.end_position().row + 1;
+                return Ok(());
+            }
+        }
+        
+        let mut entity = CodeEntity {
+            id,
             entity_type: type_info.entity_type,
             file: file_path.to_string(),
             loc: LocationInfo {
@@ -174,13 +246,16 @@ impl TypeScriptExtractor {
                 end_line: node.end_position().row + 1,
             },
             raw_name: func_name.to_string(),
-        });
+            ..Default::default()
+        };
+        details::annotate(&mut entity, node, content, is_exported);
+        entities.push(entity);
         
         Ok(())
     }
     
     /// 处理类声明
-    fn handle_class_declaration(
+    pub(super) fn handle_class_declaration(
         &self,
         node: &Node,
         content: &str,
@@ -220,8 +295,9 @@ impl TypeScriptExtractor {
             class_name
         };
         
-        entities.push(CodeEntity {
-            id: format!("{}:{}", type_info.id_prefix, final_name),
+        let class_id = format!("{}:{}", type_info.id_prefix, final_name);
+        let mut entity = CodeEntity {
+            id: class_id.clone(),
             entity_type: type_info.entity_type,
             file: file_path.to_string(),
             loc: LocationInfo {
@@ -229,11 +305,238 @@ impl TypeScriptExtractor {
                 end_line: node.end_position().row + 1,
             },
             raw_name: class_name.to_string(),
-        });
+            ..Default::default()
+        };
+        details::annotate(&mut entity, node, content, is_exported);
+        entities.push(entity);
+        
+        self.handle_class_members(node, content, file_path, final_name, &class_id, entities)
+    }
+    
+    /// 提取类成员：方法、访问器、静态字段以及以函数初始化的实例字段
+    ///
+    /// 成员 ID 为 `Method:Class.member`，`parent` 指向类实体
+    pub(super) fn handle_class_members(
+        &self,
+        class_node: &Node,
+        content: &str,
+        file_path: &str,
+        class_name: &str,
+        class_id: &str,
+        entities: &mut Vec<CodeEntity>,
+    ) -> Result<(), Box<dyn std::error::Error>> {
+        let body = match class_node.child_by_field_name("body") {
+            Some(body) => body,
+            None => return Ok(()),
+        };
+        
+        let mut cursor = body.walk();
+        for member in body.named_children(&mut cursor) {
+            let mut token_cursor = member.walk();
+            let tokens: Vec<&str> = member.children(&mut token_cursor).map(|c| c.kind()).collect();
+            let is_static = tokens.contains(&"static");
+            
+            let type_info = match member.kind() {
+                "method_definition" | "method_signature" | "abstract_method_signature" => {
+                    if tokens.contains(&"get") || tokens.contains(&"set") {
+                        TypeInfo::accessor()
+                    } else {
+                        TypeInfo::method()
+                    }
+                }
+                // TS 为 public_field_definition，JS 为 field_definition
+                "public_field_definition" | "field_definition" => {
+                    let is_function = member
+                        .child_by_field_name("value")
+                        .map(|v| matches!(v.kind(), "arrow_function" | "function_expression" | "function"))
+                        .unwrap_or(false);
+                    if is_function {
+                        TypeInfo::method()
+                    } else if is_static {
+                        TypeInfo::property()
+                    } else {
+                        continue;
+                    }
+                }
+                _ => continue,
+            };
+            
+            let name = match member
+                .child_by_field_name("name")
+                .or_else(|| member.child_by_field_name("property"))
+            {
+                Some(name) => name.utf8_text(content.as_bytes())?,
+                None => continue,
+            };
+            let id = format!("{}:{}.{}", type_info.id_prefix, class_name, name);
+            let start_line = member.start_position().row + 1;
+            let end_line = member.end_position().row + 1;
+            
+            // 重载签名与 getter/setter 对合并为一个实体
+            if let Some(existing) = entities
+                .iter_mut()
+                .rev()
+                .take_while(|e| e.id != class_id)
+                .find(|e| e.id == id)
+            {
+                existing.loc.start_line = existing.loc.start_line.min(start_line);
+                existing.loc.end_line = existing.loc.end_line.max(end_line);
+                continue;
+            }
+            
+            let mut entity = CodeEntity {
+                id,
+                entity_type: type_info.entity_type,
+                file: file_path.to_string(),
+                loc: LocationInfo::with_range(start_line, end_line),
+                raw_name: name.to_string(),
+                parent: Some(class_id.to_string()),
+                ..Default::default()
+            };
+            details::annotate(&mut entity, &member, content, false);
+            entities.push(entity);
+        }
+        
+        Ok(())
+    }
+    
+    /// 处理接口、类型别名、枚举声明（按名称记录为独立实体）
+    fn handle_type_declaration(
+        &self,
+        node: &Node,
+        content: &str,
+        file_path: &str,
+        type_info: TypeInfo,
+        entities: &mut Vec<CodeEntity>,
+    ) -> Result<(), Box<dyn std::error::Error>> {
+        let name = match node.child_by_field_name("name") {
+            Some(name) => name.utf8_text(content.as_bytes())?,
+            None => return Ok(()),
+        };
+        
+        let mut entity = CodeEntity {
+            id: format!("{}:{}", type_info.id_prefix, name),
+            entity_type: type_info.entity_type,
+            file: file_path.to_string(),
+            loc: LocationInfo {
+                start_line: node.start_position().row + 1,
+                end_line: node.end_position().row + 1,
+            },
+            raw_name: name.to_string(),
+            ..Default::default()
+        };
+        details::annotate(&mut entity, node, content, true);
+        entities.push(entity);
         
         Ok(())
     }
     
+    /// 处理 `namespace X {}` / `module 'x' {}`
+    ///
+    /// 命名空间本身记录为实体，其内部导出的声明按顶层规则继续提取
+    fn handle_namespace_declaration(
+        &self,
+        node: &Node,
+        content: &str,
+        file_path: &str,
+        is_jsx_context: bool,
+        is_exported: bool,
+        entities: &mut Vec<CodeEntity>,
+    ) -> Result<(), Box<dyn std::error::Error>> {
+        if is_exported {
+            // declare module 'foo' 的名称是字符串字面量
+            let name = match node.child_by_field_name("name") {
+                Some(name) => name
+                    .utf8_text(content.as_bytes())?
+                    .trim_matches(|c| c == '\'' || c == '"'),
+                None => return Ok(()),
+            };
+            let type_info = TypeInfo::namespace();
+            
+            let mut entity = CodeEntity {
+                id: format!("{}:{}", type_info.id_prefix, name),
+                entity_type: type_info.entity_type,
+                file: file_path.to_string(),
+                loc: LocationInfo {
+                    start_line: node.start_position().row + 1,
+                    end_line: node.end_position().row + 1,
+                },
+                raw_name: name.to_string(),
+                ..Default::default()
+            };
+            details::annotate(&mut entity, node, content, true);
+            entities.push(entity);
+        }
+        
+        if let Some(body) = node.child_by_field_name("body") {
+            self.extract_from_root(&body, content, file_path, is_jsx_context, entities)?;
+        }
+        
+        Ok(())
+    }
+    
+    /// 处理 `declare ...` 环境声明（全局可见，视为已导出）
+    fn handle_ambient_declaration(
+        &self,
+        node: &Node,
+        content: &str,
+        file_path: &str,
+        is_jsx_context: bool,
+        entities: &mut Vec<CodeEntity>,
+    ) -> Result<(), Box<dyn std::error::Error>> {
+        let mut cursor = node.walk();
+        
+        for child in node.named_children(&mut cursor) {
+            if child.kind() == "statement_block" {
+                // declare global { ... }：块内的声明同样是全局的环境声明
+                let mut inner = child.walk();
+                for statement in child.named_children(&mut inner) {
+                    if !self.handle_ambient_member(&statement, content, file_path, is_jsx_context, entities)? {
+                        self.extract_from_node(&statement, content, file_path, is_jsx_context, entities)?;
+                    }
+                }
+            } else {
+                self.handle_ambient_member(&child, content, file_path, is_jsx_context, entities)?;
+            }
+        }
+        
+        Ok(())
+    }
+    
+    /// 处理单个环境声明，返回是否识别
+    fn handle_ambient_member(
+        &self,
+        node: &Node,
+        content: &str,
+        file_path: &str,
+        is_jsx_context: bool,
+        entities: &mut Vec<CodeEntity>,
+    ) -> Result<bool, Box<dyn std::error::Error>> {
+        match node.kind() {
+            "internal_module" | "module" => {
+                self.handle_namespace_declaration(node, content, file_path, is_jsx_context, true, entities)?;
+            }
+            "interface_declaration" => {
+                self.handle_type_declaration(node, content, file_path, TypeInfo::interface(), entities)?;
+            }
+            "type_alias_declaration" => {
+                self.handle_type_declaration(node, content, file_path, TypeInfo::type_alias(), entities)?;
+            }
+            "enum_declaration" => {
+                self.handle_type_declaration(node, content, file_path, TypeInfo::enumeration(), entities)?;
+            }
+            "class_declaration" | "abstract_class_declaration" => {
+                self.handle_class_declaration(node, content, file_path, is_jsx_context, true, entities)?;
+            }
+            "function_signature" => {
+                self.handle_function_declaration(node, content, file_path, is_jsx_context, true, entities)?;
+            }
+            _ => return Ok(false),
+        }
+        
+        Ok(true)
+    }
+    
     /// 处理词法声明（const/let/var）
     fn handle_lexical_declaration(
         &self,
@@ -245,6 +548,14 @@ impl TypeScriptExtractor {
         entities: &mut Vec<CodeEntity>,
     ) -> Result<(), Box<dyn std::error::Error>> {
         if !is_exported {
+            // 未导出的 store（如 `const store = createStore({...}); export default store`）仍单独记录
+            let mut cursor = node.walk();
+            for declarator in node.children(&mut cursor).filter(|c| c.kind() == "variable_declarator") {
+                if let (Some(name), Some(value)) = (declarator.child_by_field_name("name"), declarator.child_by_field_name("value")) {
+                    let name = name.utf8_text(content.as_bytes())?;
+                    Self::push_store_entity(&value, node, content, file_path, name, None, false, entities);
+                }
+            }
             return Ok(());
         }
         
@@ -253,44 +564,131 @@ impl TypeScriptExtractor {
         // 遍历变量声明
         for child in node.children(&mut cursor) {
             if child.kind() == "variable_declarator" {
-                // 获取变量名
-                let name_node = child.child_by_field_name("name");
-                let var_name = if let Some(name) = name_node {
-                    name.utf8_text(content.as_bytes())?
-                } else {
-                    continue;
-                };
-                
-                // 获取初始化器
-                let initializer_node = child.child_by_field_name("value");
-                let initializer = if let Some(init) = initializer_node {
-                    init.utf8_text(content.as_bytes())?
-                } else {
-                    ""
-                };
-                
-                // 判断类型
-                let is_component = TypeUtils::is_component_variable(var_name, initializer, is_jsx_context);
-                let is_function = TypeUtils::is_function_variable(initializer);
-                let is_constant = TypeUtils::is_constant_variable(var_name, initializer);
-                
-                let type_info = TypeUtils::get_entity_type_info(is_component, is_function, is_constant);
-                
-                entities.push(CodeEntity {
-                    id: format!("{}:{}", type_info.id_prefix, var_name),
-                    entity_type: type_info.entity_type,
-                    file: file_path.to_string(),
-                    loc: LocationInfo {
-                        start_line: child.start_position().row + 1,
-                        end_line: child.end_position().row + 1,
-                    },
-                    raw_name: var_name.to_string(),
-                });
+                self.handle_variable_declarator(&child, content, file_path, is_jsx_context, entities)?;
             }
         }
         
         Ok(())
     }
+    
+    /// 处理单个变量声明（`name = initializer`）
+    pub(super) fn handle_variable_declarator(
+        &self,
+        node: &Node,
+        content: &str,
+        file_path: &str,
+        is_jsx_context: bool,
+        entities: &mut Vec<CodeEntity>,
+    ) -> Result<(), Box<dyn std::error::Error>> {
+        // 获取变量名
+        let var_name = match node.child_by_field_name("name") {
+            Some(name) => name.utf8_text(content.as_bytes())?,
+            None => return Ok(()),
+        };
+        
+        // 获取初始化器
+        let initializer = match node.child_by_field_name("value") {
+            Some(init) => init.utf8_text(content.as_bytes())?,
+            None => "",
+        };
+        
+        self.push_variable_entity(var_name, initializer, node, content, file_path, is_jsx_context, entities);
+        
+        // `export const useCartStore = defineStore(...)`：store 作为变量的子实体
+        if let Some(value) = node.child_by_field_name("value") {
+            let parent = entities.last().map(|e| e.id.clone());
+            Self::push_store_entity(&value, node, content, file_path, var_name, parent.as_deref(), true, entities);
+        }
+        
+        Ok(())
+    }
+    
+    /// 识别 Pinia / Vuex store 定义并记录为实体
+    ///
+    /// `name` 为声明的变量名（默认导出时为文件名），Vuex 模块名由它推导：`cartModule` -> `cart`
+    #[allow(clippy::too_many_arguments)]
+    pub(super) fn push_store_entity(
+        value: &Node,
+        loc_node: &Node,
+        content: &str,
+        file_path: &str,
+        name: &str,
+        parent: Option<&str>,
+        is_exported: bool,
+        entities: &mut Vec<CodeEntity>,
+    ) {
+        let (type_info, model): (TypeInfo, StoreModel) = if let Some(model) = store::pinia_store(value, content) {
+            (TypeInfo::pinia_store(), model)
+        } else if let Some(model) = store::vuex_store(value, content) {
+            (TypeInfo::vuex_store(), StoreModel { id: "root".to_string(), ..model })
+        } else if let Some(model) = store::vuex_module(value, content) {
+            let module_name = name
+                .strip_suffix("Module")
+                .or_else(|| name.strip_suffix("Store"))
+                .filter(|n| !n.is_empty())
+                .unwrap_or(name);
+            let mut chars = module_name.chars();
+            let module_name: String = chars
+                .next()
+                .map(|c| c.to_lowercase().chain(chars).collect())
+                .unwrap_or_default();
+            (TypeInfo::vuex_module(), StoreModel { id: module_name, ..model })
+        } else {
+            return;
+        };
+        
+        let store_name = if type_info.entity_type == "vuex_store" { name.to_string() } else { model.id.clone() };
+        entities.push(CodeEntity {
+            id: format!("{}:{}", type_info.id_prefix, store_name),
+            entity_type: type_info.entity_type,
+            file: file_path.to_string(),
+            loc: LocationInfo {
+                start_line: loc_node.start_position().row + 1,
+                end_line: loc_node.end_position().row + 1,
+            },
+            raw_name: store_name,
+            parent: parent.map(|p| p.to_string()),
+            store: Some(model),
+            is_exported,
+            ..Default::default()
+        });
+    }
+    
+    /// 按变量规则（组件/函数/常量/变量）判定类型并记录实体
+    ///
+    /// `loc_node` 决定实体的行范围
+    #[allow(clippy::too_many_arguments)]
+    pub(super) fn push_variable_entity(
+        &self,
+        var_name: &str,
+        initializer: &str,
+        loc_node: &Node,
+        content: &str,
+        file_path: &str,
+        is_jsx_context: bool,
+        entities: &mut Vec<CodeEntity>,
+    ) {
+        // 判断类型
+        let is_component = TypeUtils::is_component_variable(var_name, initializer, is_jsx_context);
+        let is_function = TypeUtils::is_function_variable(initializer);
+        let is_constant = TypeUtils::is_constant_variable(var_name, initializer);
+        
+        let type_info = TypeUtils::get_entity_type_info(is_component, is_function, is_constant);
+        
+        let mut entity = CodeEntity {
+            id: format!("{}:{}", type_info.id_prefix, var_name),
+            entity_type: type_info.entity_type,
+            file: file_path.to_string(),
+            loc: LocationInfo {
+                start_line: loc_node.start_position().row + 1,
+                end_line: loc_node.end_position().row + 1,
+            },
+            raw_name: var_name.to_string(),
+            ..Default::default()
+        };
+        details::annotate(&mut entity, loc_node, content, true);
+        entities.push(entity);
+    }
 }
 
 #[cfg(test)]
@@ -325,6 +723,203 @@ export class UserService {
         fs::remove_file(temp_file).ok();
     }
     
+    #[test]
+    fn test_type_declarations() {
+        let mut extractor = TypeScriptExtractor::new(false).unwrap();
+        
+        let test_code = r#"
+export interface Order {
+    id: string;
+    items: OrderItem[];
+}
+
+export type OrderStatus = 'pending' | 'paid';
+
+export enum RefundReason {
+    Damaged,
+    Lost,
+}
+
+export abstract class BaseRepository<T> {
+    abstract find(id: string): T;
+}
+
+interface InternalState {
+    loading: boolean;
+}
+
+export namespace Fulfillment {
+    export interface Parcel {
+        code: string;
+    }
+}
+
+export function formatAmount(value: number): string;
+export function formatAmount(value: string): string;
+export function formatAmount(value: number | string): string {
+    return String(value);
+}
+
+declare module 'order-sdk' {
+    export function createClient(): void;
+}
+
+declare global {
+    interface Window {
+        __ORDER_CONFIG__: Record<string, string>;
+    }
+    type OrderId = string;
+    enum Channel {
+        Web,
+    }
+}
+        "#;
+        
+        let temp_file = "/tmp/test_typescript_types.ts";
+        fs::write(temp_file, test_code).unwrap();
+        
+        let entities = extractor.extract(temp_file, "/tmp").unwrap();
+        let ids: Vec<&str> = entities.iter().map(|e| e.id.as_str()).collect();
+        
+        assert!(ids.contains(&"Interface:Order"));
+        assert!(ids.contains(&"Type:OrderStatus"));
+        assert!(ids.contains(&"Enum:RefundReason"));
+        assert!(ids.contains(&"Class:BaseRepository"));
+        assert!(ids.contains(&"Namespace:Fulfillment"));
+        assert!(ids.contains(&"Interface:Parcel"));
+        assert!(ids.contains(&"Namespace:order-sdk"));
+        assert!(ids.contains(&"Function:createClient"));
+        assert_eq!(ids.iter().filter(|id| **id == "Function:formatAmount").count(), 1);
+        // declare global 中的全局类型增强
+        assert!(ids.contains(&"Interface:Window"));
+        assert!(ids.contains(&"Type:OrderId"));
+        assert!(ids.contains(&"Enum:Channel"));
+        // 未导出的类型不作为实体
+        assert!(!ids.contains(&"Interface:InternalState"));
+        
+        let order = entities.iter().find(|e| e.id == "Interface:Order").unwrap();
+        assert_eq!(order.entity_type, "interface");
+        assert_eq!(order.loc.start_line, 2);
+        assert_eq!(order.loc.end_line, 5);
+        
+        fs::remove_file(temp_file).ok();
+    }
+    
+    #[test]
+    fn test_class_members() {
+        let mut extractor = TypeScriptExtractor::new(false).unwrap();
+        
+        let test_code = r#"
+export class OrderService {
+    static instance?: OrderService;
+    private cache = new Map();
+    
+    constructor(private api: Api) {}
+    
+    refund(id: string): void;
+    refund(id: string, reason: string): void;
+    refund(id: string, reason?: string) {
+        this.api.post(id, reason);
+    }
+    
+    get total() {
+        return this.cache.size;
+    }
+    
+    set total(value: number) {}
+    
+    handleClick = () => {
+        this.refund('1');
+    };
+}
+        "#;
+        
+        let temp_file = "/tmp/test_typescript_members.ts";
+        fs::write(temp_file, test_code).unwrap();
+        
+        let entities = extractor.extract(temp_file, "/tmp").unwrap();
+        let ids: Vec<&str> = entities.iter().map(|e| e.id.as_str()).collect();
+        
+        assert_eq!(
+            ids,
+            vec![
+                "Class:OrderService",
+                "Property:OrderService.instance",
+                "Method:OrderService.constructor",
+                "Method:OrderService.refund",
+                "Accessor:OrderService.total",
+                "Method:OrderService.handleClick",
+            ]
+        );
+        
+        let refund = &entities[3];
+        assert_eq!(refund.parent.as_deref(), Some("Class:OrderService"));
+        assert_eq!(refund.entity_type, "method");
+        assert_eq!((refund.loc.start_line, refund.loc.end_line), (8, 12));
+        
+        let total = &entities[4];
+        assert_eq!((total.loc.start_line, total.loc.end_line), (14, 18));
+        
+        fs::remove_file(temp_file).ok();
+    }
+    
+    #[test]
+    fn test_entity_details() {
+        let mut extractor = TypeScriptExtractor::new(false).unwrap();
+        
+        let test_code = r#"
+/**
+ * 申请退款
+ * @param id 订单号
+ */
+export async function requestRefund<T>(id: string, reason?: string, retries = 3): Promise<T> {
+    return api.post(id);
+}
+
+@Injectable()
+export class RefundService {
+    @Log()
+    private static async *stream(batch: number) {}
+}
+
+export const toCents = (value: number) => Math.round(value * 100);
+        "#;
+        
+        let temp_file = "/tmp/test_typescript_details.ts";
+        fs::write(temp_file, test_code).unwrap();
+        
+        let entities = extractor.extract(temp_file, "/tmp").unwrap();
+        
+        let refund = entities.iter().find(|e| e.raw_name == "requestRefund").unwrap();
+        assert!(refund.is_exported);
+        assert_eq!(refund.modifiers, vec!["async".to_string()]);
+        assert_eq!(refund.doc_comment.as_deref(), Some("申请退款\n@param id 订单号"));
+        let signature = refund.signature.as_ref().unwrap();
+        assert_eq!(
+            signature.display("requestRefund"),
+            "requestRefund<T>(id: string, reason?: string, retries = 3): Promise<T>"
+        );
+        assert!(signature.params[1].optional);
+        
+        let service = entities.iter().find(|e| e.raw_name == "RefundService").unwrap();
+        assert_eq!(service.decorators, vec!["@Injectable()".to_string()]);
+        
+        let stream = entities.iter().find(|e| e.raw_name == "stream").unwrap();
+        assert!(!stream.is_exported);
+        assert_eq!(stream.decorators, vec!["@Log()".to_string()]);
+        for modifier in ["private", "static", "async", "generator"] {
+            assert!(stream.modifiers.contains(&modifier.to_string()), "{}", modifier);
+        }
+        
+        let to_cents = entities.iter().find(|e| e.raw_name == "toCents").unwrap();
+        assert_eq!(
+            to_cents.signature.as_ref().unwrap().display("toCents"),
+            "toCents(value: number)"
+        );
+        
+        fs::remove_file(temp_file).ok();
+    }
+    
     #[test]
     fn test_tsx_extractor() {
         let mut extractor = TypeScriptExtractor::new(true).unwrap();
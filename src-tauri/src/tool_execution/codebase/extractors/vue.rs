@@ -1,169 +1,698 @@
+//! Vue 单文件组件提取器
+//!
+//! 使用 tree-sitter 解析 SFC 中的 `<script>` 与 `<script setup>` 块：
+//! - 组件实体覆盖整个 SFC，记录 props/emits/expose 及 Options API 成员
+//! - setup 中的顶层函数、导出的 composable 与 Pinia store 作为子实体
+
 use regex::Regex;
 use std::fs;
 use std::path::Path;
-use super::{CodeEntity, LocationInfo};
+use tree_sitter::{Node, Parser, Tree};
+use super::details;
+use super::store;
+use super::type_utils::TypeInfo;
+use super::{CodeEntity, ComponentApi, LocationInfo, PropInfo};
+
+/// 函数类节点
+const FUNCTION_KINDS: [&str; 4] = ["arrow_function", "function_expression", "function", "generator_function"];
 
 /// Vue 文件提取器
 pub struct VueExtractor {
-    script_setup_regex: Regex,
     script_regex: Regex,
-    export_default_regex: Regex,
-    define_component_regex: Regex,
-    composable_regex: Regex,
-    pinia_store_regex: Regex,
+    lang_regex: Regex,
+    composable_name_regex: Regex,
+    ts_parser: Parser,
+    tsx_parser: Parser,
+}
+
+/// SFC 中的一个 script 块
+struct ScriptBlock<'a> {
+    /// 是否为 `<script setup>`
+    is_setup: bool,
+    /// 是否使用 TSX/JSX 语法
+    is_tsx: bool,
+    /// 脚本源码
+    source: &'a str,
+    /// 脚本首行在 SFC 中的行偏移（0-based）
+    line_offset: usize,
+}
+
+impl ScriptBlock<'_> {
+    /// 将节点位置换算为 SFC 中的行范围
+    fn loc(&self, node: &Node) -> LocationInfo {
+        LocationInfo::with_range(
+            self.line_offset + node.start_position().row + 1,
+            self.line_offset + node.end_position().row + 1,
+        )
+    }
 }
 
 impl VueExtractor {
     /// 创建新的 Vue 提取器
     pub fn new() -> Self {
+        let mut ts_parser = Parser::new();
+        ts_parser
+            .set_language(&tree_sitter_typescript::language_typescript())
+            .expect("加载 TypeScript 语法失败");
+        let mut tsx_parser = Parser::new();
+        tsx_parser
+            .set_language(&tree_sitter_typescript::language_tsx())
+            .expect("加载 TSX 语法失败");
+
         Self {
-            script_setup_regex: Regex::new(r"<script\s+setup[^>]*>([\s\S]*?)</script>").unwrap(),
-            script_regex: Regex::new(r"<script[^>]*>([\s\S]*?)</script>").unwrap(),
-            export_default_regex: Regex::new(r"export\s+default\s+").unwrap(),
-            define_component_regex: Regex::new(r"defineComponent\s*\(").unwrap(),
-            composable_regex: Regex::new(r"export\s+(const|function)\s+use[A-Z]\w+").unwrap(),
-            pinia_store_regex: Regex::new(r#"defineStore\s*\(\s*['"](\w+)['"]"#).unwrap(),
+            script_regex: Regex::new(r"<script\b([^>]*)>([\s\S]*?)</script>").unwrap(),
+            lang_regex: Regex::new(r#"lang\s*=\s*["']?(\w+)"#).unwrap(),
+            composable_name_regex: Regex::new(r"^use[A-Z0-9]").unwrap(),
+            ts_parser,
+            tsx_parser,
         }
     }
 
     /// 提取 Vue 文件中的实体
     pub fn extract(
-        &self,
+        &mut self,
         file_path: &str,
         root_dir: &str,
     ) -> Result<Vec<CodeEntity>, Box<dyn std::error::Error>> {
         let content = fs::read_to_string(file_path)?;
-        let mut entities = Vec::new();
 
         let relative_path = Path::new(file_path)
             .strip_prefix(root_dir)
             .unwrap_or(Path::new(file_path))
             .to_string_lossy()
             .to_string();
+        let file_name = Path::new(file_path)
+            .file_stem()
+            .unwrap()
+            .to_string_lossy()
+            .to_string();
+        let component_id = format!("{}:{}", TypeInfo::component().id_prefix, file_name);
 
-        // 1. 检查 <script setup>
-        if let Some(captures) = self.script_setup_regex.captures(&content) {
-            let script_content = captures.get(1).unwrap().as_str();
-            let start_line = content[..captures.get(0).unwrap().start()]
-                .lines()
-                .count();
+        let mut api = ComponentApi::default();
+        let mut children = Vec::new();
+        let mut has_setup = false;
+        let mut options_kind = None;
 
-            // script setup 是组件的特殊情况
-            let file_name = Path::new(file_path)
-                .file_stem()
-                .unwrap()
-                .to_string_lossy();
+        for block in self.script_blocks(&content) {
+            let tree = self.parse(&block)?;
+            let root = tree.root_node();
 
-            entities.push(CodeEntity {
-                id: format!("Component:{}", file_name),
-                entity_type: "component".to_string(),
-                file: relative_path.clone(),
-                loc: LocationInfo::new(start_line),
-                raw_name: "setup".to_string(),
-            });
+            if block.is_setup {
+                has_setup = true;
+                Self::collect_macros(&root, block.source, &mut api);
+            } else if let Some((kind, options)) = Self::find_component_options(&root, block.source) {
+                options_kind = Some(kind);
+                Self::collect_options(&options, block.source, &mut api);
+            }
+
+            self.collect_functions(&root, &block, &relative_path, &component_id, &mut children);
+            Self::collect_stores(&root, &block, &relative_path, &component_id, &mut children);
+        }
+
+        // 既没有组件脚本也没有模板时，文件只包含子实体（或为空）
+        let raw_name = if has_setup {
+            "setup"
+        } else if let Some(kind) = options_kind {
+            kind
+        } else if content.contains("<template") {
+            "template"
+        } else {
+            return Ok(children);
+        };
+
+        let type_info = TypeInfo::component();
+        let mut entities = vec![CodeEntity {
+            id: component_id,
+            entity_type: type_info.entity_type,
+            file: relative_path,
+            loc: LocationInfo::with_range(1, content.lines().count().max(1)),
+            raw_name: raw_name.to_string(),
+            component: if api.is_empty() { None } else { Some(api) },
+            is_exported: true,
+            ..Default::default()
+        }];
+        entities.extend(children);
 
-            // 检查 Pinia store
-            self.extract_pinia_stores(&script_content, &relative_path, start_line, &mut entities);
+        Ok(entities)
+    }
+
+    /// 切分 SFC 中的 script 块
+    fn script_blocks<'a>(&self, content: &'a str) -> Vec<ScriptBlock<'a>> {
+        self.script_regex
+            .captures_iter(content)
+            .filter_map(|captures| {
+                let attrs = captures.get(1)?.as_str();
+                let body = captures.get(2)?;
+                let lang = self
+                    .lang_regex
+                    .captures(attrs)
+                    .and_then(|c| c.get(1))
+                    .map(|m| m.as_str())
+                    .unwrap_or("js");
+
+                Some(ScriptBlock {
+                    is_setup: attrs.split_whitespace().any(|attr| attr == "setup"),
+                    is_tsx: lang == "tsx" || lang == "jsx",
+                    source: body.as_str(),
+                    line_offset: content[..body.start()].matches('\n').count(),
+                })
+            })
+            .collect()
+    }
+
+    /// 解析 script 块（JS 按 TS 语法解析）
+    fn parse(&mut self, block: &ScriptBlock) -> Result<Tree, Box<dyn std::error::Error>> {
+        let parser = if block.is_tsx {
+            &mut self.tsx_parser
+        } else {
+            &mut self.ts_parser
+        };
+
+        Ok(parser.parse(block.source, None).ok_or("Failed to parse script block")?)
+    }
+
+    // ========== <script setup> 编译宏 ==========
 
-            return Ok(entities);
+    /// 收集 defineProps / withDefaults / defineEmits / defineExpose / defineModel
+    fn collect_macros(root: &Node, source: &str, api: &mut ComponentApi) {
+        let mut calls = Vec::new();
+        Self::find_calls(root, source, &mut calls);
+
+        for (name, call) in &calls {
+            let arguments = call.child_by_field_name("arguments");
+            let first_arg = arguments.and_then(|a| a.named_child(0));
+            let type_arg = call
+                .child_by_field_name("type_arguments")
+                .and_then(|t| t.named_child(0));
+
+            match name.as_str() {
+                "defineProps" => {
+                    if let Some(type_node) = type_arg {
+                        Self::props_from_type(&type_node, root, source, api);
+                    } else if let Some(arg) = first_arg {
+                        Self::props_from_runtime(&arg, source, api);
+                    }
+                }
+                "defineEmits" => {
+                    if let Some(type_node) = type_arg {
+                        Self::emits_from_type(&type_node, root, source, api);
+                    } else if let Some(arg) = first_arg {
+                        api.emits.extend(Self::names_of(&arg, source));
+                    }
+                }
+                "defineExpose" => {
+                    if let Some(arg) = first_arg {
+                        api.exposed.extend(Self::object_keys(&arg, source));
+                    }
+                }
+                "defineModel" => {
+                    let model_name = first_arg
+                        .and_then(|arg| Self::string_value(&arg, source))
+                        .unwrap_or_else(|| "modelValue".to_string());
+                    api.props.push(PropInfo {
+                        name: model_name.clone(),
+                        prop_type: type_arg.map(|t| Self::text(&t, source).to_string()),
+                        ..Default::default()
+                    });
+                    api.emits.push(format!("update:{}", model_name));
+                }
+                _ => {}
+            }
         }
 
-        // 2. 检查普通 <script>
-        if let Some(captures) = self.script_regex.captures(&content) {
-            let script_content = captures.get(1).unwrap().as_str();
-            let start_line = content[..captures.get(0).unwrap().start()]
-                .lines()
-                .count();
+        // withDefaults 的默认值在 props 收集完成后回填
+        for (name, call) in &calls {
+            if name != "withDefaults" {
+                continue;
+            }
+            let defaults = call
+                .child_by_field_name("arguments")
+                .and_then(|a| a.named_child(1))
+                .filter(|n| n.kind() == "object");
+            if let Some(defaults) = defaults {
+                for (key, value) in Self::object_pairs(&defaults, source) {
+                    if let Some(prop) = api.props.iter_mut().find(|p| p.name == key) {
+                        prop.default = Some(Self::text(&value, source).to_string());
+                    }
+                }
+            }
+        }
+    }
 
-            // 检查 export default
-            if self.export_default_regex.is_match(script_content) {
-                let file_name = Path::new(file_path)
-                    .file_stem()
-                    .unwrap()
-                    .to_string_lossy();
+    /// 递归查找编译宏与 defineStore 调用（按源码顺序）
+    fn find_calls<'a>(node: &Node<'a>, source: &str, calls: &mut Vec<(String, Node<'a>)>) {
+        if node.kind() == "call_expression" {
+            if let Some(function) = node
+                .child_by_field_name("function")
+                .filter(|f| f.kind() == "identifier")
+            {
+                calls.push((Self::text(&function, source).to_string(), *node));
+            }
+        }
 
-                entities.push(CodeEntity {
-                    id: format!("Component:{}", file_name),
-                    entity_type: "component".to_string(),
-                    file: relative_path.clone(),
-                    loc: LocationInfo::new(start_line),
-                    raw_name: "default".to_string(),
-                });
+        let mut cursor = node.walk();
+        for child in node.named_children(&mut cursor) {
+            Self::find_calls(&child, source, calls);
+        }
+    }
+
+    /// 运行时声明的 props：`['a', 'b']` 或 `{ a: String, b: { type: Number, required: true } }`
+    fn props_from_runtime(node: &Node, source: &str, api: &mut ComponentApi) {
+        match node.kind() {
+            "array" => {
+                for name in Self::names_of(node, source) {
+                    api.props.push(PropInfo {
+                        name,
+                        ..Default::default()
+                    });
+                }
             }
+            "object" => {
+                let mut cursor = node.walk();
+                for member in node.named_children(&mut cursor) {
+                    match member.kind() {
+                        "pair" => {
+                            let (Some(key), Some(value)) =
+                                (member.child_by_field_name("key"), member.child_by_field_name("value"))
+                            else {
+                                continue;
+                            };
+                            let mut prop = PropInfo {
+                                name: Self::key_name(&key, source),
+                                ..Default::default()
+                            };
+                            if value.kind() == "object" {
+                                for (option, option_value) in Self::object_pairs(&value, source) {
+                                    let option_text = Self::text(&option_value, source).to_string();
+                                    match option.as_str() {
+                                        "type" => prop.prop_type = Some(option_text),
+                                        "required" => prop.required = option_text == "true",
+                                        "default" => prop.default = Some(option_text),
+                                        _ => {}
+                                    }
+                                }
+                            } else {
+                                prop.prop_type = Some(Self::text(&value, source).to_string());
+                            }
+                            api.props.push(prop);
+                        }
+                        "shorthand_property_identifier" => api.props.push(PropInfo {
+                            name: Self::text(&member, source).to_string(),
+                            ..Default::default()
+                        }),
+                        _ => {}
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
 
-            // 检查 defineComponent
-            if self.define_component_regex.is_match(script_content) {
-                let file_name = Path::new(file_path)
-                    .file_stem()
-                    .unwrap()
-                    .to_string_lossy();
+    /// 类型声明的 props：`defineProps<{ a: string; b?: number }>()` 或 `defineProps<Props>()`
+    fn props_from_type(type_node: &Node, root: &Node, source: &str, api: &mut ComponentApi) {
+        let Some(members) = Self::resolve_object_type(type_node, root, source) else {
+            return;
+        };
 
-                entities.push(CodeEntity {
-                    id: format!("Component:{}", file_name),
-                    entity_type: "component".to_string(),
-                    file: relative_path.clone(),
-                    loc: LocationInfo::new(start_line),
-                    raw_name: "defineComponent".to_string(),
-                });
+        let mut cursor = members.walk();
+        for member in members.named_children(&mut cursor) {
+            if member.kind() != "property_signature" {
+                continue;
             }
+            let Some(name) = member.child_by_field_name("name") else {
+                continue;
+            };
+            let mut optional_cursor = member.walk();
+            let optional = member
+                .children(&mut optional_cursor)
+                .any(|c| c.kind() == "?");
+
+            api.props.push(PropInfo {
+                name: Self::key_name(&name, source),
+                prop_type: member
+                    .child_by_field_name("type")
+                    .map(|t| Self::text(&t, source).trim_start_matches(':').trim().to_string()),
+                required: !optional,
+                default: None,
+            });
+        }
+    }
 
-            // 检查 composables
-            self.extract_composables(&script_content, &relative_path, start_line, &mut entities);
+    /// 类型声明的 emits：调用签名 `(e: 'change', id: number): void` 或 3.3 的 `change: [id: number]`
+    fn emits_from_type(type_node: &Node, root: &Node, source: &str, api: &mut ComponentApi) {
+        let Some(members) = Self::resolve_object_type(type_node, root, source) else {
+            return;
+        };
 
-            // 检查 Pinia stores
-            self.extract_pinia_stores(&script_content, &relative_path, start_line, &mut entities);
+        let mut cursor = members.walk();
+        for member in members.named_children(&mut cursor) {
+            match member.kind() {
+                "call_signature" => {
+                    let event = member
+                        .child_by_field_name("parameters")
+                        .and_then(|params| params.named_child(0))
+                        .and_then(|param| param.child_by_field_name("type"))
+                        .and_then(|annotation| Self::string_in(&annotation, source));
+                    if let Some(event) = event {
+                        api.emits.push(event);
+                    }
+                }
+                "property_signature" => {
+                    if let Some(name) = member.child_by_field_name("name") {
+                        api.emits.push(Self::key_name(&name, source));
+                    }
+                }
+                _ => {}
+            }
         }
+    }
 
-        Ok(entities)
+    /// 将类型参数解析为对象类型节点，类型名引用同文件的 interface / type 声明
+    fn resolve_object_type<'a>(type_node: &Node<'a>, root: &Node<'a>, source: &str) -> Option<Node<'a>> {
+        match type_node.kind() {
+            "object_type" | "interface_body" => Some(*type_node),
+            "type_identifier" => {
+                let type_name = Self::text(type_node, source);
+                let mut cursor = root.walk();
+                let declaration = root
+                    .named_children(&mut cursor)
+                    .map(|child| {
+                        // export interface Props {...}
+                        if child.kind() == "export_statement" {
+                            child.child_by_field_name("declaration").unwrap_or(child)
+                        } else {
+                            child
+                        }
+                    })
+                    .find(|child| {
+                        matches!(child.kind(), "interface_declaration" | "type_alias_declaration")
+                            && child
+                                .child_by_field_name("name")
+                                .map(|n| Self::text(&n, source) == type_name)
+                                .unwrap_or(false)
+                    })?;
+
+                let body = declaration
+                    .child_by_field_name("body")
+                    .or_else(|| declaration.child_by_field_name("value"))?;
+                Self::resolve_object_type(&body, root, source)
+            }
+            _ => None,
+        }
     }
 
-    /// 提取 Composables
-    fn extract_composables(
+    // ========== Options API ==========
+
+    /// 查找 `export default {...}` 或 `export default defineComponent({...})` 的选项对象
+    fn find_component_options<'a>(root: &Node<'a>, source: &str) -> Option<(&'static str, Node<'a>)> {
+        let mut cursor = root.walk();
+        let export = root.named_children(&mut cursor).find(|child| {
+            child.kind() == "export_statement"
+                && child.child_by_field_name("declaration").is_none()
+                && Self::text(child, source).starts_with("export default")
+        })?;
+
+        let value = export
+            .child_by_field_name("value")
+            .or_else(|| export.named_child(export.named_child_count().checked_sub(1)?))?;
+
+        match value.kind() {
+            "object" => Some(("default", value)),
+            "call_expression" => {
+                let options = value
+                    .child_by_field_name("arguments")
+                    .and_then(|a| a.named_child(0))
+                    .filter(|n| n.kind() == "object");
+                Some(("defineComponent", options.unwrap_or(value)))
+            }
+            _ => Some(("default", value)),
+        }
+    }
+
+    /// 收集 Options API 的 props/emits/expose/data/computed/methods/watch
+    fn collect_options(options: &Node, source: &str, api: &mut ComponentApi) {
+        if options.kind() != "object" {
+            return;
+        }
+
+        let mut cursor = options.walk();
+        for member in options.named_children(&mut cursor) {
+            let (key, value) = match member.kind() {
+                "pair" => (member.child_by_field_name("key"), member.child_by_field_name("value")),
+                // data() {...}
+                "method_definition" => (member.child_by_field_name("name"), Some(member)),
+                _ => continue,
+            };
+            let (Some(key), Some(value)) = (key, value) else {
+                continue;
+            };
+
+            match Self::key_name(&key, source).as_str() {
+                "props" => Self::props_from_runtime(&value, source, api),
+                "emits" => api.emits.extend(Self::names_of(&value, source)),
+                "expose" => api.exposed.extend(Self::names_of(&value, source)),
+                "data" => {
+                    if let Some(object) = Self::returned_object(&value) {
+                        api.data.extend(Self::object_keys(&object, source));
+                    }
+                }
+                "computed" => api.computed.extend(Self::object_keys(&value, source)),
+                "methods" => api.methods.extend(Self::object_keys(&value, source)),
+                "watch" => api.watch.extend(Self::object_keys(&value, source)),
+                _ => {}
+            }
+        }
+    }
+
+    /// 函数返回的对象字面量：`() => ({...})` 或 `function () { return {...} }`
+    fn returned_object<'a>(function: &Node<'a>) -> Option<Node<'a>> {
+        let body = function.child_by_field_name("body")?;
+        match body.kind() {
+            "object" => Some(body),
+            "parenthesized_expression" => body.named_child(0).filter(|n| n.kind() == "object"),
+            "statement_block" => {
+                let mut cursor = body.walk();
+                let statement = body
+                    .named_children(&mut cursor)
+                    .find(|n| n.kind() == "return_statement")?;
+                let value = statement.named_child(0)?;
+                match value.kind() {
+                    "object" => Some(value),
+                    "parenthesized_expression" => value.named_child(0).filter(|n| n.kind() == "object"),
+                    _ => None,
+                }
+            }
+            _ => None,
+        }
+    }
+
+    // ========== 子实体 ==========
+
+    /// 提取顶层函数：setup 块中的全部函数，普通 script 中导出的函数
+    fn collect_functions(
         &self,
-        content: &str,
+        root: &Node,
+        block: &ScriptBlock,
         file: &str,
-        base_line: usize,
+        parent: &str,
         entities: &mut Vec<CodeEntity>,
     ) {
-        for capture in self.composable_regex.captures_iter(content) {
-            if let Some(matched) = capture.get(0) {
-                let line_offset = content[..matched.start()].lines().count();
-                let composable_name = matched.as_str()
-                    .split_whitespace()
-                    .last()
-                    .unwrap_or("unknown");
+        let mut cursor = root.walk();
+        for child in root.named_children(&mut cursor) {
+            let (declaration, is_exported) = match child.kind() {
+                "export_statement" => match child.child_by_field_name("declaration") {
+                    Some(declaration) => (declaration, true),
+                    None => continue,
+                },
+                _ => (child, false),
+            };
+            if !block.is_setup && !is_exported {
+                continue;
+            }
 
-                entities.push(CodeEntity {
-                    id: format!("Composable:{}", composable_name),
-                    entity_type: "composable".to_string(),
-                    file: file.to_string(),
-                    loc: LocationInfo::new(base_line + line_offset),
-                    raw_name: composable_name.to_string(),
-                });
+            match declaration.kind() {
+                "function_declaration" | "generator_function_declaration" => {
+                    self.push_function(&declaration, &child, is_exported, block, file, parent, entities);
+                }
+                "lexical_declaration" | "variable_declaration" => {
+                    let mut inner_cursor = declaration.walk();
+                    for declarator in declaration.named_children(&mut inner_cursor) {
+                        let is_function = declarator
+                            .child_by_field_name("value")
+                            .map(|v| FUNCTION_KINDS.contains(&v.kind()))
+                            .unwrap_or(false);
+                        if is_function {
+                            self.push_function(&declarator, &child, is_exported, block, file, parent, entities);
+                        }
+                    }
+                }
+                _ => {}
             }
         }
     }
 
-    /// 提取 Pinia Stores
-    fn extract_pinia_stores(
+    /// 记录函数子实体（`use` 开头的视为 composable）
+    ///
+    /// `declaration` 为函数声明或变量声明器，`statement` 为其所在的顶层语句（决定行范围）
+    #[allow(clippy::too_many_arguments)]
+    fn push_function(
         &self,
-        content: &str,
+        declaration: &Node,
+        statement: &Node,
+        is_exported: bool,
+        block: &ScriptBlock,
         file: &str,
-        base_line: usize,
+        parent: &str,
         entities: &mut Vec<CodeEntity>,
     ) {
-        for capture in self.pinia_store_regex.captures_iter(content) {
-            if let (Some(matched), Some(store_name)) = (capture.get(0), capture.get(1)) {
-                let line_offset = content[..matched.start()].lines().count();
+        let Some(name) = declaration
+            .child_by_field_name("name")
+            .filter(|n| n.kind() == "identifier")
+            .map(|n| Self::text(&n, block.source))
+        else {
+            return;
+        };
+        let type_info = if self.composable_name_regex.is_match(name) {
+            TypeInfo::composable()
+        } else {
+            TypeInfo::function()
+        };
+
+        let mut entity = CodeEntity {
+            id: format!("{}:{}", type_info.id_prefix, name),
+            entity_type: type_info.entity_type,
+            file: file.to_string(),
+            loc: block.loc(statement),
+            raw_name: name.to_string(),
+            parent: Some(parent.to_string()),
+            ..Default::default()
+        };
+        details::annotate(&mut entity, declaration, block.source, is_exported);
+        entities.push(entity);
+    }
+
+    /// 提取 Pinia Stores（`defineStore('id', ...)`）
+    fn collect_stores(
+        root: &Node,
+        block: &ScriptBlock,
+        file: &str,
+        parent: &str,
+        entities: &mut Vec<CodeEntity>,
+    ) {
+        let mut calls = Vec::new();
+        Self::find_calls(root, block.source, &mut calls);
+
+        for (name, call) in calls {
+            if name != "defineStore" {
+                continue;
+            }
+            let store_name = call
+                .child_by_field_name("arguments")
+                .and_then(|a| a.named_child(0))
+                .and_then(|arg| Self::string_value(&arg, block.source));
+            let Some(store_name) = store_name else {
+                continue;
+            };
+
+            // 行范围取所在的顶层语句
+            let mut statement = call;
+            while let Some(node_parent) = statement.parent() {
+                if node_parent.id() == root.id() {
+                    break;
+                }
+                statement = node_parent;
+            }
+
+            let type_info = TypeInfo::pinia_store();
+            entities.push(CodeEntity {
+                id: format!("{}:{}", type_info.id_prefix, store_name),
+                entity_type: type_info.entity_type,
+                file: file.to_string(),
+                loc: block.loc(&statement),
+                raw_name: store_name,
+                parent: Some(parent.to_string()),
+                store: store::pinia_store(&call, block.source),
+                is_exported: statement.kind() == "export_statement",
+                ..Default::default()
+            });
+        }
+    }
+
+    // ========== 节点工具 ==========
+
+    fn text<'s>(node: &Node, source: &'s str) -> &'s str {
+        node.utf8_text(source.as_bytes()).unwrap_or("")
+    }
+
+    /// 字符串字面量的值
+    fn string_value(node: &Node, source: &str) -> Option<String> {
+        match node.kind() {
+            "string" | "template_string" => Some(
+                Self::text(node, source)
+                    .trim_matches(|c| c == '\'' || c == '"' || c == '`')
+                    .to_string(),
+            ),
+            _ => None,
+        }
+    }
+
+    /// 节点（如类型注解）中第一个字符串字面量的值
+    fn string_in(node: &Node, source: &str) -> Option<String> {
+        if let Some(value) = Self::string_value(node, source) {
+            return Some(value);
+        }
+
+        let mut cursor = node.walk();
+        let children: Vec<_> = node.named_children(&mut cursor).collect();
+        children.iter().find_map(|child| Self::string_in(child, source))
+    }
+
+    /// 对象键名（去除引号）
+    fn key_name(node: &Node, source: &str) -> String {
+        Self::string_value(node, source).unwrap_or_else(|| Self::text(node, source).to_string())
+    }
+
+    /// 对象字面量的键名（属性、简写属性、方法）
+    fn object_keys(node: &Node, source: &str) -> Vec<String> {
+        if node.kind() != "object" {
+            return Vec::new();
+        }
+
+        let mut cursor = node.walk();
+        node.named_children(&mut cursor)
+            .filter_map(|member| match member.kind() {
+                "pair" => member.child_by_field_name("key").map(|k| Self::key_name(&k, source)),
+                "method_definition" => member.child_by_field_name("name").map(|k| Self::key_name(&k, source)),
+                "shorthand_property_identifier" => Some(Self::text(&member, source).to_string()),
+                _ => None,
+            })
+            .collect()
+    }
 
-                entities.push(CodeEntity {
-                    id: format!("Store:{}", store_name.as_str()),
-                    entity_type: "pinia_store".to_string(),
-                    file: file.to_string(),
-                    loc: LocationInfo::new(base_line + line_offset),
-                    raw_name: store_name.as_str().to_string(),
-                });
+    /// 对象字面量的 (键, 值) 对
+    fn object_pairs<'a>(node: &Node<'a>, source: &str) -> Vec<(String, Node<'a>)> {
+        let mut cursor = node.walk();
+        node.named_children(&mut cursor)
+            .filter(|member| member.kind() == "pair")
+            .filter_map(|member| {
+                let key = member.child_by_field_name("key")?;
+                let value = member.child_by_field_name("value")?;
+                Some((Self::key_name(&key, source), value))
+            })
+            .collect()
+    }
+
+    /// 字符串数组的元素或对象的键名：`['a', 'b']` / `{ a: null, b() {} }`
+    fn names_of(node: &Node, source: &str) -> Vec<String> {
+        match node.kind() {
+            "array" => {
+                let mut cursor = node.walk();
+                node.named_children(&mut cursor)
+                    .filter_map(|element| Self::string_value(&element, source))
+                    .collect()
             }
+            "object" => Self::object_keys(node, source),
+            _ => Vec::new(),
         }
     }
 }
@@ -188,11 +717,127 @@ const count = ref(0)
         let temp_file = "/tmp/test_component.vue";
         fs::write(temp_file, content).unwrap();
 
-        let extractor = VueExtractor::new();
+        let mut extractor = VueExtractor::new();
         let entities = extractor.extract(temp_file, "/tmp").unwrap();
 
         assert_eq!(entities.len(), 1);
         assert_eq!(entities[0].entity_type, "component");
         assert_eq!(entities[0].raw_name, "setup");
     }
+
+    #[test]
+    fn test_vue_extractor_setup_macros_and_children() {
+        let content = r#"<template>
+  <button @click="open">{{ title }}</button>
+</template>
+
+<script lang="ts">
+export function useDialogState() {
+  return {}
+}
+</script>
+
+<script setup lang="ts">
+interface Props {
+  title: string
+  size?: number
+}
+
+const props = withDefaults(defineProps<Props>(), { size: 2 })
+const emit = defineEmits<{
+  (e: 'open', id: number): void
+  (e: 'close'): void
+}>()
+
+function open() {
+  emit('open', 1)
+}
+
+const close = () => emit('close')
+
+defineExpose({ open, close })
+</script>
+"#;
+
+        let temp_file = "/tmp/test_sfc_setup.vue";
+        fs::write(temp_file, content).unwrap();
+
+        let mut extractor = VueExtractor::new();
+        let entities = extractor.extract(temp_file, "/tmp").unwrap();
+
+        let component = &entities[0];
+        assert_eq!(component.id, "Component:test_sfc_setup");
+        assert_eq!(component.loc.start_line, 1);
+        assert_eq!(component.loc.end_line, content.lines().count());
+
+        let api = component.component.as_ref().unwrap();
+        let names: Vec<&str> = api.props.iter().map(|p| p.name.as_str()).collect();
+        assert_eq!(names, vec!["title", "size"]);
+        assert!(api.props[0].required);
+        assert_eq!(api.props[1].prop_type.as_deref(), Some("number"));
+        assert_eq!(api.props[1].default.as_deref(), Some("2"));
+        assert_eq!(api.emits, vec!["open".to_string(), "close".to_string()]);
+        assert_eq!(api.exposed, vec!["open".to_string(), "close".to_string()]);
+
+        let composable = entities.iter().find(|e| e.id == "Composable:useDialogState").unwrap();
+        assert_eq!(composable.loc.start_line, 6);
+        assert_eq!(composable.loc.end_line, 8);
+
+        let open = entities.iter().find(|e| e.id == "Function:open").unwrap();
+        assert_eq!(open.parent.as_deref(), Some("Component:test_sfc_setup"));
+        assert_eq!(open.loc.start_line, 23);
+        assert!(entities.iter().any(|e| e.id == "Function:close"));
+
+        fs::remove_file(temp_file).ok();
+    }
+
+    #[test]
+    fn test_vue_extractor_options_api() {
+        let content = r#"<template><div /></template>
+<script>
+import { defineComponent } from 'vue'
+
+export default defineComponent({
+  name: 'OrderList',
+  props: {
+    orders: { type: Array, required: true },
+    pageSize: Number
+  },
+  emits: ['select'],
+  data() {
+    return { loading: false, page: 1 }
+  },
+  computed: {
+    total() { return this.orders.length }
+  },
+  methods: {
+    select(order) { this.$emit('select', order) },
+    refresh: function () {}
+  },
+  watch: { page() {} }
+})
+</script>
+"#;
+
+        let temp_file = "/tmp/test_sfc_options.vue";
+        fs::write(temp_file, content).unwrap();
+
+        let mut extractor = VueExtractor::new();
+        let entities = extractor.extract(temp_file, "/tmp").unwrap();
+
+        assert_eq!(entities.len(), 1);
+        assert_eq!(entities[0].raw_name, "defineComponent");
+
+        let api = entities[0].component.as_ref().unwrap();
+        assert_eq!(api.props.len(), 2);
+        assert!(api.props[0].required);
+        assert_eq!(api.props[1].prop_type.as_deref(), Some("Number"));
+        assert_eq!(api.emits, vec!["select".to_string()]);
+        assert_eq!(api.data, vec!["loading".to_string(), "page".to_string()]);
+        assert_eq!(api.computed, vec!["total".to_string()]);
+        assert_eq!(api.methods, vec!["select".to_string(), "refresh".to_string()]);
+        assert_eq!(api.watch, vec!["page".to_string()]);
+
+        fs::remove_file(temp_file).ok();
+    }
 }
@@ -4,14 +4,23 @@
 //!
 //! 负责扫描目录、识别 workspace 配置、调用提取器提取代码实体
 
-use super::extractors::{CodeEntity, TypeScriptExtractor, VueExtractor};
+use super::entity_id::{assign_canonical_ids, EntityId, PackageResolver};
+use super::extractors::{CodeEntity, JavaScriptExtractor, TypeScriptExtractor, VueExtractor};
+use super::project_config::ProjectConfig;
+pub use super::workspace::WorkspaceInfo;
+use super::scan_manifest::{
+    hash_content, hash_entity_slice, EntityChanges, FileRecord, FileStamp, ScanManifest,
+};
 use anyhow::{Context, Result};
 use chrono::Utc;
+use rayon::prelude::*;
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use std::cell::RefCell;
+use std::collections::BTreeMap;
 use std::fs;
 use std::path::{Path, PathBuf};
-use walkdir::WalkDir;
+use globset::{GlobBuilder, GlobSet, GlobSetBuilder};
+use ignore::WalkBuilder;
 
 /// 扫描配置
 #[derive(Debug, Clone)]
@@ -19,10 +28,19 @@ pub struct ScanConfig {
     /// 支持的文件扩展名
     pub extensions: Vec<String>,
 
-    /// 要忽略的目录
+    /// 要忽略的目录（按目录名匹配）
     pub ignore_dirs: Vec<String>,
 
-    /// 最大并行处理数（Rust 中通过 rayon 实现）
+    /// 包含的 glob（相对项目根目录），为空表示不限制
+    pub include_globs: Vec<String>,
+
+    /// 排除的 glob（相对项目根目录）
+    pub exclude_globs: Vec<String>,
+
+    /// 是否遵循 .gitignore / .ignore（按目录层级逐级生效）
+    pub respect_gitignore: bool,
+
+    /// 最大并行处理数（rayon 线程池大小，每个线程复用一组解析器）
     pub max_parallel: usize,
 
     /// 是否扫描 workspace 依赖
@@ -32,7 +50,15 @@ pub struct ScanConfig {
 impl Default for ScanConfig {
     fn default() -> Self {
         Self {
-            extensions: vec![".vue".to_string(), ".ts".to_string(), ".tsx".to_string()],
+            extensions: vec![
+                ".vue".to_string(),
+                ".ts".to_string(),
+                ".tsx".to_string(),
+                ".js".to_string(),
+                ".jsx".to_string(),
+                ".mjs".to_string(),
+                ".cjs".to_string(),
+            ],
             ignore_dirs: vec![
                 "node_modules".to_string(),
                 "dist".to_string(),
@@ -42,27 +68,29 @@ impl Default for ScanConfig {
                 "tmp".to_string(),
                 "temp".to_string(),
                 ".cache".to_string(),
-                "public".to_string(),
-                "static".to_string(),
-                "assets".to_string(),
             ],
+            include_globs: Vec::new(),
+            exclude_globs: vec![
+                "**/*.d.ts".to_string(),
+                "**/*.min.js".to_string(),
+                "**/storybook-static/**".to_string(),
+                "**/__generated__/**".to_string(),
+                "**/.nuxt/**".to_string(),
+                "**/.next/**".to_string(),
+                "**/.output/**".to_string(),
+            ],
+            respect_gitignore: true,
             max_parallel: 8,
             include_workspace: true,
         }
     }
 }
 
-/// Workspace 信息
-#[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct WorkspaceInfo {
-    /// workspace 根目录
-    pub root: PathBuf,
-
-    /// workspace 包的路径列表
-    pub package_paths: Vec<PathBuf>,
-
-    /// 包名到路径的映射
-    pub package_map: HashMap<String, PathBuf>,
+impl ScanConfig {
+    /// 读取项目根目录下的 `codebase.config.json`，与默认配置合并
+    pub fn for_project<P: AsRef<Path>>(project_path: P) -> Result<Self> {
+        Ok(ProjectConfig::load(project_path)?.scan_config())
+    }
 }
 
 /// 扫描统计
@@ -81,13 +109,25 @@ pub struct ScanStats {
     pub total_entities: usize,
 
     /// 按文件类型统计
-    pub by_extension: HashMap<String, usize>,
+    pub by_extension: BTreeMap<String, usize>,
 
     /// 按实体类型统计
-    pub by_entity_type: HashMap<String, usize>,
+    pub by_entity_type: BTreeMap<String, usize>,
 
     /// 总耗时（毫秒）
     pub duration_ms: u128,
+
+    /// 内容未变化、直接复用上次结果的文件数
+    #[serde(default)]
+    pub unchanged_files: usize,
+
+    /// 相对上次扫描已删除的文件数
+    #[serde(default)]
+    pub removed_files: usize,
+
+    /// 相对上次扫描的实体变化（仅增量扫描时填充）
+    #[serde(default)]
+    pub changes: EntityChanges,
 }
 
 /// 保存的实体数据（包含元数据）
@@ -117,17 +157,34 @@ pub struct EntityMetadata {
 
     /// 工具名称
     pub tool: String,
+
+    /// 项目所在 workspace 的包、版本与包间依赖
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub workspace: Option<WorkspaceInfo>,
 }
 
 /// 文件扫描器
 pub struct FileWalker {
     config: ScanConfig,
+    include_set: Option<GlobSet>,
+    exclude_set: GlobSet,
 }
 
 impl FileWalker {
     /// 创建新的文件扫描器
     pub fn new(config: ScanConfig) -> Self {
-        Self { config }
+        let include_set = if config.include_globs.is_empty() {
+            None
+        } else {
+            Some(Self::build_glob_set(&config.include_globs))
+        };
+        let exclude_set = Self::build_glob_set(&config.exclude_globs);
+
+        Self {
+            config,
+            include_set,
+            exclude_set,
+        }
     }
 
     /// 使用默认配置创建
@@ -151,23 +208,33 @@ impl FileWalker {
         stats: ScanStats,
         project_path: &str,
         output_dir: Option<&str>,
+    ) -> Result<PathBuf> {
+        let workspace = self.resolve_workspace(Path::new(project_path));
+        self.save_entities_with_workspace(entities, stats, project_path, output_dir, workspace)
+    }
+
+    /// 保存实体到 JSON 文件，使用已解析的 workspace 信息
+    fn save_entities_with_workspace(
+        &self,
+        entities: Vec<CodeEntity>,
+        stats: ScanStats,
+        project_path: &str,
+        output_dir: Option<&str>,
+        workspace: Option<WorkspaceInfo>,
     ) -> Result<PathBuf> {
         // 1. 确定输出目录
-        let output_dir = output_dir.unwrap_or("src/data");
-        let output_path = Path::new(project_path).join(output_dir);
+        let output_path = Self::resolve_output_dir(project_path, output_dir);
 
         // 2. 创建目录（如果不存在）
         if !output_path.exists() {
-            fs::create_dir_all(&output_path).context(format!("无法创建目录: {}", output_dir))?;
-            println!("📁 创建输出目录: {}", output_dir);
+            fs::create_dir_all(&output_path)
+                .context(format!("无法创建目录: {}", output_path.display()))?;
+            println!("📁 创建输出目录: {}", output_path.display());
         }
 
         // 3. 生成文件名（基于时间戳）
         let timestamp = Utc::now().format("%Y%m%d_%H%M%S").to_string();
-        let project_name = Path::new(project_path)
-            .file_name()
-            .and_then(|n| n.to_str())
-            .unwrap_or("project");
+        let project_name = Self::project_name(project_path);
         let filename = format!("entities_{}_{}.json", project_name, timestamp);
         let file_path = output_path.join(&filename);
 
@@ -178,6 +245,7 @@ impl FileWalker {
                 scan_time: Utc::now().to_rfc3339(),
                 version: env!("CARGO_PKG_VERSION").to_string(),
                 tool: "tauri-code-base-analyzer".to_string(),
+                workspace,
             },
             entities,
             stats,
@@ -201,24 +269,100 @@ impl FileWalker {
 
     /// 扫描并保存实体（一体化方法）
     ///
-    /// 这是一个便捷方法，将扫描和保存组合在一起
+    /// 这是一个便捷方法，将扫描和保存组合在一起。
+    /// 输出目录中的扫描清单会被用来做增量扫描，完成后同步更新
     pub fn scan_and_save(
         &self,
         root_dir: &str,
         output_dir: Option<&str>,
     ) -> Result<(Vec<CodeEntity>, ScanStats, PathBuf)> {
-        // 1. 扫描提取实体
-        let (entities, stats) = self.extract_all_entities(root_dir)?;
+        // 1. 加载上次的扫描清单
+        let output_path = Self::resolve_output_dir(root_dir, output_dir);
+        let manifest_path = ScanManifest::path_for(&output_path, Self::project_name(root_dir));
+        let previous = ScanManifest::load(&manifest_path);
+        if previous.is_some() {
+            println!("📋 使用扫描清单增量扫描: {}", manifest_path.display());
+        }
 
-        // 2. 保存到文件
-        let file_path =
-            self.save_entities(entities.clone(), stats.clone(), root_dir, output_dir)?;
+        // 2. 扫描提取实体（workspace 只解析一次，扫描与保存共用）
+        let workspace = self.resolve_workspace(Path::new(root_dir));
+        let (entities, stats, manifest) =
+            self.extract_with_workspace(root_dir, previous.as_ref(), workspace.as_ref())?;
+
+        // 3. 保存到文件
+        let file_path = self.save_entities_with_workspace(
+            entities.clone(),
+            stats.clone(),
+            root_dir,
+            output_dir,
+            workspace,
+        )?;
+
+        // 4. 更新扫描清单
+        manifest.save(&manifest_path)?;
 
         Ok((entities, stats, file_path))
     }
 
+    /// 加载项目最近一次 `scan_and_save` 写入的扫描清单（包含不产出实体的文件）
+    pub fn saved_manifest(project_path: &str, output_dir: Option<&str>) -> Option<ScanManifest> {
+        let output_path = Self::resolve_output_dir(project_path, output_dir);
+        ScanManifest::load(&ScanManifest::path_for(&output_path, Self::project_name(project_path)))
+    }
+
+    /// 查找项目最近一次保存的实体文件（`save_entities` 生成的 `entities_<project>_<timestamp>.json`）
+    pub fn latest_saved_entities(project_path: &str, output_dir: Option<&str>) -> Option<PathBuf> {
+        let output_path = Self::resolve_output_dir(project_path, output_dir);
+        let prefix = format!("entities_{}_", Self::project_name(project_path));
+
+        fs::read_dir(&output_path)
+            .ok()?
+            .filter_map(|entry| entry.ok())
+            .filter(|entry| {
+                let name = entry.file_name().to_string_lossy().to_string();
+                name.strip_prefix(&prefix)
+                    .and_then(|rest| rest.strip_suffix(".json"))
+                    .map(|timestamp| {
+                        timestamp.len() == 15
+                            && timestamp.chars().all(|c| c.is_ascii_digit() || c == '_')
+                    })
+                    .unwrap_or(false)
+            })
+            // 时间戳格式固定，按文件名排序即按时间排序
+            .max_by_key(|entry| entry.file_name())
+            .map(|entry| entry.path())
+    }
+
     /// 扫描目录并提取所有实体
     pub fn extract_all_entities(&self, root_dir: &str) -> Result<(Vec<CodeEntity>, ScanStats)> {
+        let (entities, stats, _) = self.extract_incremental(root_dir, None)?;
+        Ok((entities, stats))
+    }
+
+    /// 增量扫描目录并提取实体
+    ///
+    /// 对照 `previous` 清单：mtime 与大小未变的文件直接复用，
+    /// 内容哈希未变的文件只刷新元数据，其余文件重新提取；已删除文件的实体被丢弃。
+    /// `previous` 为 `None` 时等同于全量扫描。
+    ///
+    /// # 返回
+    /// 返回实体列表、扫描统计（含实体变化）以及新的扫描清单
+    pub fn extract_incremental(
+        &self,
+        root_dir: &str,
+        previous: Option<&ScanManifest>,
+    ) -> Result<(Vec<CodeEntity>, ScanStats, ScanManifest)> {
+        let workspace = self.resolve_workspace(Path::new(root_dir));
+        self.extract_with_workspace(root_dir, previous, workspace.as_ref())
+    }
+
+    /// 增量扫描目录并提取实体，使用已解析的 workspace 信息（见 [`Self::resolve_workspace`]）
+    pub fn extract_with_workspace(
+        &self,
+        root_dir: &str,
+        previous: Option<&ScanManifest>,
+        workspace: Option<&WorkspaceInfo>,
+    ) -> Result<(Vec<CodeEntity>, ScanStats, ScanManifest)> {
         let start_time = std::time::Instant::now();
         println!("🚀 开始从目录提取实体: {}", root_dir);
 
@@ -228,33 +372,47 @@ impl FileWalker {
         }
 
         // 1. 查找所有要处理的文件
-        let files = self.find_files(root_path)?;
+        let files = self.find_files(root_path, workspace)?;
         println!("📁 找到 {} 个文件", files.len());
 
-        if files.is_empty() {
-            return Ok((
-                Vec::new(),
-                ScanStats {
-                    total_files: 0,
-                    success_files: 0,
-                    failed_files: 0,
-                    total_entities: 0,
-                    by_extension: HashMap::new(),
-                    by_entity_type: HashMap::new(),
-                    duration_ms: start_time.elapsed().as_millis(),
-                },
-            ));
-        }
-
-        // 2. 提取实体
-        let mut all_entities = Vec::new();
+        // 2. 确定每个文件所属的包（用于生成规范实体 ID）
+        let mut package_resolver = PackageResolver::with_workspace(root_path, workspace);
+        let owners: Vec<(String, String)> = files
+            .iter()
+            .map(|file| package_resolver.resolve(file))
+            .collect();
+
+        // 3. 在大小为 max_parallel 的线程池中并行扫描，每个工作线程复用自己的解析器
+        let pool = rayon::ThreadPoolBuilder::new()
+            .num_threads(self.config.max_parallel.max(1))
+            .build()
+            .context("创建扫描线程池失败")?;
+
+        let results: Vec<(String, Result<(FileRecord, bool)>)> = pool.install(|| {
+            files
+                .par_iter()
+                .zip(owners.par_iter())
+                .map(|(file, owner)| {
+                    let relative_path = Self::relative_path(file, root_dir);
+                    let previous_record = previous.and_then(|m| m.files.get(&relative_path));
+                    let result = EXTRACTORS.with(|extractors| {
+                        self.scan_file(file, root_dir, owner, previous_record, &mut extractors.borrow_mut())
+                    });
+                    (relative_path, result)
+                })
+                .collect()
+        });
+
+        // 4. 按文件顺序合并结果
+        let mut manifest = ScanManifest::new(root_dir);
         let mut success_count = 0;
         let mut failed_count = 0;
-        let mut by_extension: HashMap<String, usize> = HashMap::new();
+        let mut unchanged_count = 0;
+        let mut by_extension: BTreeMap<String, usize> = BTreeMap::new();
 
-        for file in &files {
-            match self.extract_from_file(file, root_dir) {
-                Ok(entities) => {
+        for (file, (relative_path, result)) in files.iter().zip(results) {
+            match result {
+                Ok((record, reused)) => {
                     let ext = file
                         .extension()
                         .and_then(|s| s.to_str())
@@ -262,24 +420,51 @@ impl FileWalker {
                         .to_string();
                     *by_extension.entry(format!(".{}", ext)).or_insert(0) += 1;
 
-                    all_entities.extend(entities);
+                    if reused {
+                        unchanged_count += 1;
+                    }
                     success_count += 1;
+                    manifest.files.insert(relative_path, record);
                 }
                 Err(e) => {
                     eprintln!("⚠️  提取文件失败 {}: {}", file.display(), e);
                     failed_count += 1;
+                    // 提取失败不代表文件被删除：沿用上次的记录，其实体不计入删除
+                    if let Some(record) = previous.and_then(|m| m.files.get(&relative_path)) {
+                        manifest.files.insert(relative_path, record.clone());
+                    }
                 }
             }
         }
 
-        // 3. 统计实体类型
-        let mut by_entity_type: HashMap<String, usize> = HashMap::new();
+        // 5. 按清单顺序汇总实体（清单按路径排序，保证输出稳定）
+        let all_entities: Vec<CodeEntity> = manifest
+            .files
+            .values()
+            .flat_map(|record| record.entities.iter().cloned())
+            .collect();
+
+        // 6. 统计实体类型
+        let mut by_entity_type: BTreeMap<String, usize> = BTreeMap::new();
         for entity in &all_entities {
             *by_entity_type
                 .entry(entity.entity_type.clone())
                 .or_insert(0) += 1;
         }
 
+        // 7. 计算相对上次扫描的变化
+        let (changes, removed_files) = match previous {
+            Some(previous) => {
+                let removed_files = previous
+                    .files
+                    .keys()
+                    .filter(|path| !manifest.files.contains_key(*path))
+                    .count();
+                (manifest.diff(previous), removed_files)
+            }
+            None => (EntityChanges::default(), 0),
+        };
+
         let duration_ms = start_time.elapsed().as_millis();
 
         let stats = ScanStats {
@@ -290,77 +475,180 @@ impl FileWalker {
             by_extension,
             by_entity_type,
             duration_ms,
+            unchanged_files: unchanged_count,
+            removed_files,
+            changes,
         };
 
         println!("\n⏱️  实体提取统计:");
         println!("  - 总文件数: {}", stats.total_files);
         println!("  - 成功提取: {}", stats.success_files);
         println!("  - 失败文件: {}", stats.failed_files);
+        println!("  - 未变化文件: {}", stats.unchanged_files);
         println!("  - 总实体数: {}", stats.total_entities);
+        if previous.is_some() {
+            println!(
+                "  - 实体变化: +{} -{} ~{}",
+                stats.changes.added.len(),
+                stats.changes.removed.len(),
+                stats.changes.changed.len()
+            );
+        }
         println!("  - 总耗时: {}ms", stats.duration_ms);
-        println!("  - 平均耗时: {}ms/文件", duration_ms / files.len() as u128);
+        if !files.is_empty() {
+            println!("  - 平均耗时: {}ms/文件", duration_ms / files.len() as u128);
+        }
+
+        Ok((all_entities, stats, manifest))
+    }
+
+    /// 扫描单个文件，生成清单记录
+    ///
+    /// `owner` 为 (包名, 包内路径)，用于生成规范实体 ID；
+    /// 返回的布尔值表示是否复用了上次的提取结果
+    fn scan_file(
+        &self,
+        file: &Path,
+        root_dir: &str,
+        owner: &(String, String),
+        previous: Option<&FileRecord>,
+        extractors: &mut ExtractorSet,
+    ) -> Result<(FileRecord, bool)> {
+        let stamp = FileStamp::of(file)?;
+
+        // 所属包改名（package.json 的 name 变化）或移动后，上次的规范 ID 已失效，需要重新提取
+        let prefix = EntityId::new(&owner.0, &owner.1, "").to_string();
+        let previous = previous.filter(|record| record.entities.iter().all(|e| e.id.starts_with(&prefix)));
+
+        // 1. mtime 与大小都未变化，直接复用
+        if let Some(record) = previous {
+            if record.mtime == stamp.mtime && record.size == stamp.size {
+                return Ok((record.clone(), true));
+            }
+        }
+
+        // 2. 内容哈希未变化，只刷新元数据
+        let content = fs::read(file).context(format!("无法读取文件: {}", file.display()))?;
+        let hash = hash_content(&content);
+        if let Some(record) = previous.filter(|r| r.hash == hash) {
+            return Ok((
+                FileRecord {
+                    mtime: stamp.mtime,
+                    size: stamp.size,
+                    ..record.clone()
+                },
+                true,
+            ));
+        }
+
+        // 3. 重新提取
+        let mut entities = extractors.extract(file, root_dir)?;
+        assign_canonical_ids(&mut entities, &owner.0, &owner.1);
+        let text = String::from_utf8_lossy(&content);
+        let entity_hashes = entities
+            .iter()
+            .map(|e| (e.id.clone(), hash_entity_slice(&text, e)))
+            .collect();
+
+        Ok((
+            FileRecord {
+                mtime: stamp.mtime,
+                size: stamp.size,
+                hash,
+                entities,
+                entity_hashes,
+            },
+            false,
+        ))
+    }
 
-        Ok((all_entities, stats))
+    /// 计算文件相对项目根目录的路径（与提取器生成的 CodeEntity::file 一致）
+    fn relative_path(file: &Path, root_dir: &str) -> String {
+        file.strip_prefix(root_dir)
+            .unwrap_or(file)
+            .to_string_lossy()
+            .to_string()
+    }
+
+    /// 解析输出目录（相对项目根目录，默认为 "src/data"）
+    fn resolve_output_dir(project_path: &str, output_dir: Option<&str>) -> PathBuf {
+        Path::new(project_path).join(output_dir.unwrap_or("src/data"))
+    }
+
+    /// 项目名（项目根目录的目录名）
+    fn project_name(project_path: &str) -> &str {
+        Path::new(project_path)
+            .file_name()
+            .and_then(|n| n.to_str())
+            .unwrap_or("project")
     }
 
     /// 查找所有符合条件的文件
-    fn find_files(&self, root_dir: &Path) -> Result<Vec<PathBuf>> {
+    fn find_files(&self, root_dir: &Path, workspace: Option<&WorkspaceInfo>) -> Result<Vec<PathBuf>> {
         let mut files = Vec::new();
 
         // 1. 检查是否有 workspace 配置
-        if self.config.include_workspace {
-            if let Some(workspace_info) = self.find_workspace_info(root_dir)? {
-                println!(
-                    "🏢 找到 workspace 配置，包含 {} 个包",
-                    workspace_info.package_paths.len()
-                );
-
-                // 扫描所有 workspace 包
-                for workspace_path in &workspace_info.package_paths {
-                    self.scan_directory(workspace_path, &mut files)?;
-                }
+        if let Some(workspace_info) = workspace {
+            println!(
+                "🏢 找到 workspace 配置，包含 {} 个包",
+                workspace_info.package_paths.len()
+            );
+
+            // 扫描所有 workspace 包
+            for workspace_path in &workspace_info.package_paths {
+                self.scan_directory(workspace_path, root_dir, &mut files)?;
+            }
 
-                // 扫描项目根目录的源码
-                self.scan_project_root(root_dir, &mut files)?;
+            // 扫描项目根目录的源码
+            self.scan_project_root(root_dir, &mut files)?;
 
-                return Ok(files);
-            }
+            return Ok(Self::sorted_unique(files));
         }
 
         // 2. 如果没有 workspace 配置，直接扫描整个目录
         println!("📂 使用默认扫描模式");
-        self.scan_directory(root_dir, &mut files)?;
+        self.scan_directory(root_dir, root_dir, &mut files)?;
+
+        Ok(Self::sorted_unique(files))
+    }
 
-        Ok(files)
+    /// 排序并去重文件列表，保证扫描顺序与文件系统遍历顺序无关
+    fn sorted_unique(mut files: Vec<PathBuf>) -> Vec<PathBuf> {
+        files.sort();
+        files.dedup();
+        files
     }
 
     /// 递归扫描目录
-    fn scan_directory(&self, dir: &Path, results: &mut Vec<PathBuf>) -> Result<()> {
-        let walker = WalkDir::new(dir)
+    ///
+    /// 按层级遵循 .gitignore / .ignore，并应用 ignore_dirs 与 include/exclude glob
+    fn scan_directory(&self, dir: &Path, root_dir: &Path, results: &mut Vec<PathBuf>) -> Result<()> {
+        let ignore_dirs = self.config.ignore_dirs.clone();
+        let respect_gitignore = self.config.respect_gitignore;
+
+        let walker = WalkBuilder::new(dir)
             .follow_links(false)
-            .into_iter()
-            .filter_entry(|e| {
+            .hidden(false)
+            .parents(respect_gitignore)
+            .git_ignore(respect_gitignore)
+            .git_exclude(respect_gitignore)
+            .git_global(false)
+            .ignore(respect_gitignore)
+            .require_git(false)
+            .filter_entry(move |e| {
                 // 过滤掉要忽略的目录
                 let file_name = e.file_name().to_string_lossy();
-                !self
-                    .config
-                    .ignore_dirs
-                    .iter()
-                    .any(|ignore| file_name == ignore.as_str())
-            });
+                !ignore_dirs.iter().any(|ignore| file_name == ignore.as_str())
+            })
+            .build();
 
         for entry in walker {
             match entry {
                 Ok(entry) => {
                     let path = entry.path();
-                    if path.is_file() {
-                        if let Some(ext) = path.extension() {
-                            let ext_str = format!(".{}", ext.to_string_lossy());
-                            if self.config.extensions.contains(&ext_str) {
-                                println!("📄 找到文件: {}", path.display());
-                                results.push(path.to_path_buf());
-                            }
-                        }
+                    if path.is_file() && self.is_selected(path, root_dir) {
+                        println!("📄 找到文件: {}", path.display());
+                        results.push(path.to_path_buf());
                     }
                 }
                 Err(e) => {
@@ -372,6 +660,49 @@ impl FileWalker {
         Ok(())
     }
 
+    /// 判断文件是否需要扫描（扩展名 + include/exclude glob）
+    fn is_selected(&self, path: &Path, root_dir: &Path) -> bool {
+        let has_extension = path
+            .extension()
+            .map(|ext| {
+                let ext_str = format!(".{}", ext.to_string_lossy());
+                self.config.extensions.contains(&ext_str)
+            })
+            .unwrap_or(false);
+        if !has_extension {
+            return false;
+        }
+
+        // glob 相对项目根目录匹配；根目录之外的 workspace 包使用完整路径
+        let relative = path.strip_prefix(root_dir).unwrap_or(path);
+        if self.exclude_set.is_match(relative) {
+            return false;
+        }
+
+        match &self.include_set {
+            Some(include_set) => include_set.is_match(relative),
+            None => true,
+        }
+    }
+
+    /// 编译 glob 列表，无效的模式会被跳过
+    fn build_glob_set(patterns: &[String]) -> GlobSet {
+        let mut builder = GlobSetBuilder::new();
+        for pattern in patterns {
+            match GlobBuilder::new(pattern).literal_separator(true).build() {
+                Ok(glob) => {
+                    builder.add(glob);
+                }
+                Err(e) => eprintln!("⚠️  无效的 glob 模式 {}: {}", pattern, e),
+            }
+        }
+
+        builder.build().unwrap_or_else(|e| {
+            eprintln!("⚠️  编译 glob 失败: {}", e);
+            GlobSet::empty()
+        })
+    }
+
     /// 扫描项目根目录的常见源码目录
     fn scan_project_root(&self, root_dir: &Path, results: &mut Vec<PathBuf>) -> Result<()> {
         let common_source_dirs = ["src", "lib", "app", "components", "pages", "views", "utils"];
@@ -382,14 +713,9 @@ impl FileWalker {
         if let Ok(entries) = fs::read_dir(root_dir) {
             for entry in entries.flatten() {
                 let path = entry.path();
-                if path.is_file() {
-                    if let Some(ext) = path.extension() {
-                        let ext_str = format!(".{}", ext.to_string_lossy());
-                        if self.config.extensions.contains(&ext_str) {
-                            println!("📄 根目录文件: {}", path.display());
-                            results.push(path);
-                        }
-                    }
+                if path.is_file() && self.is_selected(&path, root_dir) {
+                    println!("📄 根目录文件: {}", path.display());
+                    results.push(path);
                 }
             }
         }
@@ -399,244 +725,122 @@ impl FileWalker {
             let source_path = root_dir.join(source_dir);
             if source_path.exists() && source_path.is_dir() {
                 println!("📂 扫描源码目录: {}", source_path.display());
-                self.scan_directory(&source_path, results)?;
+                self.scan_directory(&source_path, root_dir, results)?;
             }
         }
 
         Ok(())
     }
 
-    /// 从单个文件提取实体
-    fn extract_from_file(&self, file: &Path, root_dir: &str) -> Result<Vec<CodeEntity>> {
-        let file_str = file.to_str().context("无法转换文件路径")?;
-
-        if file_str.ends_with(".vue") {
-            println!("📄 从 Vue 文件提取实体: {}", file_str);
-            VueExtractor::new()
-                .extract(file_str, root_dir)
-                .map_err(|e| anyhow::anyhow!("Vue 提取失败: {}", e))
-        } else if file_str.ends_with(".tsx") {
-            println!("📄 从 TSX 文件提取实体: {}", file_str);
-            TypeScriptExtractor::new(true)
-                .map_err(|e| anyhow::anyhow!("创建 TSX 提取器失败: {}", e))?
-                .extract(file_str, root_dir)
-                .map_err(|e| anyhow::anyhow!("TSX 提取失败: {}", e))
-        } else if file_str.ends_with(".ts") {
-            println!("📄 从 TS 文件提取实体: {}", file_str);
-            TypeScriptExtractor::new(false)
-                .map_err(|e| anyhow::anyhow!("创建 TS 提取器失败: {}", e))?
-                .extract(file_str, root_dir)
-                .map_err(|e| anyhow::anyhow!("TS 提取失败: {}", e))
-        } else {
-            Err(anyhow::anyhow!("不支持的文件类型: {}", file_str))
+    /// 解析项目所在的 workspace（未启用 include_workspace 或不在 workspace 中时返回 `None`）
+    pub fn workspace_info(&self, root_dir: &Path) -> Result<Option<WorkspaceInfo>> {
+        if !self.config.include_workspace {
+            return Ok(None);
         }
-    }
-
-    /// 查找 workspace 信息
-    fn find_workspace_info(&self, root_dir: &Path) -> Result<Option<WorkspaceInfo>> {
-        // 1. 查找 workspace 根目录
-        let workspace_root = match self.find_workspace_root(root_dir)? {
-            Some(root) => root,
-            None => return Ok(None),
-        };
 
-        println!("🏢 找到 workspace 根目录: {}", workspace_root.display());
-
-        // 2. 解析 workspace 配置
-        let patterns = self.parse_workspace_patterns(&workspace_root)?;
-        println!("📋 workspace 模式: {:?}", patterns);
-
-        // 3. 解析模式为实际路径
-        let mut package_paths = Vec::new();
-        let mut package_map = HashMap::new();
-
-        for pattern in &patterns {
-            let paths = self.resolve_workspace_pattern(&workspace_root, pattern)?;
-            for path in paths {
-                // 读取 package.json 获取包名
-                let package_json_path = path.join("package.json");
-                if package_json_path.exists() {
-                    if let Ok(content) = fs::read_to_string(&package_json_path) {
-                        if let Ok(package_json) =
-                            serde_json::from_str::<serde_json::Value>(&content)
-                        {
-                            if let Some(name) = package_json.get("name").and_then(|v| v.as_str()) {
-                                package_map.insert(name.to_string(), path.clone());
-                            }
-                        }
-                    }
-                }
-                package_paths.push(path);
-            }
+        let workspace = WorkspaceInfo::discover(root_dir, &self.config.ignore_dirs)?;
+        if let Some(workspace) = &workspace {
+            println!("🏢 找到 workspace 根目录: {}", workspace.root.display());
+            println!("📋 workspace 模式: {:?}", workspace.patterns);
+            println!("📦 找到 {} 个 workspace 包", workspace.package_paths.len());
         }
 
-        println!("📦 找到 {} 个 workspace 包", package_paths.len());
-
-        Ok(Some(WorkspaceInfo {
-            root: workspace_root,
-            package_paths,
-            package_map,
-        }))
+        Ok(workspace)
     }
 
-    /// 查找 workspace 根目录
-    fn find_workspace_root(&self, start_dir: &Path) -> Result<Option<PathBuf>> {
-        let mut current_dir = start_dir.to_path_buf();
+    /// 解析 workspace，配置无法解析时打印警告并按无 workspace 处理
+    pub fn resolve_workspace(&self, root_dir: &Path) -> Option<WorkspaceInfo> {
+        self.workspace_info(root_dir).unwrap_or_else(|e| {
+            eprintln!("⚠️  解析 workspace 失败，按普通项目扫描: {:#}", e);
+            None
+        })
+    }
+}
 
-        loop {
-            // 检查 pnpm-workspace.yaml
-            if current_dir.join("pnpm-workspace.yaml").exists() {
-                return Ok(Some(current_dir));
-            }
+thread_local! {
+    /// 当前工作线程的提取器集合。扫描线程池随每次扫描创建和销毁，
+    /// 因此每个工作线程在一次扫描中只创建一组解析器
+    static EXTRACTORS: RefCell<ExtractorSet> = RefCell::new(ExtractorSet::new());
+}
 
-            // 检查 package.json 的 workspaces 字段
-            let package_json_path = current_dir.join("package.json");
-            if package_json_path.exists() {
-                if let Ok(content) = fs::read_to_string(&package_json_path) {
-                    if let Ok(package_json) = serde_json::from_str::<serde_json::Value>(&content) {
-                        if package_json.get("workspaces").is_some() {
-                            return Ok(Some(current_dir));
-                        }
-                    }
-                }
-            }
+/// 单个工作线程持有的提取器集合
+///
+/// tree-sitter 解析器按需创建，并在同一线程处理的所有文件之间复用（见 [`EXTRACTORS`]）
+struct ExtractorSet {
+    typescript: Option<TypeScriptExtractor>,
+    tsx: Option<TypeScriptExtractor>,
+    javascript: Option<JavaScriptExtractor>,
+    vue: VueExtractor,
+}
 
-            // 向上一级目录
-            match current_dir.parent() {
-                Some(parent) => current_dir = parent.to_path_buf(),
-                None => break,
-            }
+impl ExtractorSet {
+    fn new() -> Self {
+        Self {
+            typescript: None,
+            tsx: None,
+            javascript: None,
+            vue: VueExtractor::new(),
         }
-
-        Ok(None)
     }
 
-    /// 解析 workspace 模式配置
-    fn parse_workspace_patterns(&self, workspace_root: &Path) -> Result<Vec<String>> {
-        let mut patterns = Vec::new();
+    /// 获取（必要时创建）TS/TSX 提取器
+    fn typescript(&mut self, is_tsx: bool) -> Result<&mut TypeScriptExtractor> {
+        let slot = if is_tsx {
+            &mut self.tsx
+        } else {
+            &mut self.typescript
+        };
 
-        // 1. 检查 pnpm-workspace.yaml
-        let pnpm_workspace_path = workspace_root.join("pnpm-workspace.yaml");
-        if pnpm_workspace_path.exists() {
-            let content = fs::read_to_string(&pnpm_workspace_path)?;
-            // 简单解析 YAML（仅支持 packages 列表）
-            let mut in_packages = false;
-            for line in content.lines() {
-                let trimmed = line.trim();
-                if trimmed == "packages:" {
-                    in_packages = true;
-                    continue;
-                }
-                if in_packages {
-                    if trimmed.starts_with('-') {
-                        let pattern = trimmed
-                            .trim_start_matches('-')
-                            .trim()
-                            .trim_matches('\'')
-                            .trim_matches('"');
-                        if !pattern.is_empty() {
-                            patterns.push(pattern.to_string());
-                            println!("  从 pnpm-workspace.yaml 解析到模式: {}", pattern);
-                        }
-                    } else if !trimmed.starts_with(' ') && trimmed.contains(':') {
-                        // 遇到新的顶级配置，退出 packages 部分
-                        break;
-                    }
-                }
-            }
+        if slot.is_none() {
+            let extractor = TypeScriptExtractor::new(is_tsx)
+                .map_err(|e| anyhow::anyhow!("创建 TS 提取器失败: {}", e))?;
+            *slot = Some(extractor);
         }
 
-        // 2. 检查 package.json 的 workspaces 字段
-        let package_json_path = workspace_root.join("package.json");
-        if package_json_path.exists() {
-            let content = fs::read_to_string(&package_json_path)?;
-            if let Ok(package_json) = serde_json::from_str::<serde_json::Value>(&content) {
-                if let Some(workspaces) = package_json.get("workspaces") {
-                    if let Some(array) = workspaces.as_array() {
-                        for item in array {
-                            if let Some(pattern) = item.as_str() {
-                                patterns.push(pattern.to_string());
-                                println!("  从 package.json 解析到模式: {}", pattern);
-                            }
-                        }
-                    } else if let Some(obj) = workspaces.as_object() {
-                        if let Some(packages) = obj.get("packages").and_then(|v| v.as_array()) {
-                            for item in packages {
-                                if let Some(pattern) = item.as_str() {
-                                    patterns.push(pattern.to_string());
-                                    println!("  从 package.json 解析到 packages 模式: {}", pattern);
-                                }
-                            }
-                        }
-                    }
-                }
-            }
-        }
+        Ok(slot.as_mut().expect("提取器已初始化"))
+    }
 
-        // 3. 如果没有找到任何模式，使用默认模式
-        if patterns.is_empty() {
-            patterns = vec![
-                "packages/*".to_string(),
-                "apps/*".to_string(),
-                "libs/*".to_string(),
-            ];
-            println!("  使用默认 workspace 模式");
+    /// 获取（必要时创建）JS/JSX 提取器
+    fn javascript(&mut self) -> Result<&mut JavaScriptExtractor> {
+        if self.javascript.is_none() {
+            let extractor = JavaScriptExtractor::new()
+                .map_err(|e| anyhow::anyhow!("创建 JS 提取器失败: {}", e))?;
+            self.javascript = Some(extractor);
         }
 
-        Ok(patterns)
+        Ok(self.javascript.as_mut().expect("提取器已初始化"))
     }
 
-    /// 解析 workspace 模式为实际路径
-    fn resolve_workspace_pattern(
-        &self,
-        workspace_root: &Path,
-        pattern: &str,
-    ) -> Result<Vec<PathBuf>> {
-        let mut results = Vec::new();
-
-        if pattern.contains('*') {
-            // 处理通配符模式
-            let parts: Vec<&str> = pattern.split('/').collect();
-            let mut current_paths = vec![workspace_root.to_path_buf()];
-
-            for part in parts {
-                if part == "*" {
-                    let mut new_paths = Vec::new();
-                    for current_path in current_paths {
-                        if let Ok(entries) = fs::read_dir(&current_path) {
-                            for entry in entries.flatten() {
-                                let entry_path = entry.path();
-                                if entry_path.is_dir() {
-                                    let dir_name =
-                                        entry_path.file_name().unwrap().to_string_lossy();
-                                    // 跳过要忽略的目录
-                                    if !self.config.ignore_dirs.iter().any(|d| *d == dir_name) {
-                                        new_paths.push(entry_path);
-                                    }
-                                }
-                            }
-                        }
-                    }
-                    current_paths = new_paths;
-                } else {
-                    current_paths = current_paths
-                        .iter()
-                        .map(|p| p.join(part))
-                        .filter(|p| p.exists())
-                        .collect();
-                }
-            }
+    /// 从单个文件提取实体
+    fn extract(&mut self, file: &Path, root_dir: &str) -> Result<Vec<CodeEntity>> {
+        let file_str = file.to_str().context("无法转换文件路径")?;
 
-            results.extend(current_paths);
+        if file_str.ends_with(".vue") {
+            println!("📄 从 Vue 文件提取实体: {}", file_str);
+            self.vue
+                .extract(file_str, root_dir)
+                .map_err(|e| anyhow::anyhow!("Vue 提取失败: {}", e))
+        } else if file_str.ends_with(".tsx") {
+            println!("📄 从 TSX 文件提取实体: {}", file_str);
+            self.typescript(true)?
+                .extract(file_str, root_dir)
+                .map_err(|e| anyhow::anyhow!("TSX 提取失败: {}", e))
+        } else if file_str.ends_with(".ts") {
+            println!("📄 从 TS 文件提取实体: {}", file_str);
+            self.typescript(false)?
+                .extract(file_str, root_dir)
+                .map_err(|e| anyhow::anyhow!("TS 提取失败: {}", e))
+        } else if [".js", ".jsx", ".mjs", ".cjs"]
+            .iter()
+            .any(|ext| file_str.ends_with(ext))
+        {
+            println!("📄 从 JS 文件提取实体: {}", file_str);
+            self.javascript()?
+                .extract(file_str, root_dir)
+                .map_err(|e| anyhow::anyhow!("JS 提取失败: {}", e))
         } else {
-            // 直接路径
-            let full_path = workspace_root.join(pattern);
-            if full_path.exists() {
-                results.push(full_path);
-            }
+            Err(anyhow::anyhow!("不支持的文件类型: {}", file_str))
         }
-
-        Ok(results)
     }
 }
 
@@ -676,19 +880,156 @@ mod tests {
 
     #[test]
     fn test_find_workspace_root() {
-        let walker = FileWalker::with_default();
         let test_dir = "/Users/songdingan/dev/tauri-code-base-analyzer/src/test/after-sale-demo";
 
-        match walker.find_workspace_root(Path::new(test_dir)) {
-            Ok(Some(root)) => {
+        match WorkspaceInfo::find_root(Path::new(test_dir)) {
+            Some(root) => {
                 println!("✅ 找到 workspace 根目录: {}", root.display());
             }
-            Ok(None) => {
+            None => {
                 println!("ℹ️  未找到 workspace 配置");
             }
-            Err(e) => {
-                println!("⚠️  查找失败: {}", e);
-            }
         }
     }
+
+    #[test]
+    fn test_incremental_rescan() {
+        let root = Path::new("/tmp/test_file_walker_incremental");
+        fs::remove_dir_all(root).ok();
+        fs::create_dir_all(root.join("src")).unwrap();
+        fs::write(root.join("src/a.ts"), "export function keep() {}\n").unwrap();
+        fs::write(root.join("src/b.ts"), "export function gone() {}\n").unwrap();
+
+        let walker = FileWalker::new(ScanConfig {
+            include_workspace: false,
+            ..ScanConfig::default()
+        });
+        let root_dir = root.to_str().unwrap();
+        let (_, _, manifest) = walker.extract_incremental(root_dir, None).unwrap();
+
+        fs::remove_file(root.join("src/b.ts")).unwrap();
+        fs::write(root.join("src/c.ts"), "export function added() {}\n").unwrap();
+
+        let (entities, stats, _) = walker
+            .extract_incremental(root_dir, Some(&manifest))
+            .unwrap();
+
+        assert_eq!(entities.len(), 2);
+        assert_eq!(stats.unchanged_files, 1);
+        assert_eq!(stats.removed_files, 1);
+        assert_eq!(
+            stats.changes.added,
+            vec!["test_file_walker_incremental:src/c.ts#Function:added".to_string()]
+        );
+        assert_eq!(
+            stats.changes.removed,
+            vec!["test_file_walker_incremental:src/b.ts#Function:gone".to_string()]
+        );
+        assert!(stats.changes.changed.is_empty());
+
+        // 提取失败（如内容不是合法 UTF-8）的文件沿用上次的记录，不算删除
+        let (_, _, manifest) = walker.extract_incremental(root_dir, Some(&manifest)).unwrap();
+        fs::write(root.join("src/c.ts"), b"export function added() {}\n\xff\xfe\n").unwrap();
+        let (entities, stats, broken) = walker
+            .extract_incremental(root_dir, Some(&manifest))
+            .unwrap();
+        assert_eq!(stats.failed_files, 1);
+        assert_eq!(stats.removed_files, 0);
+        assert!(stats.changes.removed.is_empty());
+        assert_eq!(entities.len(), 2);
+        assert!(broken.files.contains_key("src/c.ts"));
+        fs::write(root.join("src/c.ts"), "export function added() {}\n").unwrap();
+
+        // 包名变化后，未修改的文件也使用新的规范 ID
+        let (_, _, manifest) = walker.extract_incremental(root_dir, Some(&manifest)).unwrap();
+        fs::write(root.join("package.json"), r#"{ "name": "@acme/app" }"#).unwrap();
+        let (entities, stats, _) = walker
+            .extract_incremental(root_dir, Some(&manifest))
+            .unwrap();
+        assert!(entities.iter().all(|e| e.id.starts_with("@acme/app:src/")));
+        assert_eq!(stats.unchanged_files, 0);
+
+        fs::remove_dir_all(root).ok();
+    }
+
+    #[test]
+    fn test_malformed_workspace_config_is_tolerated() {
+        let root = Path::new("/tmp/test_file_walker_bad_workspace");
+        fs::remove_dir_all(root).ok();
+        fs::create_dir_all(root.join("src")).unwrap();
+        fs::write(root.join("pnpm-workspace.yaml"), "packages: [unclosed\n").unwrap();
+        fs::write(root.join("src/a.ts"), "export function a() {}\n").unwrap();
+
+        let walker = FileWalker::with_default();
+        let (entities, stats) = walker.extract_all_entities(root.to_str().unwrap()).unwrap();
+        assert_eq!(entities.len(), 1);
+        assert_eq!(stats.total_files, 1);
+
+        fs::remove_dir_all(root).ok();
+    }
+
+    #[test]
+    fn test_parallel_extraction_is_deterministic() {
+        let root = Path::new("/tmp/test_file_walker_parallel");
+        fs::remove_dir_all(root).ok();
+        fs::create_dir_all(root.join("src")).unwrap();
+        for i in 0..20 {
+            fs::write(
+                root.join(format!("src/mod{}.ts", i)),
+                format!("export function fn{}() {{}}\nexport const VALUE_{} = {};\n", i, i, i),
+            )
+            .unwrap();
+        }
+
+        let root_dir = root.to_str().unwrap();
+        let extract = |max_parallel| {
+            let walker = FileWalker::new(ScanConfig {
+                include_workspace: false,
+                max_parallel,
+                ..ScanConfig::default()
+            });
+            let (entities, _) = walker.extract_all_entities(root_dir).unwrap();
+            entities.into_iter().map(|e| e.id).collect::<Vec<_>>()
+        };
+
+        let sequential = extract(1);
+        assert_eq!(sequential.len(), 40);
+        assert_eq!(sequential, extract(4));
+
+        fs::remove_dir_all(root).ok();
+    }
+
+    #[test]
+    fn test_scan_respects_gitignore_and_globs() {
+        let root = Path::new("/tmp/test_file_walker_ignore");
+        fs::remove_dir_all(root).ok();
+        fs::create_dir_all(root.join("src/generated")).unwrap();
+        fs::create_dir_all(root.join("src/assets")).unwrap();
+        fs::write(root.join(".gitignore"), "src/generated/\n").unwrap();
+        fs::write(root.join("src/main.ts"), "export function main() {}\n").unwrap();
+        fs::write(root.join("src/types.d.ts"), "export declare const x: number;\n").unwrap();
+        fs::write(root.join("src/generated/api.ts"), "export function api() {}\n").unwrap();
+        fs::write(root.join("src/assets/icons.ts"), "export function icon() {}\n").unwrap();
+
+        let walker = FileWalker::new(ScanConfig {
+            include_workspace: false,
+            ..ScanConfig::default()
+        });
+        let mut files = walker.find_files(root, None).unwrap();
+        files.iter_mut().for_each(|f| *f = f.strip_prefix(root).unwrap().to_path_buf());
+        assert_eq!(
+            files,
+            vec![PathBuf::from("src/assets/icons.ts"), PathBuf::from("src/main.ts")]
+        );
+
+        let walker = FileWalker::new(ScanConfig {
+            include_workspace: false,
+            include_globs: vec!["src/assets/**".to_string()],
+            ..ScanConfig::default()
+        });
+        let files = walker.find_files(root, None).unwrap();
+        assert_eq!(files, vec![root.join("src/assets/icons.ts")]);
+
+        fs::remove_dir_all(root).ok();
+    }
 }
@@ -0,0 +1,473 @@
+//! 变更影响分析
+//!
+//! 把 unified diff 中变化的行范围映射到实体的 `loc`，再沿依赖图反向遍历，
+//! 列出受影响的页面、组件和 store 及其距离。diff 可以来自本地仓库的 `git diff`，
+//! 也可以来自补丁文件或标准输入，整个过程不需要网络。
+//!
+//! 行号按 diff 的新版本计算，实体应来自变更之后的扫描结果；
+//! 删除的文件按旧路径匹配，文件内全部实体视为变化
+
+use super::dependency_graph::{DependencyGraph, Direction, EdgeKind};
+use super::extractors::CodeEntity;
+use anyhow::{bail, Context, Result};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::path::Path;
+use std::process::Command;
+
+/// diff 中一个文件的变化
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ChangedFile {
+    /// 文件路径（新路径；删除的文件为旧路径）
+    pub path: String,
+
+    /// 新版本中变化的行范围（1-based，闭区间）
+    pub ranges: Vec<(usize, usize)>,
+
+    /// 文件是否被删除
+    pub deleted: bool,
+}
+
+/// 受影响实体的分类
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ImpactCategory {
+    /// 页面：位于 `pages/` 或 `views/` 目录下的组件
+    Page,
+    /// 组件
+    Component,
+    /// Pinia / Vuex store
+    Store,
+    /// 组合式函数
+    Composable,
+    /// 其他实体
+    Other,
+}
+
+/// 直接被修改的实体
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ChangedEntity {
+    pub id: String,
+
+    #[serde(rename = "type")]
+    pub entity_type: String,
+
+    pub file: String,
+
+    pub category: ImpactCategory,
+
+    /// 与实体重叠的变化行范围
+    pub lines: Vec<(usize, usize)>,
+}
+
+/// 间接受影响的实体
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AffectedEntity {
+    pub id: String,
+
+    #[serde(rename = "type")]
+    pub entity_type: String,
+
+    pub file: String,
+
+    pub category: ImpactCategory,
+
+    /// 距离最近的被修改实体的跳数
+    pub depth: usize,
+
+    /// 最近的被修改实体
+    pub source: String,
+
+    /// 最短路径上的前一个实体
+    pub via: String,
+
+    /// 经由的依赖类型
+    pub kind: EdgeKind,
+}
+
+/// 影响分析报告
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ImpactReport {
+    /// diff 涉及的文件
+    pub changed_files: Vec<ChangedFile>,
+
+    /// 直接被修改的实体
+    pub changed: Vec<ChangedEntity>,
+
+    /// 受影响的实体，按距离排序
+    pub affected: Vec<AffectedEntity>,
+
+    /// 受影响实体按分类计数
+    pub by_category: BTreeMap<ImpactCategory, usize>,
+}
+
+/// 在本地仓库中执行 `git diff --relative --unified=0 <range>`
+///
+/// 在 `repo_dir` 下执行，路径相对 `repo_dir`，与实体的 `file` 一致；
+/// `range` 可以是 `main...HEAD`、`HEAD~3` 或单个提交，为空时对比工作区与 HEAD
+/// （包括已暂存与未暂存的修改；不带参数的 `git diff` 只对比工作区与暂存区）
+pub fn git_diff(repo_dir: &Path, range: &str) -> Result<String> {
+    let range = match range.trim() {
+        "" => "HEAD",
+        range => range,
+    };
+    let output = Command::new("git")
+        .current_dir(repo_dir)
+        .args(["diff", "--relative", "--unified=0", "--no-color", "--no-ext-diff", range])
+        .output()
+        .context(format!("无法执行 git diff: {}", repo_dir.display()))?;
+    if !output.status.success() {
+        bail!(
+            "git diff {} 执行失败: {}",
+            range,
+            String::from_utf8_lossy(&output.stderr).trim()
+        );
+    }
+
+    Ok(String::from_utf8_lossy(&output.stdout).to_string())
+}
+
+/// 解析 unified diff
+///
+/// 支持 `git diff` 与 `diff -u` 的输出；纯删除的 hunk 记为删除位置前后两行。
+/// hunk 内按行数计数，内容以 `---` / `+++` 开头的行不会被误认为文件头
+pub fn parse_unified_diff(diff: &str) -> Vec<ChangedFile> {
+    let mut files: Vec<ChangedFile> = Vec::new();
+    let mut old_path: Option<String> = None;
+    // 当前 hunk 中尚未读到的旧版本 / 新版本行数
+    let (mut old_left, mut new_left) = (0usize, 0usize);
+
+    for line in diff.lines() {
+        if old_left > 0 || new_left > 0 {
+            match line.chars().next() {
+                Some('-') => old_left = old_left.saturating_sub(1),
+                Some('+') => new_left = new_left.saturating_sub(1),
+                Some('\\') => {}
+                _ => {
+                    old_left = old_left.saturating_sub(1);
+                    new_left = new_left.saturating_sub(1);
+                }
+            }
+            continue;
+        }
+
+        if let Some(path) = line.strip_prefix("--- ") {
+            old_path = header_path(path, "a/");
+        } else if let Some(path) = line.strip_prefix("+++ ") {
+            let (path, deleted) = match header_path(path, "b/") {
+                Some(path) => (path, false),
+                None => match old_path.take() {
+                    Some(path) => (path, true),
+                    None => continue,
+                },
+            };
+            files.push(ChangedFile {
+                path,
+                ranges: Vec::new(),
+                deleted,
+            });
+        } else if line.starts_with("@@") {
+            let Some((old, new)) = hunk_ranges(line) else {
+                continue;
+            };
+            old_left = old.1;
+            new_left = new.1;
+
+            let Some(file) = files.last_mut() else {
+                continue;
+            };
+            let (start, count) = new;
+            let range = if count == 0 {
+                (start.max(1), start + 1)
+            } else {
+                (start, start + count - 1)
+            };
+            file.ranges.push(range);
+        }
+    }
+
+    files
+}
+
+/// diff 文件头中的路径，`/dev/null` 返回 `None`
+fn header_path(raw: &str, prefix: &str) -> Option<String> {
+    // `diff -u` 的文件头在路径后带制表符和时间戳
+    let path = raw.split('\t').next().unwrap_or(raw).trim();
+    if path == "/dev/null" {
+        return None;
+    }
+    let path = path.trim_matches('"');
+    Some(path.strip_prefix(prefix).unwrap_or(path).to_string())
+}
+
+/// `@@ -a,b +c,d @@` 中的 ((a, b), (c, d))，省略行数时为 1
+fn hunk_ranges(line: &str) -> Option<((usize, usize), (usize, usize))> {
+    let mut parts = line.split_whitespace().skip(1);
+    let old = parse_range(parts.next()?.strip_prefix('-')?)?;
+    let new = parse_range(parts.next()?.strip_prefix('+')?)?;
+    Some((old, new))
+}
+
+fn parse_range(range: &str) -> Option<(usize, usize)> {
+    match range.split_once(',') {
+        Some((start, count)) => Some((start.parse().ok()?, count.parse().ok()?)),
+        None => Some((range.parse().ok()?, 1)),
+    }
+}
+
+/// diff 路径是否指向实体文件：相同，或 diff 路径以 `/<file>` 结尾（补丁相对仓库根目录、扫描根目录是子目录）
+fn same_file(diff_path: &str, entity_file: &str) -> bool {
+    let entity_file = entity_file.replace('\\', "/");
+    diff_path == entity_file || diff_path.ends_with(&format!("/{}", entity_file))
+}
+
+/// 找出 diff 直接修改的实体
+pub fn changed_entities(entities: &[CodeEntity], changes: &[ChangedFile]) -> Vec<ChangedEntity> {
+    let mut changed = Vec::new();
+
+    for entity in entities {
+        let mut lines = Vec::new();
+        for file in changes.iter().filter(|f| same_file(&f.path, &entity.file)) {
+            if file.deleted {
+                lines.push((entity.loc.start_line, entity.loc.end_line));
+                continue;
+            }
+            lines.extend(
+                file.ranges
+                    .iter()
+                    .filter(|(start, end)| *start <= entity.loc.end_line && *end >= entity.loc.start_line)
+                    .copied(),
+            );
+        }
+
+        if !lines.is_empty() {
+            changed.push(ChangedEntity {
+                id: entity.id.clone(),
+                entity_type: entity.entity_type.clone(),
+                file: entity.file.clone(),
+                category: categorize(entity, entities),
+                lines,
+            });
+        }
+    }
+
+    changed
+}
+
+/// 影响分析：定位被修改的实体，沿依赖图反向遍历求受影响实体
+///
+/// 多个被修改实体都能到达同一实体时取最近的一个；`max_depth` 限制最大跳数
+pub fn analyze_impact(
+    entities: &[CodeEntity],
+    graph: &DependencyGraph,
+    changes: Vec<ChangedFile>,
+    max_depth: Option<usize>,
+) -> ImpactReport {
+    let changed = changed_entities(entities, &changes);
+    let changed_ids: HashSet<&str> = changed.iter().map(|c| c.id.as_str()).collect();
+    let by_id: HashMap<&str, &CodeEntity> = entities.iter().map(|e| (e.id.as_str(), e)).collect();
+
+    let mut nearest: HashMap<String, AffectedEntity> = HashMap::new();
+    for seed in &changed {
+        for reached in graph.closure(&seed.id, Direction::Dependents, &[], max_depth) {
+            if changed_ids.contains(reached.id.as_str()) {
+                continue;
+            }
+            if nearest.get(&reached.id).is_some_and(|a| a.depth <= reached.depth) {
+                continue;
+            }
+
+            let (entity_type, file, category) = match by_id.get(reached.id.as_str()) {
+                Some(entity) => (
+                    entity.entity_type.clone(),
+                    entity.file.clone(),
+                    categorize(entity, entities),
+                ),
+                None => match graph.nodes.get(&reached.id) {
+                    Some(node) => (node.entity_type.clone(), node.file.clone(), ImpactCategory::Other),
+                    None => (String::new(), String::new(), ImpactCategory::Other),
+                },
+            };
+            nearest.insert(
+                reached.id.clone(),
+                AffectedEntity {
+                    id: reached.id,
+                    entity_type,
+                    file,
+                    category,
+                    depth: reached.depth,
+                    source: seed.id.clone(),
+                    via: reached.via,
+                    kind: reached.kind,
+                },
+            );
+        }
+    }
+
+    let mut affected: Vec<AffectedEntity> = nearest.into_values().collect();
+    affected.sort_by(|a, b| a.depth.cmp(&b.depth).then_with(|| a.id.cmp(&b.id)));
+
+    let mut by_category = BTreeMap::new();
+    for entity in &affected {
+        *by_category.entry(entity.category).or_insert(0) += 1;
+    }
+
+    ImpactReport {
+        changed_files: changes,
+        changed,
+        affected,
+        by_category,
+    }
+}
+
+/// 实体分类
+///
+/// store 既包括 store 定义本身，也包括声明 store 的顶层实体（`export const useOrderStore = defineStore(...)`）
+pub fn categorize(entity: &CodeEntity, entities: &[CodeEntity]) -> ImpactCategory {
+    let is_store = |e: &CodeEntity| e.entity_type.ends_with("store") || e.entity_type == "vuex_module";
+
+    match entity.entity_type.as_str() {
+        "component" => {
+            let in_pages = entity
+                .file
+                .replace('\\', "/")
+                .split('/')
+                .any(|segment| segment == "pages" || segment == "views");
+            if in_pages {
+                ImpactCategory::Page
+            } else {
+                ImpactCategory::Component
+            }
+        }
+        "composable" => ImpactCategory::Composable,
+        _ if is_store(entity) => ImpactCategory::Store,
+        _ if entities
+            .iter()
+            .any(|e| e.parent.as_deref() == Some(entity.id.as_str()) && is_store(e)) =>
+        {
+            ImpactCategory::Store
+        }
+        _ => ImpactCategory::Other,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tool_execution::codebase::LocationInfo;
+
+    fn entity(id: &str, entity_type: &str, file: &str, lines: (usize, usize)) -> CodeEntity {
+        CodeEntity {
+            id: id.to_string(),
+            entity_type: entity_type.to_string(),
+            file: file.to_string(),
+            loc: LocationInfo::with_range(lines.0, lines.1),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_parse_unified_diff() {
+        let diff = r#"diff --git a/src/composables/useOrderStatus.ts b/src/composables/useOrderStatus.ts
+index 1111111..2222222 100644
+--- a/src/composables/useOrderStatus.ts
++++ b/src/composables/useOrderStatus.ts
+@@ -10,2 +10,3 @@ export function useOrderStatus() {
+-    const a = 1;
+-    const b = 2;
++    const a = 1;
++    const b = 2;
++    const c = 3;
+@@ -30 +31 @@ export function useOrderStatus() {
+--- removed sql comment
++++ added line that looks like a header
+@@ -40,3 +41,0 @@ export function useOrderStatus() {
+-a
+-b
+-c
+diff --git a/src/legacy.ts b/src/legacy.ts
+deleted file mode 100644
+--- a/src/legacy.ts
++++ /dev/null
+@@ -1,2 +0,0 @@
+-export const legacy = 1;
+-export const old = 2;
+"#;
+        let files = parse_unified_diff(diff);
+
+        assert_eq!(files.len(), 2);
+        assert_eq!(files[0].path, "src/composables/useOrderStatus.ts");
+        assert_eq!(files[0].ranges, vec![(10, 12), (31, 31), (41, 42)]);
+        assert!(!files[0].deleted);
+        assert_eq!(files[1].path, "src/legacy.ts");
+        assert!(files[1].deleted);
+    }
+
+    #[test]
+    fn test_impact_walks_dependents_with_distance() {
+        let entities = vec![
+            entity("app:src/composables/useOrderStatus.ts#Composable:useOrderStatus", "composable", "src/composables/useOrderStatus.ts", (1, 20)),
+            entity("app:src/composables/useOrderStatus.ts#Function:unrelated", "function", "src/composables/useOrderStatus.ts", (22, 30)),
+            entity("app:src/components/OrderBadge.vue#Component:setup", "component", "src/components/OrderBadge.vue", (1, 40)),
+            entity("app:src/views/OrderDetail.vue#Component:setup", "component", "src/views/OrderDetail.vue", (1, 80)),
+        ];
+        let ids: Vec<&str> = entities.iter().map(|e| e.id.as_str()).collect();
+
+        let mut graph = DependencyGraph::new();
+        for e in &entities {
+            graph.add_entity(e);
+        }
+        graph.add_edge(ids[2], ids[0], EdgeKind::Calls);
+        graph.add_edge(ids[3], ids[2], EdgeKind::Renders);
+        graph.add_edge(ids[3], ids[0], EdgeKind::Imports);
+        graph.add_edge(ids[3], ids[1], EdgeKind::Calls);
+
+        // 补丁相对仓库根目录，扫描根目录是 packages/app
+        let diff = "--- a/packages/app/src/composables/useOrderStatus.ts\n+++ b/packages/app/src/composables/useOrderStatus.ts\n@@ -5 +5 @@\n";
+        let report = analyze_impact(&entities, &graph, parse_unified_diff(diff), None);
+
+        assert_eq!(report.changed.len(), 1);
+        assert_eq!(report.changed[0].id, ids[0]);
+        assert_eq!(report.changed[0].category, ImpactCategory::Composable);
+
+        let affected: Vec<(&str, usize, ImpactCategory)> =
+            report.affected.iter().map(|a| (a.id.as_str(), a.depth, a.category)).collect();
+        assert_eq!(
+            affected,
+            vec![(ids[2], 1, ImpactCategory::Component), (ids[3], 1, ImpactCategory::Page)]
+        );
+        assert_eq!(report.by_category.get(&ImpactCategory::Page), Some(&1));
+    }
+
+    #[test]
+    fn test_git_diff_without_range_includes_staged_changes() {
+        let repo = std::env::temp_dir().join("codebase_impact_git_diff_test");
+        let _ = std::fs::remove_dir_all(&repo);
+        std::fs::create_dir_all(repo.join("src")).unwrap();
+        std::fs::write(repo.join("src/order.ts"), "export const a = 1\n").unwrap();
+
+        let git = |args: &[&str]| {
+            let status = Command::new("git")
+                .current_dir(&repo)
+                .args(["-c", "user.name=test", "-c", "user.email=test@example.com"])
+                .args(args)
+                .status()
+                .unwrap();
+            assert!(status.success());
+        };
+        git(&["init", "-q"]);
+        git(&["add", "."]);
+        git(&["commit", "-q", "-m", "init"]);
+
+        // 只暂存、未提交的修改也要出现在补丁中
+        std::fs::write(repo.join("src/order.ts"), "export const a = 2\n").unwrap();
+        git(&["add", "."]);
+
+        let files = parse_unified_diff(&git_diff(&repo, "").unwrap());
+        assert_eq!(files.len(), 1);
+        assert_eq!(files[0].path, "src/order.ts");
+        assert_eq!(files[0].ranges, vec![(1, 1)]);
+
+        let _ = std::fs::remove_dir_all(&repo);
+    }
+}
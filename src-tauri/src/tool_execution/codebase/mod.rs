@@ -2,21 +2,42 @@
 //!
 //! 提供代码实体提取、分块增强和向量化功能
 
+pub mod architecture;
 pub mod chunking;
+pub mod dead_code;
+pub mod dependency_graph;
 pub mod embeddings;
 pub mod enrichment;
+pub mod entity_id;
 pub mod examples_enrichment;
 pub mod examples_file_walker;
 pub mod extractors;
+pub mod impact;
 pub mod file_walker;
+pub mod module_resolver;
+pub mod project_config;
+pub mod routes;
+pub mod scan_manifest;
+pub mod workspace;
 
 // 导出核心类型
+pub use architecture::{check_architecture, ArchitectureConfig, ArchitectureReport, LayerRule, Severity};
 pub use chunking::{ChunkBuilder, ChunkStats, CodeChunk};
+pub use dead_code::{find_dead_code, Confidence, DeadCodeReport, UnusedEntity, UnusedKind};
+pub use dependency_graph::{DependencyEdge, DependencyGraph, Direction, EdgeKind, ReachedEntity};
 pub use embeddings::{EmbeddedChunk, EmbeddingStats, EmbeddingsClient};
 pub use enrichment::{
     EnrichedEntity, EnrichmentConfig, EnrichmentOrchestrator, StaticAnalysisResult,
 };
-pub use extractors::{CodeEntity, LocationInfo, TypeScriptExtractor, VueExtractor};
-pub use file_walker::{
-    EntityMetadata, FileWalker, SavedEntityData, ScanConfig, ScanStats, WorkspaceInfo,
+pub use entity_id::{resolve_entity_fuzzy, resolve_short_id, EntityId};
+pub use module_resolver::ModuleResolver;
+pub use extractors::{
+    CodeEntity, ComponentApi, JavaScriptExtractor, LocationInfo, PropInfo, StoreModel, StoreStyle,
+    TypeScriptExtractor, VueExtractor,
 };
+pub use file_walker::{EntityMetadata, FileWalker, SavedEntityData, ScanConfig, ScanStats};
+pub use impact::{analyze_impact, git_diff, parse_unified_diff, ImpactCategory, ImpactReport};
+pub use project_config::{ProjectConfig, PROJECT_CONFIG_FILE};
+pub use routes::{extract_routes, RouteMap};
+pub use scan_manifest::{EntityChanges, ScanManifest};
+pub use workspace::{WorkspaceDependency, WorkspaceInfo, WorkspacePackage};
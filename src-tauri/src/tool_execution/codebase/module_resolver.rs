@@ -0,0 +1,860 @@
+//! 模块解析
+//!
+//! 将导入语句中的模块说明符解析为磁盘上的源码文件，按以下顺序尝试：
+//! 1. 相对路径（`./`、`../`）
+//! 2. tsconfig.json / jsconfig.json 的 `compilerOptions.paths`（支持 `extends` 链）
+//! 3. vite / webpack / vue.config 中可静态读取的 `resolve.alias`
+//! 4. `compilerOptions.baseUrl`
+//! 5. workspace 包名（`@company/shared-ui`、`@company/shared-ui/button`）
+//!
+//! 别名配置按导入文件所在目录向上查找最近的配置文件，并按目录缓存
+
+use super::file_walker::ScanConfig;
+use super::workspace::WorkspaceInfo;
+use lazy_static::lazy_static;
+use regex::Regex;
+use std::collections::{BTreeMap, HashMap};
+use std::fs;
+use std::path::{Component, Path, PathBuf};
+use std::sync::{Arc, Mutex};
+
+/// 按顺序尝试补全的扩展名
+const RESOLVE_EXTENSIONS: [&str; 9] = ["ts", "tsx", "mts", "cts", "vue", "js", "jsx", "mjs", "cjs"];
+
+/// tsconfig 可能的文件名
+const TSCONFIG_FILES: [&str; 2] = ["tsconfig.json", "jsconfig.json"];
+
+/// 打包工具配置文件（按优先级）
+const BUNDLER_CONFIG_FILES: [&str; 10] = [
+    "vite.config.ts",
+    "vite.config.mts",
+    "vite.config.js",
+    "vite.config.mjs",
+    "vitest.config.ts",
+    "webpack.config.ts",
+    "webpack.config.js",
+    "vue.config.js",
+    "nuxt.config.ts",
+    "rsbuild.config.ts",
+];
+
+/// `extends` 链的最大深度，防止循环引用
+const MAX_EXTENDS_DEPTH: usize = 8;
+
+lazy_static! {
+    static ref ALIAS_OBJECT_REGEX: Regex = Regex::new(r"\balias\s*:\s*\{").unwrap();
+    static ref ALIAS_ARRAY_REGEX: Regex = Regex::new(r"\balias\s*:\s*\[").unwrap();
+    static ref STRING_LITERAL_REGEX: Regex = Regex::new(r#"['"`]([^'"`]*)['"`]"#).unwrap();
+    static ref FIND_REGEX: Regex = Regex::new(r#"\bfind\s*:\s*['"]([^'"]+)['"]"#).unwrap();
+    static ref REPLACEMENT_REGEX: Regex = Regex::new(r"\breplacement\s*:").unwrap();
+}
+
+/// 单条别名规则
+///
+/// `pattern` 最多包含一个 `*`，匹配到的部分替换进 `targets` 中的 `*`
+#[derive(Debug, Clone)]
+struct AliasRule {
+    pattern: String,
+    targets: Vec<PathBuf>,
+}
+
+impl AliasRule {
+    /// 匹配说明符，返回 `*` 捕获的内容（精确匹配时为空串）
+    fn matches<'a>(&self, specifier: &'a str) -> Option<&'a str> {
+        match self.pattern.split_once('*') {
+            Some((prefix, suffix)) => {
+                if specifier.len() >= prefix.len() + suffix.len()
+                    && specifier.starts_with(prefix)
+                    && specifier.ends_with(suffix)
+                {
+                    Some(&specifier[prefix.len()..specifier.len() - suffix.len()])
+                } else {
+                    None
+                }
+            }
+            None => (specifier == self.pattern).then_some(""),
+        }
+    }
+
+    /// 前缀长度，多条规则同时匹配时取前缀最长的
+    fn prefix_len(&self) -> usize {
+        self.pattern.split('*').next().map(|p| p.len()).unwrap_or(0)
+    }
+}
+
+/// 某个目录生效的别名配置
+#[derive(Debug, Clone, Default)]
+struct AliasConfig {
+    /// tsconfig paths
+    paths: Vec<AliasRule>,
+
+    /// 打包工具 alias
+    bundler: Vec<AliasRule>,
+
+    /// tsconfig baseUrl
+    base_url: Option<PathBuf>,
+}
+
+/// tsconfig 中与模块解析相关的选项
+#[derive(Debug, Clone, Default)]
+struct TsConfigOptions {
+    base_url: Option<PathBuf>,
+    paths: Option<TsPaths>,
+}
+
+/// tsconfig 的 `paths`
+#[derive(Debug, Clone)]
+struct TsPaths {
+    /// 模式 -> 目标列表
+    entries: Vec<(String, Vec<String>)>,
+
+    /// 定义 paths 的配置文件所在目录（没有 baseUrl 时作为相对基准）
+    dir: PathBuf,
+}
+
+/// 模块解析器
+pub struct ModuleResolver {
+    root_dir: PathBuf,
+
+    /// 解析的上边界：workspace 根目录或项目根目录
+    boundary: PathBuf,
+
+    /// workspace 包名 -> 包目录
+    packages: BTreeMap<String, PathBuf>,
+
+    /// 目录 -> 生效的别名配置
+    cache: Mutex<HashMap<PathBuf, Arc<AliasConfig>>>,
+}
+
+impl ModuleResolver {
+    /// 创建解析器，自动识别项目所在的 workspace
+    pub fn new<P: AsRef<Path>>(root_dir: P) -> Self {
+        let root_dir = root_dir.as_ref().to_path_buf();
+        let workspace = WorkspaceInfo::discover(&root_dir, &ScanConfig::default().ignore_dirs)
+            .unwrap_or_else(|e| {
+                eprintln!("⚠️  解析 workspace 失败: {}", e);
+                None
+            });
+        Self::with_workspace(root_dir, workspace.as_ref())
+    }
+
+    /// 使用已解析的 workspace 信息创建解析器
+    pub fn with_workspace<P: AsRef<Path>>(root_dir: P, workspace: Option<&WorkspaceInfo>) -> Self {
+        let root_dir = root_dir.as_ref().to_path_buf();
+        let boundary = workspace
+            .map(|w| w.root.clone())
+            .filter(|root| root_dir.starts_with(root))
+            .unwrap_or_else(|| root_dir.clone());
+
+        Self {
+            root_dir,
+            boundary,
+            packages: workspace.map(|w| w.package_map.clone()).unwrap_or_default(),
+            cache: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// 解析 `importer` 中的模块说明符，返回源码文件的绝对路径
+    ///
+    /// 外部依赖（node_modules 中的包）以及无法解析的说明符返回 `None`
+    pub fn resolve(&self, specifier: &str, importer: &Path) -> Option<PathBuf> {
+        let importer = if importer.is_absolute() {
+            importer.to_path_buf()
+        } else {
+            self.root_dir.join(importer)
+        };
+        let importer_dir = importer.parent()?;
+
+        // 1. 相对路径
+        if specifier.starts_with("./") || specifier.starts_with("../") || specifier == "." || specifier == ".." {
+            return resolve_file(&importer_dir.join(specifier));
+        }
+
+        let config = self.alias_config(importer_dir);
+
+        // 2. tsconfig paths，3. 打包工具 alias
+        for rules in [&config.paths, &config.bundler] {
+            if let Some(resolved) = Self::resolve_alias(rules, specifier) {
+                return Some(resolved);
+            }
+        }
+
+        // 4. baseUrl
+        if let Some(base_url) = &config.base_url {
+            if let Some(resolved) = resolve_file(&base_url.join(specifier)) {
+                return Some(resolved);
+            }
+        }
+
+        // 5. workspace 包
+        self.resolve_package(specifier)
+    }
+
+    /// 将解析结果转为实体的 `file` 字段格式（项目内为相对路径，项目外为绝对路径）
+    pub fn entity_file(&self, path: &Path) -> String {
+        path.strip_prefix(&self.root_dir)
+            .unwrap_or(path)
+            .to_string_lossy()
+            .to_string()
+    }
+
+    /// 按前缀最长优先匹配别名规则
+    fn resolve_alias(rules: &[AliasRule], specifier: &str) -> Option<PathBuf> {
+        let mut matched: Vec<(&AliasRule, &str)> = rules
+            .iter()
+            .filter_map(|rule| rule.matches(specifier).map(|captured| (rule, captured)))
+            .collect();
+        matched.sort_by_key(|(rule, _)| std::cmp::Reverse(rule.prefix_len()));
+
+        for (rule, captured) in matched {
+            for target in &rule.targets {
+                let target = PathBuf::from(target.to_string_lossy().replace('*', captured));
+                if let Some(resolved) = resolve_file(&target) {
+                    return Some(resolved);
+                }
+            }
+        }
+
+        None
+    }
+
+    /// 解析 workspace 包名及其子路径
+    fn resolve_package(&self, specifier: &str) -> Option<PathBuf> {
+        let (name, dir) = self
+            .packages
+            .iter()
+            .filter(|(name, _)| {
+                specifier == name.as_str()
+                    || specifier
+                        .strip_prefix(name.as_str())
+                        .map(|rest| rest.starts_with('/'))
+                        .unwrap_or(false)
+            })
+            .max_by_key(|(name, _)| name.len())?;
+
+        let subpath = specifier[name.len()..].trim_start_matches('/');
+        if subpath.is_empty() {
+            return resolve_package_entry(dir);
+        }
+
+        resolve_file(&dir.join(subpath)).or_else(|| resolve_file(&dir.join("src").join(subpath)))
+    }
+
+    /// 目录生效的别名配置（带缓存）
+    fn alias_config(&self, dir: &Path) -> Arc<AliasConfig> {
+        if let Some(config) = self.cache.lock().unwrap().get(dir) {
+            return config.clone();
+        }
+
+        let mut config = AliasConfig::default();
+
+        if let Some(tsconfig) = self.find_upwards(dir, &TSCONFIG_FILES) {
+            let options = load_tsconfig(&tsconfig, 0);
+            let paths_base = options.base_url.clone();
+            if let Some(paths) = options.paths {
+                let base = paths_base.clone().unwrap_or(paths.dir);
+                config.paths = paths
+                    .entries
+                    .into_iter()
+                    .map(|(pattern, targets)| AliasRule {
+                        pattern,
+                        targets: targets.iter().map(|t| normalize_path(&base.join(t))).collect(),
+                    })
+                    .collect();
+            }
+            config.base_url = paths_base;
+        }
+
+        if let Some(bundler_config) = self.find_upwards(dir, &BUNDLER_CONFIG_FILES) {
+            config.bundler = load_bundler_aliases(&bundler_config);
+        }
+
+        let config = Arc::new(config);
+        self.cache
+            .lock()
+            .unwrap()
+            .insert(dir.to_path_buf(), config.clone());
+        config
+    }
+
+    /// 从 `dir` 向上查找配置文件，不超过解析边界
+    fn find_upwards(&self, dir: &Path, names: &[&str]) -> Option<PathBuf> {
+        let mut current = Some(dir);
+
+        while let Some(dir) = current {
+            for name in names {
+                let candidate = dir.join(name);
+                if candidate.is_file() {
+                    return Some(candidate);
+                }
+            }
+            if dir == self.boundary || !dir.starts_with(&self.boundary) {
+                break;
+            }
+            current = dir.parent();
+        }
+
+        None
+    }
+}
+
+/// 将路径补全为实际存在的源码文件
+///
+/// 依次尝试：原路径、追加扩展名、`.js` 改为 `.ts`（TS ESM 写法）、目录下的 index 文件与 package.json 入口
+fn resolve_file(path: &Path) -> Option<PathBuf> {
+    let path = normalize_path(path);
+
+    if path.is_file() {
+        return Some(path);
+    }
+
+    let raw = path.to_string_lossy().to_string();
+    for ext in RESOLVE_EXTENSIONS {
+        let candidate = PathBuf::from(format!("{}.{}", raw, ext));
+        if candidate.is_file() {
+            return Some(candidate);
+        }
+    }
+
+    if let Some(ext) = path.extension().and_then(|e| e.to_str()) {
+        if ["js", "jsx", "mjs", "cjs"].contains(&ext) {
+            for ts_ext in ["ts", "tsx", "mts", "cts"] {
+                let candidate = path.with_extension(ts_ext);
+                if candidate.is_file() {
+                    return Some(candidate);
+                }
+            }
+        }
+    }
+
+    if path.is_dir() {
+        for ext in RESOLVE_EXTENSIONS {
+            let candidate = path.join(format!("index.{}", ext));
+            if candidate.is_file() {
+                return Some(candidate);
+            }
+        }
+        return package_json_entry(&path).and_then(|entry| {
+            let entry = normalize_path(&path.join(entry));
+            (entry != path).then(|| resolve_file(&entry)).flatten()
+        });
+    }
+
+    None
+}
+
+/// workspace 包的入口文件：优先源码入口（`source`、`src/index.*`），其次 `exports` / `module` / `main`
+fn resolve_package_entry(dir: &Path) -> Option<PathBuf> {
+    let json = read_json(&dir.join("package.json"));
+
+    if let Some(source) = json.as_ref().and_then(|j| j.get("source")).and_then(|v| v.as_str()) {
+        if let Some(resolved) = resolve_file(&dir.join(source)) {
+            return Some(resolved);
+        }
+    }
+
+    for ext in RESOLVE_EXTENSIONS {
+        let candidate = dir.join("src").join(format!("index.{}", ext));
+        if candidate.is_file() {
+            return Some(candidate);
+        }
+    }
+
+    resolve_file(dir)
+}
+
+/// workspace 包对外公开的全部入口文件：主入口以及 `exports` 中的子路径导出
+///
+/// 指向构建产物且无法对应到源码的入口被忽略，通配符子路径（`./*`）不展开
+pub fn package_entry_files(dir: &Path) -> Vec<PathBuf> {
+    let mut files: Vec<PathBuf> = resolve_package_entry(dir).into_iter().collect();
+
+    let mut targets = Vec::new();
+    if let Some(json) = read_json(&dir.join("package.json")) {
+        if let Some(exports) = json.get("exports") {
+            collect_export_targets(exports, &mut targets);
+        }
+        for key in ["source", "module", "main"] {
+            if let Some(target) = json.get(key).and_then(|v| v.as_str()) {
+                targets.push(target.to_string());
+            }
+        }
+    }
+
+    for target in targets {
+        if target.contains('*') {
+            continue;
+        }
+        if let Some(resolved) = resolve_file(&dir.join(&target)) {
+            if !files.contains(&resolved) {
+                files.push(resolved);
+            }
+        }
+    }
+
+    files
+}
+
+/// `exports` 中的全部目标路径（条件导出逐层展开）
+fn collect_export_targets(value: &serde_json::Value, targets: &mut Vec<String>) {
+    match value {
+        serde_json::Value::String(s) => targets.push(s.clone()),
+        serde_json::Value::Array(items) => items.iter().for_each(|v| collect_export_targets(v, targets)),
+        serde_json::Value::Object(map) => map.values().for_each(|v| collect_export_targets(v, targets)),
+        _ => {}
+    }
+}
+
+/// package.json 中的入口字段
+fn package_json_entry(dir: &Path) -> Option<String> {
+    let json = read_json(&dir.join("package.json"))?;
+
+    let export_entry = json.get("exports").and_then(|exports| {
+        let root = exports.get(".").unwrap_or(exports);
+        root.as_str().map(|s| s.to_string()).or_else(|| {
+            ["source", "import", "default", "require"]
+                .iter()
+                .find_map(|key| root.get(key).and_then(|v| v.as_str()))
+                .map(|s| s.to_string())
+        })
+    });
+
+    export_entry.or_else(|| {
+        ["module", "main"]
+            .iter()
+            .find_map(|key| json.get(key).and_then(|v| v.as_str()))
+            .map(|s| s.to_string())
+    })
+}
+
+/// 读取 tsconfig 并合并 `extends` 链，子配置覆盖父配置
+fn load_tsconfig(path: &Path, depth: usize) -> TsConfigOptions {
+    let Some(json) = read_json(path) else {
+        return TsConfigOptions::default();
+    };
+    let config_dir = path.parent().unwrap_or(Path::new("")).to_path_buf();
+
+    // 1. 先合并父配置（TS 5 起 extends 可以是数组，后面的覆盖前面的）
+    let mut options = TsConfigOptions::default();
+    if depth < MAX_EXTENDS_DEPTH {
+        let parents: Vec<String> = match json.get("extends") {
+            Some(serde_json::Value::String(s)) => vec![s.clone()],
+            Some(serde_json::Value::Array(items)) => items
+                .iter()
+                .filter_map(|v| v.as_str().map(|s| s.to_string()))
+                .collect(),
+            _ => Vec::new(),
+        };
+        for parent in parents {
+            if let Some(parent_path) = resolve_extends(&parent, &config_dir) {
+                let parent_options = load_tsconfig(&parent_path, depth + 1);
+                if parent_options.base_url.is_some() {
+                    options.base_url = parent_options.base_url;
+                }
+                if parent_options.paths.is_some() {
+                    options.paths = parent_options.paths;
+                }
+            }
+        }
+    }
+
+    // 2. 当前配置
+    let Some(compiler_options) = json.get("compilerOptions") else {
+        return options;
+    };
+    if let Some(base_url) = compiler_options.get("baseUrl").and_then(|v| v.as_str()) {
+        options.base_url = Some(normalize_path(&config_dir.join(base_url)));
+    }
+    if let Some(paths) = compiler_options.get("paths").and_then(|v| v.as_object()) {
+        let entries = paths
+            .iter()
+            .map(|(pattern, targets)| {
+                let targets = targets
+                    .as_array()
+                    .map(|items| {
+                        items
+                            .iter()
+                            .filter_map(|v| v.as_str().map(|s| s.to_string()))
+                            .collect()
+                    })
+                    .unwrap_or_default();
+                (pattern.clone(), targets)
+            })
+            .collect();
+        options.paths = Some(TsPaths {
+            entries,
+            dir: config_dir,
+        });
+    }
+
+    options
+}
+
+/// 解析 `extends` 指向的配置文件（相对路径或 node_modules 中的包）
+fn resolve_extends(spec: &str, config_dir: &Path) -> Option<PathBuf> {
+    let candidates = |base: PathBuf| {
+        let with_json = PathBuf::from(format!("{}.json", base.to_string_lossy()));
+        [base.clone(), with_json, base.join("tsconfig.json")]
+    };
+
+    if spec.starts_with('.') || Path::new(spec).is_absolute() {
+        return candidates(config_dir.join(spec))
+            .into_iter()
+            .find(|p| p.is_file());
+    }
+
+    // 包名：向上查找 node_modules
+    let mut current = Some(config_dir);
+    while let Some(dir) = current {
+        if let Some(found) = candidates(dir.join("node_modules").join(spec))
+            .into_iter()
+            .find(|p| p.is_file())
+        {
+            return Some(found);
+        }
+        current = dir.parent();
+    }
+
+    None
+}
+
+/// 从 vite / webpack 配置中读取可静态确定的 alias
+///
+/// 支持对象写法 `alias: { '@': path.resolve(__dirname, 'src') }`
+/// 与数组写法 `alias: [{ find: '@', replacement: fileURLToPath(new URL('./src', import.meta.url)) }]`，
+/// 值中无法静态求值的条目会被忽略
+fn load_bundler_aliases(path: &Path) -> Vec<AliasRule> {
+    let Ok(content) = fs::read_to_string(path) else {
+        return Vec::new();
+    };
+    let config_dir = path.parent().unwrap_or(Path::new(""));
+    let mut rules = Vec::new();
+
+    for m in ALIAS_OBJECT_REGEX.find_iter(&content) {
+        let body = balanced_body(&content[m.end() - 1..]);
+        for entry in split_top_level(body, ',') {
+            let Some((key, value)) = split_once_top_level(entry, ':') else {
+                continue;
+            };
+            let key = key.trim().trim_matches(|c| c == '\'' || c == '"' || c == '`');
+            if let Some(target) = static_path_value(value, config_dir) {
+                push_bundler_alias(&mut rules, key, target);
+            }
+        }
+    }
+
+    for m in ALIAS_ARRAY_REGEX.find_iter(&content) {
+        let body = balanced_body(&content[m.end() - 1..]);
+        for entry in split_top_level(body, ',') {
+            let Some(find) = FIND_REGEX.captures(entry) else {
+                continue;
+            };
+            let Some(replacement) = REPLACEMENT_REGEX.find(entry) else {
+                continue;
+            };
+            if let Some(target) = static_path_value(&entry[replacement.end()..], config_dir) {
+                push_bundler_alias(&mut rules, &find[1], target);
+            }
+        }
+    }
+
+    rules
+}
+
+/// 打包工具 alias 同时匹配 `key` 本身与 `key/...`；webpack 的 `key$` 只做精确匹配
+fn push_bundler_alias(rules: &mut Vec<AliasRule>, key: &str, target: PathBuf) {
+    if key.is_empty() {
+        return;
+    }
+
+    if let Some(exact) = key.strip_suffix('$') {
+        rules.push(AliasRule {
+            pattern: exact.to_string(),
+            targets: vec![target],
+        });
+        return;
+    }
+
+    rules.push(AliasRule {
+        pattern: key.to_string(),
+        targets: vec![target.clone()],
+    });
+    rules.push(AliasRule {
+        pattern: format!("{}/*", key.trim_end_matches('/')),
+        targets: vec![target.join("*")],
+    });
+}
+
+/// 静态求值 alias 的值：拼接其中的字符串字面量
+///
+/// 带 `__dirname` / `import.meta.url` / `process.cwd()` 或以 `.` 开头的路径相对配置文件目录，
+/// 以 `/` 开头且不存在的路径视为相对配置文件目录（vite 常见的 `'/src'` 写法）
+fn static_path_value(value: &str, config_dir: &Path) -> Option<PathBuf> {
+    let literals: Vec<&str> = STRING_LITERAL_REGEX
+        .captures_iter(value)
+        .map(|c| c.get(1).map(|m| m.as_str()).unwrap_or(""))
+        .filter(|s| !s.is_empty())
+        .collect();
+    if literals.is_empty() {
+        return None;
+    }
+
+    let mut path = config_dir.to_path_buf();
+    for literal in literals {
+        let literal = literal.trim_start_matches("file://");
+        if Path::new(literal).is_absolute() && Path::new(literal).exists() {
+            path = PathBuf::from(literal);
+        } else {
+            path = path.join(literal.trim_start_matches('/'));
+        }
+    }
+
+    Some(normalize_path(&path))
+}
+
+/// 从开括号开始截取到匹配的闭括号，返回括号内的内容
+fn balanced_body(text: &str) -> &str {
+    let mut depth = 0usize;
+    let mut quote: Option<char> = None;
+
+    for (i, c) in text.char_indices() {
+        if let Some(q) = quote {
+            if c == q {
+                quote = None;
+            }
+            continue;
+        }
+        match c {
+            '\'' | '"' | '`' => quote = Some(c),
+            '{' | '[' | '(' => depth += 1,
+            '}' | ']' | ')' => {
+                depth = depth.saturating_sub(1);
+                if depth == 0 {
+                    return &text[1..i];
+                }
+            }
+            _ => {}
+        }
+    }
+
+    text.get(1..).unwrap_or("")
+}
+
+/// 不在括号和字符串内的分隔符位置
+fn top_level_positions(text: &str, separator: char) -> Vec<usize> {
+    let mut positions = Vec::new();
+    let mut depth = 0usize;
+    let mut quote: Option<char> = None;
+
+    for (i, c) in text.char_indices() {
+        if let Some(q) = quote {
+            if c == q {
+                quote = None;
+            }
+            continue;
+        }
+        match c {
+            '\'' | '"' | '`' => quote = Some(c),
+            '{' | '[' | '(' => depth += 1,
+            '}' | ']' | ')' => depth = depth.saturating_sub(1),
+            c if c == separator && depth == 0 => positions.push(i),
+            _ => {}
+        }
+    }
+
+    positions
+}
+
+/// 按顶层分隔符拆分，忽略空白片段
+fn split_top_level(text: &str, separator: char) -> Vec<&str> {
+    let mut parts = Vec::new();
+    let mut start = 0;
+
+    for position in top_level_positions(text, separator) {
+        parts.push(&text[start..position]);
+        start = position + separator.len_utf8();
+    }
+    parts.push(&text[start..]);
+
+    parts.into_iter().filter(|p| !p.trim().is_empty()).collect()
+}
+
+/// 在第一个顶层分隔符处拆成两段
+fn split_once_top_level(text: &str, separator: char) -> Option<(&str, &str)> {
+    let position = *top_level_positions(text, separator).first()?;
+    Some((&text[..position], &text[position + separator.len_utf8()..]))
+}
+
+/// 读取 JSON（允许 tsconfig 中常见的注释与尾随逗号）
+fn read_json(path: &Path) -> Option<serde_json::Value> {
+    let content = fs::read_to_string(path).ok()?;
+    serde_json::from_str(&strip_jsonc(&content)).ok()
+}
+
+/// 去掉 JSONC 中的注释和尾随逗号
+fn strip_jsonc(content: &str) -> String {
+    let chars: Vec<char> = content.chars().collect();
+    let mut result = String::with_capacity(content.len());
+    let mut i = 0;
+    let mut in_string = false;
+
+    while i < chars.len() {
+        let c = chars[i];
+        if in_string {
+            result.push(c);
+            if c == '\\' && i + 1 < chars.len() {
+                result.push(chars[i + 1]);
+                i += 1;
+            } else if c == '"' {
+                in_string = false;
+            }
+        } else if c == '"' {
+            in_string = true;
+            result.push(c);
+        } else if c == '/' && chars.get(i + 1) == Some(&'/') {
+            while i < chars.len() && chars[i] != '\n' {
+                i += 1;
+            }
+            continue;
+        } else if c == '/' && chars.get(i + 1) == Some(&'*') {
+            i += 2;
+            while i + 1 < chars.len() && !(chars[i] == '*' && chars[i + 1] == '/') {
+                i += 1;
+            }
+            i += 2;
+            continue;
+        } else if c == ',' {
+            // 尾随逗号：后面只有空白就遇到 } 或 ]
+            let next = chars[i + 1..].iter().find(|c| !c.is_whitespace());
+            if !matches!(next, Some('}') | Some(']')) {
+                result.push(c);
+            }
+        } else {
+            result.push(c);
+        }
+        i += 1;
+    }
+
+    result
+}
+
+/// 按词法规则去掉路径中的 `.` 与 `..`
+pub fn normalize_path(path: &Path) -> PathBuf {
+    let mut result = PathBuf::new();
+
+    for component in path.components() {
+        match component {
+            Component::CurDir => {}
+            Component::ParentDir => {
+                if !result.pop() {
+                    result.push("..");
+                }
+            }
+            other => result.push(other.as_os_str()),
+        }
+    }
+
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn write(root: &Path, file: &str, content: &str) {
+        let path = root.join(file);
+        fs::create_dir_all(path.parent().unwrap()).unwrap();
+        fs::write(path, content).unwrap();
+    }
+
+    #[test]
+    fn test_resolve_tsconfig_paths_and_bundler_alias() {
+        let root = Path::new("/tmp/test_module_resolver_alias");
+        fs::remove_dir_all(root).ok();
+
+        write(
+            root,
+            "tsconfig.base.json",
+            r#"{
+                // 公共配置
+                "compilerOptions": {
+                    "baseUrl": ".",
+                    "paths": { "@/*": ["src/*"], "@shared/*": ["shared/*", "src/shared/*"], },
+                },
+            }"#,
+        );
+        write(root, "tsconfig.json", r#"{ "extends": "./tsconfig.base" }"#);
+        write(
+            root,
+            "vite.config.ts",
+            r#"import { fileURLToPath, URL } from 'node:url'
+export default defineConfig({
+  resolve: {
+    alias: {
+      '~': fileURLToPath(new URL('./src', import.meta.url)),
+      'utils$': path.resolve(__dirname, 'src/utils/index.ts'),
+    },
+  },
+})"#,
+        );
+        write(root, "src/components/Header.vue", "<template><div/></template>");
+        write(root, "src/shared/format.ts", "export const a = 1;");
+        write(root, "src/utils/index.ts", "export const b = 1;");
+        write(root, "src/views/Home.ts", "");
+        write(root, "src/api/order.ts", "");
+
+        let resolver = ModuleResolver::with_workspace(root, None);
+        let importer = root.join("src/views/Home.ts");
+
+        assert_eq!(
+            resolver.resolve("@/components/Header.vue", &importer),
+            Some(root.join("src/components/Header.vue"))
+        );
+        assert_eq!(
+            resolver.resolve("@shared/format", &importer),
+            Some(root.join("src/shared/format.ts"))
+        );
+        assert_eq!(resolver.resolve("~/utils", &importer), Some(root.join("src/utils/index.ts")));
+        assert_eq!(resolver.resolve("utils", &importer), Some(root.join("src/utils/index.ts")));
+        assert_eq!(resolver.resolve("../api/order.js", &importer), Some(root.join("src/api/order.ts")));
+        // baseUrl
+        assert_eq!(resolver.resolve("src/api/order", &importer), Some(root.join("src/api/order.ts")));
+        assert_eq!(resolver.resolve("vue", &importer), None);
+        assert_eq!(resolver.entity_file(&root.join("src/api/order.ts")), "src/api/order.ts");
+
+        fs::remove_dir_all(root).ok();
+    }
+
+    #[test]
+    fn test_resolve_workspace_packages() {
+        let root = Path::new("/tmp/test_module_resolver_workspace");
+        fs::remove_dir_all(root).ok();
+
+        write(root, "pnpm-workspace.yaml", "packages:\n  - packages/*\n");
+        write(
+            root,
+            "packages/shared-ui/package.json",
+            r#"{ "name": "@company/shared-ui", "main": "dist/index.js" }"#,
+        );
+        write(root, "packages/shared-ui/src/index.ts", "export * from './button';");
+        write(root, "packages/shared-ui/src/button.ts", "export function Button() {}");
+        write(root, "packages/app/package.json", r#"{ "name": "app" }"#);
+        write(root, "packages/app/src/main.ts", "");
+
+        let app = root.join("packages/app");
+        let resolver = ModuleResolver::new(&app);
+        let importer = app.join("src/main.ts");
+
+        assert_eq!(
+            resolver.resolve("@company/shared-ui", &importer),
+            Some(root.join("packages/shared-ui/src/index.ts"))
+        );
+        assert_eq!(
+            resolver.resolve("@company/shared-ui/button", &importer),
+            Some(root.join("packages/shared-ui/src/button.ts"))
+        );
+        assert_eq!(resolver.resolve("@company/shared", &importer), None);
+
+        fs::remove_dir_all(root).ok();
+    }
+}
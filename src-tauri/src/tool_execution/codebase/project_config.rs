@@ -0,0 +1,182 @@
+//! 项目级配置
+//!
+//! 从项目根目录的 `codebase.config.json` 读取扫描、架构检查、LLM 与标签表等配置，
+//! Tauri 命令与 MCP 工具共用同一份配置
+
+use super::architecture::ArchitectureConfig;
+use super::enrichment::{LlmConfig, TagTaxonomy};
+use super::file_walker::ScanConfig;
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// 项目配置文件名
+pub const PROJECT_CONFIG_FILE: &str = "codebase.config.json";
+
+/// 默认输出目录（相对项目根目录）
+pub const DEFAULT_OUTPUT_DIR: &str = "src/data";
+
+/// 项目配置
+///
+/// 示例：
+/// ```json
+/// {
+///   "output_dir": "codebase",
+///   "scan": {
+///     "exclude": ["**/*.d.ts", "**/mocks/**"],
+///     "respect_gitignore": true
+///   },
+///   "architecture": {
+///     "rules": [{ "from": ["src/components/**"], "to": ["src/views/**"] }]
+///   },
+///   "llm": {
+///     "provider": "openai",
+///     "base_url": "http://localhost:11434/v1",
+///     "model": "qwen2.5-coder:7b"
+///   },
+///   "taxonomy": {
+///     "tags": [{ "name": "订单", "aliases": ["order", "订单管理"] }]
+///   }
+/// }
+/// ```
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ProjectConfig {
+    /// 输出目录（相对项目根目录），默认为 "src/data"
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub output_dir: Option<String>,
+
+    /// 扫描配置
+    #[serde(default)]
+    pub scan: ScanOverrides,
+
+    /// 架构检查配置（循环依赖与分层规则）
+    #[serde(default)]
+    pub architecture: ArchitectureConfig,
+
+    /// 富化使用的 LLM 提供方
+    #[serde(default)]
+    pub llm: LlmConfig,
+
+    /// 项目标签表，LLM 只能从中选择标签；为空时使用首次富化自动生成的 `tag_taxonomy.json`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub taxonomy: Option<TagTaxonomy>,
+}
+
+/// 扫描配置覆盖项
+///
+/// 未设置的字段沿用 `ScanConfig::default()`，设置的字段整体替换默认值
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ScanOverrides {
+    /// 支持的文件扩展名
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub extensions: Option<Vec<String>>,
+
+    /// 按目录名忽略的目录
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub ignore_dirs: Option<Vec<String>>,
+
+    /// 包含的 glob（相对项目根目录），为空表示包含全部
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub include: Option<Vec<String>>,
+
+    /// 排除的 glob（相对项目根目录）
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub exclude: Option<Vec<String>>,
+
+    /// 是否遵循 .gitignore / .ignore
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub respect_gitignore: Option<bool>,
+
+    /// 最大并行处理数
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_parallel: Option<usize>,
+
+    /// 是否扫描 workspace 依赖
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub include_workspace: Option<bool>,
+}
+
+impl ProjectConfig {
+    /// 配置文件路径
+    pub fn path_for(project_path: &Path) -> PathBuf {
+        project_path.join(PROJECT_CONFIG_FILE)
+    }
+
+    /// 加载项目配置，配置文件不存在时返回默认配置
+    pub fn load<P: AsRef<Path>>(project_path: P) -> Result<Self> {
+        let config_path = Self::path_for(project_path.as_ref());
+        if !config_path.exists() {
+            return Ok(Self::default());
+        }
+
+        let content = fs::read_to_string(&config_path)
+            .context(format!("无法读取配置文件: {}", config_path.display()))?;
+        let config = serde_json::from_str(&content)
+            .context(format!("解析配置文件失败: {}", config_path.display()))?;
+
+        println!("⚙️  加载项目配置: {}", config_path.display());
+        Ok(config)
+    }
+
+    /// 输出目录（相对项目根目录）
+    pub fn output_dir(&self) -> &str {
+        self.output_dir.as_deref().unwrap_or(DEFAULT_OUTPUT_DIR)
+    }
+
+    /// 合并默认值得到扫描配置
+    pub fn scan_config(&self) -> ScanConfig {
+        let mut config = ScanConfig::default();
+        let scan = self.scan.clone();
+
+        if let Some(extensions) = scan.extensions {
+            config.extensions = extensions;
+        }
+        if let Some(ignore_dirs) = scan.ignore_dirs {
+            config.ignore_dirs = ignore_dirs;
+        }
+        if let Some(include) = scan.include {
+            config.include_globs = include;
+        }
+        if let Some(exclude) = scan.exclude {
+            config.exclude_globs = exclude;
+        }
+        if let Some(respect_gitignore) = scan.respect_gitignore {
+            config.respect_gitignore = respect_gitignore;
+        }
+        if let Some(max_parallel) = scan.max_parallel {
+            config.max_parallel = max_parallel;
+        }
+        if let Some(include_workspace) = scan.include_workspace {
+            config.include_workspace = include_workspace;
+        }
+
+        config
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_load_project_config() {
+        let root = Path::new("/tmp/test_project_config");
+        fs::create_dir_all(root).unwrap();
+        fs::write(
+            ProjectConfig::path_for(root),
+            r#"{ "output_dir": "codebase", "scan": { "exclude": ["**/mocks/**"], "max_parallel": 2 } }"#,
+        )
+        .unwrap();
+
+        let config = ProjectConfig::load(root).unwrap();
+        assert_eq!(config.output_dir(), "codebase");
+
+        let scan = config.scan_config();
+        assert_eq!(scan.exclude_globs, vec!["**/mocks/**".to_string()]);
+        assert_eq!(scan.max_parallel, 2);
+        assert_eq!(scan.extensions, ScanConfig::default().extensions);
+
+        fs::remove_dir_all(root).ok();
+    }
+}
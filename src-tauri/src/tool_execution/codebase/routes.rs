@@ -0,0 +1,197 @@
+//! 路由表提取
+//!
+//! 从 Vue Router / React Router 配置中提取路由树：`createRouter({ routes })`、带 `children` 的路由数组、
+//! `createBrowserRouter([...])` 与 `<Route>` 元素。每条路由的路径、名称、meta 都关联到页面组件实体，
+//! 用于回答「哪个 URL 显示这个组件」，也作为死代码检测的入口。
+//!
+//! 从其他文件导入后以 `children: userRoutes` 挂载的路由数组会在其所在文件中作为独立的路由树出现，
+//! 完整路径不含父路由前缀。
+
+use super::enrichment::{Route, StaticAnalyzer};
+use super::extractors::CodeEntity;
+use super::workspace::WorkspaceInfo;
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+
+/// 项目的路由表
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct RouteMap {
+    /// 各文件中的顶层路由（子路由嵌套在 `children` 中）
+    pub routes: Vec<Route>,
+
+    /// 包含路由定义的文件
+    pub files: Vec<String>,
+}
+
+impl RouteMap {
+    /// 全部路由（先序遍历，子路由紧跟父路由）
+    pub fn flatten(&self) -> Vec<&Route> {
+        fn walk<'a>(routes: &'a [Route], result: &mut Vec<&'a Route>) {
+            for route in routes {
+                result.push(route);
+                walk(&route.children, result);
+            }
+        }
+
+        let mut result = Vec::new();
+        walk(&self.routes, &mut result);
+        result
+    }
+
+    /// 显示指定组件的路由
+    pub fn routes_for_component(&self, entity_id: &str) -> Vec<&Route> {
+        self.flatten()
+            .into_iter()
+            .filter(|route| route.component.as_deref() == Some(entity_id))
+            .collect()
+    }
+
+    /// 被路由引用的组件实体 ID
+    pub fn components(&self) -> Vec<&str> {
+        let mut components: Vec<&str> = self.flatten().iter().filter_map(|r| r.component.as_deref()).collect();
+        components.sort();
+        components.dedup();
+        components
+    }
+
+    /// 路由总数（含子路由）
+    pub fn len(&self) -> usize {
+        self.flatten().len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.routes.is_empty()
+    }
+}
+
+/// 提取项目的路由表
+///
+/// `files` 为全部源文件（路由配置文件通常不产出组件实体），为空时使用实体所在的文件；
+/// `workspace` 为扫描时已解析的 workspace 信息
+pub fn extract_routes(
+    root: &Path,
+    entities: Vec<CodeEntity>,
+    files: &[String],
+    workspace: Option<&WorkspaceInfo>,
+) -> RouteMap {
+    let files: Vec<String> = if files.is_empty() {
+        let mut files: Vec<String> = entities.iter().map(|e| e.file.clone()).collect();
+        files.sort();
+        files.dedup();
+        files
+    } else {
+        files.to_vec()
+    };
+
+    let analyzer = StaticAnalyzer::with_workspace(root, entities, workspace);
+    let mut map = RouteMap::default();
+    for file in &files {
+        let routes = analyzer.routes_of_file(file);
+        if !routes.is_empty() {
+            map.files.push(file.clone());
+            map.routes.extend(routes);
+        }
+    }
+
+    map
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+
+    #[test]
+    fn test_extracts_vue_and_react_routes() {
+        let root = Path::new("/tmp/test_routes");
+        fs::remove_dir_all(root).ok();
+        fs::create_dir_all(root.join("src/router")).unwrap();
+        fs::create_dir_all(root.join("src/views")).unwrap();
+        fs::create_dir_all(root.join("web/pages")).unwrap();
+
+        for view in ["Layout", "Home", "OrderList", "OrderDetail"] {
+            fs::write(root.join(format!("src/views/{}.vue", view)), "<template><div /></template>\n").unwrap();
+        }
+        fs::write(
+            root.join("src/router/index.ts"),
+            r#"import { createRouter, createWebHistory } from 'vue-router';
+import Layout from '../views/Layout.vue';
+import Home from '../views/Home.vue';
+
+const OrderDetail = () => import('../views/OrderDetail.vue');
+
+export const router = createRouter({
+  history: createWebHistory(),
+  routes: [
+    {
+      path: '/',
+      component: Layout,
+      children: [
+        { path: '', name: 'home', component: Home },
+        {
+          path: 'orders',
+          name: 'orders',
+          component: () => import('../views/OrderList.vue'),
+          meta: { title: '订单', auth: true, roles: ['admin'] },
+        },
+        { path: 'orders/:id', name: 'order-detail', component: OrderDetail },
+      ],
+    },
+    { path: '/:pathMatch(.*)*', redirect: '/' },
+  ],
+});
+"#,
+        )
+        .unwrap();
+        fs::write(
+            root.join("web/pages/Settings.tsx"),
+            "export function Settings() { return <div />; }\n",
+        )
+        .unwrap();
+        fs::write(
+            root.join("web/App.tsx"),
+            r#"import { Routes, Route } from 'react-router-dom';
+import { Settings } from './pages/Settings';
+
+export function App() {
+  return (
+    <Routes>
+      <Route path="/admin">
+        <Route path="settings" element={<Settings />} />
+      </Route>
+    </Routes>
+  );
+}
+"#,
+        )
+        .unwrap();
+
+        let walker = crate::tool_execution::codebase::FileWalker::with_default();
+        let (entities, _, manifest) = walker.extract_incremental(root.to_str().unwrap(), None).unwrap();
+        let files: Vec<String> = manifest.files.keys().cloned().collect();
+        let component = |file: &str| entities.iter().find(|e| e.file == file && e.parent.is_none()).unwrap().id.clone();
+        let list = component("src/views/OrderList.vue");
+        let detail = component("src/views/OrderDetail.vue");
+        let settings = component("web/pages/Settings.tsx");
+
+        let map = extract_routes(root, entities, &files, None);
+        assert_eq!(map.files, vec!["src/router/index.ts", "web/App.tsx"]);
+
+        let paths: Vec<&str> = map.flatten().iter().map(|r| r.full_path.as_str()).collect();
+        assert_eq!(
+            paths,
+            vec!["/", "/", "/orders", "/orders/:id", "/:pathMatch(.*)*", "/admin", "/admin/settings"]
+        );
+
+        let orders = map.routes_for_component(&list);
+        assert_eq!(orders.len(), 1);
+        assert!(orders[0].lazy);
+        assert_eq!(orders[0].name.as_deref(), Some("orders"));
+        assert_eq!(orders[0].meta.as_ref().unwrap()["roles"][0], "admin");
+        assert_eq!(map.routes_for_component(&detail)[0].component_ref.as_deref(), Some("../views/OrderDetail.vue"));
+        assert_eq!(map.routes_for_component(&settings)[0].full_path, "/admin/settings");
+        assert_eq!(map.components().len(), 5);
+
+        fs::remove_dir_all(root).ok();
+    }
+}
@@ -0,0 +1,284 @@
+//! 增量扫描清单
+//!
+//! 记录每个源文件的 mtime、大小、内容哈希以及该文件产出的实体，
+//! 供 FileWalker 在重扫时只处理发生变化的文件
+
+use super::extractors::CodeEntity;
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::UNIX_EPOCH;
+
+/// 清单格式版本
+///
+/// 提取器的输出结构或判定逻辑发生变化时需要递增，旧清单会被整体丢弃并触发全量扫描
+pub const MANIFEST_VERSION: u32 = 7;
+
+/// 扫描清单
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ScanManifest {
+    /// 清单格式版本
+    pub version: u32,
+
+    /// 项目路径
+    pub project_path: String,
+
+    /// 最近一次更新时间
+    pub updated_at: String,
+
+    /// 文件记录（key 为相对项目根目录的路径，与 CodeEntity::file 一致）
+    pub files: BTreeMap<String, FileRecord>,
+}
+
+/// 单个文件的扫描记录
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FileRecord {
+    /// 修改时间（Unix 毫秒）
+    pub mtime: u64,
+
+    /// 文件大小（字节）
+    pub size: u64,
+
+    /// 文件内容的 SHA-256
+    pub hash: String,
+
+    /// 该文件产出的实体
+    pub entities: Vec<CodeEntity>,
+
+    /// 实体 ID 到其代码片段哈希的映射，用于判断实体本身是否变化
+    #[serde(default)]
+    pub entity_hashes: BTreeMap<String, String>,
+}
+
+/// 文件的快速指纹（mtime + size）
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FileStamp {
+    pub mtime: u64,
+    pub size: u64,
+}
+
+impl FileStamp {
+    /// 读取文件的 mtime 和大小
+    pub fn of(path: &Path) -> Result<Self> {
+        let metadata =
+            fs::metadata(path).context(format!("无法读取文件元数据: {}", path.display()))?;
+        let mtime = metadata
+            .modified()
+            .ok()
+            .and_then(|t| t.duration_since(UNIX_EPOCH).ok())
+            .map(|d| d.as_millis() as u64)
+            .unwrap_or(0);
+
+        Ok(Self {
+            mtime,
+            size: metadata.len(),
+        })
+    }
+}
+
+impl ScanManifest {
+    /// 创建空清单
+    pub fn new(project_path: &str) -> Self {
+        Self {
+            version: MANIFEST_VERSION,
+            project_path: project_path.to_string(),
+            updated_at: chrono::Utc::now().to_rfc3339(),
+            files: BTreeMap::new(),
+        }
+    }
+
+    /// 清单文件路径：`<output_dir>/scan_manifest_<project>.json`
+    pub fn path_for(output_dir: &Path, project_name: &str) -> PathBuf {
+        output_dir.join(format!("scan_manifest_{}.json", project_name))
+    }
+
+    /// 加载清单
+    ///
+    /// 文件不存在、无法解析或版本不匹配时返回 `None`，调用方应退回全量扫描
+    pub fn load(path: &Path) -> Option<Self> {
+        let content = fs::read_to_string(path).ok()?;
+
+        match serde_json::from_str::<ScanManifest>(&content) {
+            Ok(manifest) if manifest.version == MANIFEST_VERSION => Some(manifest),
+            Ok(manifest) => {
+                println!(
+                    "ℹ️  扫描清单版本不匹配 ({} != {})，将执行全量扫描",
+                    manifest.version, MANIFEST_VERSION
+                );
+                None
+            }
+            Err(e) => {
+                eprintln!("⚠️  解析扫描清单失败 {}: {}", path.display(), e);
+                None
+            }
+        }
+    }
+
+    /// 保存清单
+    pub fn save(&self, path: &Path) -> Result<()> {
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent).context(format!("无法创建目录: {}", parent.display()))?;
+        }
+
+        let json = serde_json::to_string_pretty(self).context("序列化扫描清单失败")?;
+        fs::write(path, json).context(format!("写入扫描清单失败: {}", path.display()))?;
+
+        Ok(())
+    }
+
+    /// 对比上一份清单，计算实体的新增、删除和变化
+    ///
+    /// 实体以 (文件, ID) 标识；同一实体的代码片段哈希不同即视为变化
+    pub fn diff(&self, previous: &ScanManifest) -> EntityChanges {
+        let before = previous.entity_fingerprints();
+        let after = self.entity_fingerprints();
+
+        let mut changes = EntityChanges::default();
+        for (key, hash) in &after {
+            match before.get(key) {
+                None => changes.added.push(key.1.to_string()),
+                Some(old_hash) if old_hash != hash => changes.changed.push(key.1.to_string()),
+                Some(_) => {}
+            }
+        }
+        for key in before.keys() {
+            if !after.contains_key(key) {
+                changes.removed.push(key.1.to_string());
+            }
+        }
+
+        changes
+    }
+
+    /// (文件, 实体 ID) -> 代码片段哈希
+    fn entity_fingerprints(&self) -> BTreeMap<(&str, &str), &str> {
+        let mut fingerprints = BTreeMap::new();
+        for (path, record) in &self.files {
+            for entity in &record.entities {
+                let hash = record
+                    .entity_hashes
+                    .get(&entity.id)
+                    .map(|h| h.as_str())
+                    .unwrap_or("");
+                fingerprints.insert((path.as_str(), entity.id.as_str()), hash);
+            }
+        }
+        fingerprints
+    }
+}
+
+/// 两次扫描之间的实体变化
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct EntityChanges {
+    /// 新增的实体 ID
+    pub added: Vec<String>,
+
+    /// 删除的实体 ID
+    pub removed: Vec<String>,
+
+    /// 代码发生变化的实体 ID
+    pub changed: Vec<String>,
+}
+
+/// 计算内容的 SHA-256（十六进制）
+pub fn hash_content(content: &[u8]) -> String {
+    let digest = Sha256::digest(content);
+    digest.iter().map(|b| format!("{:02x}", b)).collect()
+}
+
+/// 计算实体所在行范围的代码哈希
+pub fn hash_entity_slice(content: &str, entity: &CodeEntity) -> String {
+    let start = entity.loc.start_line.saturating_sub(1);
+    let len = entity
+        .loc
+        .end_line
+        .saturating_sub(entity.loc.start_line)
+        .saturating_add(1);
+    let slice = content.lines().skip(start).take(len).collect::<Vec<_>>().join("\n");
+
+    hash_content(slice.as_bytes())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tool_execution::codebase::extractors::LocationInfo;
+
+    #[test]
+    fn test_hash_entity_slice_ignores_other_lines() {
+        let entity = CodeEntity {
+            id: "Function:foo".to_string(),
+            entity_type: "function".to_string(),
+            file: "a.ts".to_string(),
+            loc: LocationInfo::with_range(2, 3),
+            raw_name: "foo".to_string(),
+            ..Default::default()
+        };
+
+        let before = "// a\nfunction foo() {\n}\nconst x = 1;";
+        let after = "// b\nfunction foo() {\n}\nconst x = 2;";
+        let changed = "// a\nfunction foo() { return 1;\n}\nconst x = 1;";
+
+        assert_eq!(
+            hash_entity_slice(before, &entity),
+            hash_entity_slice(after, &entity)
+        );
+        assert_ne!(
+            hash_entity_slice(before, &entity),
+            hash_entity_slice(changed, &entity)
+        );
+    }
+
+    fn record(entities: &[(&str, &str)]) -> FileRecord {
+        FileRecord {
+            mtime: 1,
+            size: 2,
+            hash: hash_content(b"abc"),
+            entities: entities
+                .iter()
+                .map(|(id, _)| CodeEntity {
+                    id: id.to_string(),
+                    entity_type: "function".to_string(),
+                    file: "src/a.ts".to_string(),
+                    loc: LocationInfo::new(1),
+                    raw_name: id.to_string(),
+                    ..Default::default()
+                })
+                .collect(),
+            entity_hashes: entities
+                .iter()
+                .map(|(id, hash)| (id.to_string(), hash.to_string()))
+                .collect(),
+        }
+    }
+
+    #[test]
+    fn test_manifest_diff_and_roundtrip() {
+        let mut previous = ScanManifest::new("/tmp/project");
+        previous.files.insert(
+            "src/a.ts".to_string(),
+            record(&[("Function:keep", "h1"), ("Function:edit", "h2"), ("Function:gone", "h3")]),
+        );
+
+        let mut current = ScanManifest::new("/tmp/project");
+        current.files.insert(
+            "src/a.ts".to_string(),
+            record(&[("Function:keep", "h1"), ("Function:edit", "h2x"), ("Function:new", "h4")]),
+        );
+
+        let changes = current.diff(&previous);
+        assert_eq!(changes.added, vec!["Function:new".to_string()]);
+        assert_eq!(changes.removed, vec!["Function:gone".to_string()]);
+        assert_eq!(changes.changed, vec!["Function:edit".to_string()]);
+
+        let path = Path::new("/tmp/test_scan_manifest.json");
+        current.save(path).unwrap();
+        let loaded = ScanManifest::load(path).expect("加载清单失败");
+        assert_eq!(loaded.files.len(), 1);
+
+        fs::remove_file(path).ok();
+    }
+}
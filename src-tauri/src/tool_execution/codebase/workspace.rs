@@ -0,0 +1,505 @@
+//! Workspace（monorepo）解析
+//!
+//! 支持两种配置来源：
+//! - `pnpm-workspace.yaml`：`packages` 列表以及 `catalog` / `catalogs` 版本目录
+//! - `package.json` 的 `workspaces` 字段（数组或 `{ packages: [...] }`）
+//!
+//! 包模式按完整 glob 语义匹配（`*` 不跨目录、`**` 跨任意层级），
+//! 以 `!` 开头的模式表示排除。只有包含 package.json 的目录才算作 workspace 包
+
+use anyhow::{Context, Result};
+use globset::{GlobBuilder, GlobSet, GlobSetBuilder};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// 默认 catalog 的名称（对应 `catalog:` 字段和 `catalog:` 依赖写法）
+pub const DEFAULT_CATALOG: &str = "default";
+
+/// 版本目录：catalog 名 -> (依赖名 -> 版本)
+pub type Catalogs = BTreeMap<String, BTreeMap<String, String>>;
+
+/// package.json 中记录依赖的字段
+const DEPENDENCY_FIELDS: [&str; 4] = [
+    "dependencies",
+    "devDependencies",
+    "peerDependencies",
+    "optionalDependencies",
+];
+
+/// Workspace 信息
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WorkspaceInfo {
+    /// workspace 根目录
+    pub root: PathBuf,
+
+    /// workspace 包的路径列表
+    pub package_paths: Vec<PathBuf>,
+
+    /// 包名到路径的映射
+    pub package_map: BTreeMap<String, PathBuf>,
+
+    /// 配置中的包模式（保留 `!` 前缀）
+    #[serde(default)]
+    pub patterns: Vec<String>,
+
+    /// 包详情，按包路径排序
+    #[serde(default)]
+    pub packages: Vec<WorkspacePackage>,
+
+    /// 版本目录，默认 catalog 名为 `default`
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub catalogs: Catalogs,
+}
+
+/// 单个 workspace 包
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WorkspacePackage {
+    /// 包名
+    pub name: String,
+
+    /// 版本号
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub version: Option<String>,
+
+    /// 相对 workspace 根目录的路径（使用 `/`）
+    pub path: String,
+
+    /// 是否为私有包
+    #[serde(default)]
+    pub private: bool,
+
+    /// 对其他 workspace 包的依赖
+    #[serde(default)]
+    pub dependencies: Vec<WorkspaceDependency>,
+}
+
+/// 包之间的依赖
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct WorkspaceDependency {
+    /// 被依赖的包名
+    pub name: String,
+
+    /// 版本声明，如 `workspace:*`、`^1.2.0`
+    pub spec: String,
+
+    /// 声明所在字段，如 `dependencies`、`devDependencies`
+    pub kind: String,
+}
+
+/// pnpm-workspace.yaml 的结构
+#[derive(Debug, Default, Deserialize)]
+struct PnpmWorkspaceFile {
+    #[serde(default)]
+    packages: Vec<String>,
+
+    #[serde(default)]
+    catalog: BTreeMap<String, serde_yaml::Value>,
+
+    #[serde(default)]
+    catalogs: BTreeMap<String, BTreeMap<String, serde_yaml::Value>>,
+}
+
+impl WorkspaceInfo {
+    /// 从 `start_dir` 向上查找 workspace 根目录并解析
+    ///
+    /// `ignore_dirs` 中的目录（以及 node_modules）不会被当作包目录遍历
+    pub fn discover(start_dir: &Path, ignore_dirs: &[String]) -> Result<Option<Self>> {
+        match Self::find_root(start_dir) {
+            Some(root) => Ok(Some(Self::load(&root, ignore_dirs)?)),
+            None => Ok(None),
+        }
+    }
+
+    /// 查找 workspace 根目录：包含 pnpm-workspace.yaml 或带 `workspaces` 字段的 package.json
+    pub fn find_root(start_dir: &Path) -> Option<PathBuf> {
+        let mut current = Some(start_dir);
+
+        while let Some(dir) = current {
+            if dir.join("pnpm-workspace.yaml").exists() {
+                return Some(dir.to_path_buf());
+            }
+            if read_package_json(dir)
+                .map(|json| json.get("workspaces").is_some())
+                .unwrap_or(false)
+            {
+                return Some(dir.to_path_buf());
+            }
+            current = dir.parent();
+        }
+
+        None
+    }
+
+    /// 解析指定根目录的 workspace 配置
+    pub fn load(root: &Path, ignore_dirs: &[String]) -> Result<Self> {
+        let (patterns, catalogs) = Self::read_config(root)?;
+        let package_dirs = Self::resolve_patterns(root, &patterns, ignore_dirs)?;
+
+        // 1. 读取每个包的 package.json
+        let mut manifests = Vec::new();
+        for dir in &package_dirs {
+            if let Some(json) = read_package_json(dir) {
+                manifests.push((dir.clone(), json));
+            }
+        }
+
+        let package_map: BTreeMap<String, PathBuf> = manifests
+            .iter()
+            .filter_map(|(dir, json)| {
+                let name = json.get("name").and_then(|v| v.as_str())?;
+                Some((name.to_string(), dir.clone()))
+            })
+            .collect();
+
+        // 2. 收集包详情与包之间的依赖
+        let packages = manifests
+            .iter()
+            .filter_map(|(dir, json)| {
+                let name = json.get("name").and_then(|v| v.as_str())?;
+                Some(WorkspacePackage {
+                    name: name.to_string(),
+                    version: json.get("version").and_then(|v| v.as_str()).map(|s| s.to_string()),
+                    path: relative_path(dir, root),
+                    private: json.get("private").and_then(|v| v.as_bool()).unwrap_or(false),
+                    dependencies: Self::internal_dependencies(json, &package_map),
+                })
+            })
+            .collect();
+
+        Ok(Self {
+            root: root.to_path_buf(),
+            package_paths: package_dirs,
+            package_map,
+            patterns,
+            packages,
+            catalogs,
+        })
+    }
+
+    /// 按包名查找包
+    pub fn package(&self, name: &str) -> Option<&WorkspacePackage> {
+        self.packages.iter().find(|p| p.name == name)
+    }
+
+    /// 依赖指定包的所有 workspace 包
+    pub fn dependents(&self, name: &str) -> Vec<&WorkspacePackage> {
+        self.packages
+            .iter()
+            .filter(|p| p.dependencies.iter().any(|d| d.name == name))
+            .collect()
+    }
+
+    /// 文件所属的 workspace 包（取路径最深的包）
+    pub fn package_for_file(&self, file: &Path) -> Option<&WorkspacePackage> {
+        self.packages
+            .iter()
+            .filter(|p| file.starts_with(self.root.join(&p.path)))
+            .max_by_key(|p| p.path.len())
+    }
+
+    /// 将 `catalog:` / `catalog:<name>` 依赖声明解析为实际版本，其他写法返回 `None`
+    pub fn resolve_catalog(&self, dependency: &str, spec: &str) -> Option<&str> {
+        let catalog = spec.strip_prefix("catalog:")?.trim();
+        let catalog = if catalog.is_empty() { DEFAULT_CATALOG } else { catalog };
+
+        self.catalogs
+            .get(catalog)
+            .and_then(|entries| entries.get(dependency))
+            .map(|s| s.as_str())
+    }
+
+    /// 读取包模式与版本目录
+    fn read_config(root: &Path) -> Result<(Vec<String>, Catalogs)> {
+        let mut patterns = Vec::new();
+        let mut catalogs = BTreeMap::new();
+
+        // 1. pnpm-workspace.yaml
+        let pnpm_workspace_path = root.join("pnpm-workspace.yaml");
+        if pnpm_workspace_path.exists() {
+            let content = fs::read_to_string(&pnpm_workspace_path)
+                .context(format!("无法读取 {}", pnpm_workspace_path.display()))?;
+            // 空文件解析为 null，按空配置处理
+            let config: Option<PnpmWorkspaceFile> = serde_yaml::from_str(&content)
+                .context(format!("解析 {} 失败", pnpm_workspace_path.display()))?;
+            let config = config.unwrap_or_default();
+
+            println!("  从 pnpm-workspace.yaml 解析到模式: {:?}", config.packages);
+            patterns.extend(config.packages);
+
+            if !config.catalog.is_empty() {
+                catalogs.insert(DEFAULT_CATALOG.to_string(), scalar_map(config.catalog));
+            }
+            for (name, entries) in config.catalogs {
+                catalogs
+                    .entry(name)
+                    .or_insert_with(BTreeMap::new)
+                    .extend(scalar_map(entries));
+            }
+        }
+
+        // 2. package.json 的 workspaces 字段
+        if let Some(workspaces) = read_package_json(root).and_then(|json| json.get("workspaces").cloned()) {
+            let list = workspaces
+                .as_array()
+                .cloned()
+                .or_else(|| workspaces.get("packages").and_then(|v| v.as_array()).cloned())
+                .unwrap_or_default();
+            for pattern in list.iter().filter_map(|v| v.as_str()) {
+                println!("  从 package.json 解析到模式: {}", pattern);
+                patterns.push(pattern.to_string());
+            }
+        }
+
+        // 3. 没有任何模式时使用默认模式
+        if patterns.is_empty() {
+            patterns = vec![
+                "packages/*".to_string(),
+                "apps/*".to_string(),
+                "libs/*".to_string(),
+            ];
+            println!("  使用默认 workspace 模式");
+        }
+
+        Ok((patterns, catalogs))
+    }
+
+    /// 将包模式解析为包目录（按路径排序）
+    fn resolve_patterns(root: &Path, patterns: &[String], ignore_dirs: &[String]) -> Result<Vec<PathBuf>> {
+        let mut include = GlobSetBuilder::new();
+        let mut exclude = GlobSetBuilder::new();
+
+        for pattern in patterns {
+            let (negated, pattern) = match pattern.trim().strip_prefix('!') {
+                Some(rest) => (true, rest),
+                None => (false, pattern.trim()),
+            };
+            let pattern = normalize_pattern(pattern);
+            if pattern.is_empty() {
+                continue;
+            }
+
+            let glob = GlobBuilder::new(&pattern)
+                .literal_separator(true)
+                .build()
+                .context(format!("无效的 workspace 模式: {}", pattern))?;
+            if negated {
+                exclude.add(glob);
+            } else {
+                include.add(glob);
+            }
+        }
+
+        let include = include.build()?;
+        let exclude = exclude.build()?;
+
+        let mut results = Vec::new();
+        Self::walk_dirs(root, root, &include, &exclude, ignore_dirs, &mut results);
+        results.sort();
+        Ok(results)
+    }
+
+    /// 递归遍历目录（不跟随符号链接），收集匹配模式且包含 package.json 的目录
+    fn walk_dirs(
+        root: &Path,
+        dir: &Path,
+        include: &GlobSet,
+        exclude: &GlobSet,
+        ignore_dirs: &[String],
+        results: &mut Vec<PathBuf>,
+    ) {
+        let entries = match fs::read_dir(dir) {
+            Ok(entries) => entries,
+            Err(_) => return,
+        };
+
+        for entry in entries.flatten() {
+            // 不跟随符号链接，避免链接成环时无限递归
+            if !entry.file_type().map(|t| t.is_dir()).unwrap_or(false) {
+                continue;
+            }
+            let path = entry.path();
+
+            let dir_name = entry.file_name().to_string_lossy().to_string();
+            if dir_name == "node_modules" || dir_name.starts_with('.') || ignore_dirs.contains(&dir_name) {
+                continue;
+            }
+
+            let relative = relative_path(&path, root);
+            if include.is_match(&relative)
+                && !exclude.is_match(&relative)
+                && path.join("package.json").exists()
+            {
+                results.push(path.clone());
+            }
+
+            Self::walk_dirs(root, &path, include, exclude, ignore_dirs, results);
+        }
+    }
+
+    /// package.json 中指向其他 workspace 包的依赖
+    fn internal_dependencies(
+        json: &serde_json::Value,
+        package_map: &BTreeMap<String, PathBuf>,
+    ) -> Vec<WorkspaceDependency> {
+        let mut dependencies = Vec::new();
+
+        for field in DEPENDENCY_FIELDS {
+            let Some(entries) = json.get(field).and_then(|v| v.as_object()) else {
+                continue;
+            };
+            for (name, spec) in entries {
+                if package_map.contains_key(name) {
+                    dependencies.push(WorkspaceDependency {
+                        name: name.clone(),
+                        spec: spec.as_str().unwrap_or("").to_string(),
+                        kind: field.to_string(),
+                    });
+                }
+            }
+        }
+
+        dependencies
+    }
+}
+
+/// 去掉模式的 `./` 前缀和末尾的 `/`
+fn normalize_pattern(pattern: &str) -> String {
+    let pattern = pattern.trim_start_matches("./").trim_end_matches('/');
+    pattern.replace('\\', "/")
+}
+
+fn relative_path(path: &Path, root: &Path) -> String {
+    path.strip_prefix(root)
+        .unwrap_or(path)
+        .to_string_lossy()
+        .replace('\\', "/")
+}
+
+fn read_package_json(dir: &Path) -> Option<serde_json::Value> {
+    let content = fs::read_to_string(dir.join("package.json")).ok()?;
+    serde_json::from_str(&content).ok()
+}
+
+/// YAML 标量值转为字符串（版本号可能被写成数字）
+fn scalar_map(entries: BTreeMap<String, serde_yaml::Value>) -> BTreeMap<String, String> {
+    entries
+        .into_iter()
+        .filter_map(|(name, value)| {
+            let version = match value {
+                serde_yaml::Value::String(s) => s,
+                serde_yaml::Value::Number(n) => n.to_string(),
+                serde_yaml::Value::Bool(b) => b.to_string(),
+                _ => return None,
+            };
+            Some((name, version))
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn write_package(root: &Path, dir: &str, json: &str) {
+        fs::create_dir_all(root.join(dir)).unwrap();
+        fs::write(root.join(dir).join("package.json"), json).unwrap();
+    }
+
+    #[test]
+    fn test_pnpm_workspace_with_negation_and_catalogs() {
+        let root = Path::new("/tmp/test_workspace_pnpm");
+        fs::remove_dir_all(root).ok();
+        fs::create_dir_all(root).unwrap();
+
+        fs::write(
+            root.join("pnpm-workspace.yaml"),
+            r#"
+# 包列表
+packages: ['packages/**', "apps/*-web", '!**/fixtures/**']
+
+catalog:
+  vue: ^3.4.0
+  typescript: 5.4
+
+catalogs:
+  legacy:
+    vue: ^2.7.0
+"#,
+        )
+        .unwrap();
+        write_package(root, "packages/core", r#"{ "name": "@acme/core", "version": "1.2.0" }"#);
+        write_package(
+            root,
+            "packages/ui/button",
+            r#"{ "name": "@acme/button", "version": "0.3.1",
+                 "dependencies": { "@acme/core": "workspace:*", "vue": "catalog:" } }"#,
+        );
+        write_package(root, "packages/core/fixtures/demo", r#"{ "name": "demo" }"#);
+        write_package(
+            root,
+            "apps/admin-web",
+            r#"{ "name": "admin", "private": true,
+                 "devDependencies": { "@acme/button": "workspace:^" } }"#,
+        );
+        write_package(root, "apps/admin-api", r#"{ "name": "admin-api" }"#);
+        fs::create_dir_all(root.join("packages/empty")).unwrap();
+
+        let info = WorkspaceInfo::discover(&root.join("apps/admin-web"), &[])
+            .unwrap()
+            .unwrap();
+        assert_eq!(info.root, root);
+
+        let names: Vec<&str> = info.packages.iter().map(|p| p.name.as_str()).collect();
+        assert_eq!(names, vec!["admin", "@acme/core", "@acme/button"]);
+
+        let button = info.package("@acme/button").unwrap();
+        assert_eq!(button.version.as_deref(), Some("0.3.1"));
+        assert_eq!(button.path, "packages/ui/button");
+        assert_eq!(
+            button.dependencies,
+            vec![WorkspaceDependency {
+                name: "@acme/core".to_string(),
+                spec: "workspace:*".to_string(),
+                kind: "dependencies".to_string(),
+            }]
+        );
+        assert!(info.package("admin").unwrap().private);
+        assert_eq!(info.dependents("@acme/button")[0].name, "admin");
+
+        assert_eq!(info.resolve_catalog("vue", "catalog:"), Some("^3.4.0"));
+        assert_eq!(info.resolve_catalog("vue", "catalog:legacy"), Some("^2.7.0"));
+        assert_eq!(info.resolve_catalog("typescript", "catalog:default"), Some("5.4"));
+        assert_eq!(info.resolve_catalog("vue", "^3.0.0"), None);
+
+        let file = root.join("packages/ui/button/src/index.ts");
+        assert_eq!(info.package_for_file(&file).unwrap().name, "@acme/button");
+
+        fs::remove_dir_all(root).ok();
+    }
+
+    #[test]
+    fn test_package_json_workspaces() {
+        let root = Path::new("/tmp/test_workspace_npm");
+        fs::remove_dir_all(root).ok();
+        fs::create_dir_all(root).unwrap();
+
+        fs::write(
+            root.join("package.json"),
+            r#"{ "name": "root", "workspaces": { "packages": ["./libs/*/", "!libs/legacy"] } }"#,
+        )
+        .unwrap();
+        write_package(root, "libs/shared", r#"{ "name": "shared" }"#);
+        write_package(root, "libs/legacy", r#"{ "name": "legacy" }"#);
+        // 指向上级目录的符号链接不会导致无限递归
+        #[cfg(unix)]
+        std::os::unix::fs::symlink(root, root.join("libs/shared/loop")).unwrap();
+
+        let info = WorkspaceInfo::load(root, &[]).unwrap();
+        assert_eq!(info.package_map.keys().collect::<Vec<_>>(), vec!["shared"]);
+        assert_eq!(info.package_paths, vec![root.join("libs/shared")]);
+
+        fs::remove_dir_all(root).ok();
+    }
+}